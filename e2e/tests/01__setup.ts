@@ -110,6 +110,7 @@ describe("Setup", () => {
                         payer: payer.publicKey,
                     },
                     {
+                        ...swapLayerSdk.TEST_PEER_PARAMS,
                         chain: toChainId(chain),
                         address: Array.from(toUniversal(chain, cfg.swapLayer).toUint8Array()),
                         relayParams: cfg.relayParams,
@@ -2,7 +2,8 @@ use std::ops::Deref;
 
 use crate::{
     error::SwapLayerError,
-    state::{Custodian, Peer},
+    events::FeesCollected,
+    state::{Custodian, FeeVault, OutputSwapDeadlinePolicy, Peer, RecipientConfig},
     utils::{
         self,
         jupiter_v6::{self, cpi::SharedAccountsRouteArgs, JUPITER_V6_PROGRAM_ID},
@@ -10,20 +11,19 @@ use crate::{
     },
 };
 use anchor_lang::{prelude::*, system_program};
-use anchor_spl::{associated_token, token, token_interface};
+use anchor_spl::{associated_token, token, token_2022::spl_token_2022, token_interface};
 use common::{
     admin::utils::{
         assistant::{self, only_authorized},
         ownable::only_owner,
     },
-    wormhole_io::TypePrefixedPayload,
     USDC_MINT,
 };
 use swap_layer_messages::{
-    messages::SwapMessageV1,
-    types::{OutputSwap, OutputToken, RedeemMode, SwapType},
+    messages::SwapMessageV1Ref,
+    types::{OutputSwapRef, OutputTokenRef, RedeemModeRef, SwapTypeRef, UniversalAddress},
 };
-use token_router::state::PreparedFill;
+use token_router::state::{FillType, PreparedFill};
 
 #[derive(Accounts)]
 pub struct Usdc<'info> {
@@ -162,6 +162,212 @@ impl<'info> Deref for RegisteredPeer<'info> {
     }
 }
 
+/// Registered peer that an outbound transfer is sent to.
+#[derive(Accounts)]
+pub struct OutboundTarget<'info> {
+    /// Peer used to determine whether assets are sent to a valid destination. The registered peer
+    /// also acts as the authority over staged custody token accounts.
+    ///
+    /// Ordinarily we could consider the authority to be the staged outbound account itself. But
+    /// because this account can be signed for outside of this program (either keypair or PDA), the
+    /// token account would then be out of this program's control.
+    pub peer: RegisteredPeer<'info>,
+
+    /// Native token price feed used to compute the relaying fee. This account is required for
+    /// relays if the target peer has a price feed configured.
+    ///
+    /// CHECK: This account must equal the price feed configured for the target peer.
+    pub native_token_price_feed: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> OutboundTarget<'info> {
+    /// Verify that the peer is registered for the target chain and that the recipient is a valid
+    /// address on the target network.
+    pub fn verify_destination(&self, target_chain: u16, recipient: [u8; 32]) -> Result<()> {
+        require_eq!(
+            target_chain,
+            self.seeds.chain,
+            SwapLayerError::InvalidTargetChain,
+        );
+
+        UniversalAddress::from(recipient)
+            .validate(self.is_evm())
+            .map_err(|_| SwapLayerError::InvalidRecipient)?;
+
+        Ok(())
+    }
+
+    pub fn native_token_price_feed(&self) -> Option<&AccountInfo<'info>> {
+        self.native_token_price_feed.as_ref().map(AsRef::as_ref)
+    }
+}
+
+impl<'info> Deref for OutboundTarget<'info> {
+    type Target = Account<'info, Peer>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.peer
+    }
+}
+
+/// Source mint of an outbound transfer, whose Token-2022 extensions must be allowed by the
+/// custodian's extension policy. The source token program is verified by the token accounts using
+/// this mint.
+#[derive(Accounts)]
+pub struct OutboundSource<'info> {
+    pub custodian: CheckedCustodian<'info>,
+
+    /// Mint can either be USDC or whichever mint is used to swap into USDC. This mint must be mutable
+    /// if it withholds transfer fees, which are harvested before closing token accounts.
+    #[account(
+        constraint = {
+            utils::token_2022::verify_mint_extensions(
+                &src_mint.to_account_info(),
+                &custodian.token_2022_extension_policy,
+            )?;
+
+            true
+        }
+    )]
+    pub src_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+}
+
+/// Fee vault for USDC, which collects relaying fees and swap dust from inbound transfers.
+#[derive(Accounts)]
+pub struct UsdcFeeVault<'info> {
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            USDC_MINT.as_ref(),
+        ],
+        bump = fee_vault.seeds.bump,
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    /// CHECK: Mutable, seeds must be \["fee-vault-custody", fee_vault.key()\].
+    #[account(
+        mut,
+        seeds = [
+            crate::FEE_VAULT_CUSTODY_TOKEN_SEED_PREFIX,
+            fee_vault.key().as_ref(),
+        ],
+        bump = fee_vault.custody_token_bump,
+    )]
+    pub custody_token: UncheckedAccount<'info>,
+}
+
+impl<'info> UsdcFeeVault<'info> {
+    pub fn collect(&mut self, chain: u16, relaying_fee: u64, dust: u64, self_redemption: bool) {
+        collect_fees(
+            &mut self.fee_vault,
+            chain,
+            relaying_fee,
+            dust,
+            self_redemption,
+        )
+    }
+}
+
+impl<'info> Deref for UsdcFeeVault<'info> {
+    type Target = Account<'info, FeeVault>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.fee_vault
+    }
+}
+
+/// Record fees transferred to the fee vault's custody token account. The peer chain is logged so
+/// that fees can be reconciled per corridor.
+pub(crate) fn collect_fees(
+    fee_vault: &mut Account<FeeVault>,
+    chain: u16,
+    relaying_fee: u64,
+    dust: u64,
+    self_redemption: bool,
+) {
+    if relaying_fee == 0 && dust == 0 {
+        return;
+    }
+
+    fee_vault.collect(relaying_fee, dust, self_redemption);
+
+    emit!(FeesCollected {
+        fee_vault: fee_vault.key(),
+        chain,
+        relaying_fee,
+        dust,
+        self_redemption,
+    });
+}
+
+/// Accounts used to pay the relaying fee from a USDC token account.
+pub struct RelayingFeeAccounts<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub src_token: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub fee_vault_custody_token: AccountInfo<'info>,
+    pub relayer_fee_token: Option<AccountInfo<'info>>,
+}
+
+/// Split the relaying fee between the relayer (if it provided a token account) and the protocol,
+/// transferring each share from the source token account. Returns the protocol's share.
+pub(crate) fn transfer_relaying_fee(
+    accounts: RelayingFeeAccounts,
+    signer_seeds: &[&[u8]],
+    relaying_fee: u64,
+    protocol_share: u32,
+) -> Result<u64> {
+    let RelayingFeeAccounts {
+        token_program,
+        src_token,
+        authority,
+        fee_vault_custody_token,
+        relayer_fee_token,
+    } = accounts;
+
+    let (protocol_fee, relayer_fee) = match &relayer_fee_token {
+        Some(_) => utils::relayer_fees::split_relaying_fee(relaying_fee, protocol_share),
+        None => (relaying_fee, 0),
+    };
+
+    // Transfer the relayer's share to the relayer.
+    if let Some(relayer_fee_token) = relayer_fee_token {
+        if relayer_fee > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    token::Transfer {
+                        from: src_token.clone(),
+                        to: relayer_fee_token,
+                        authority: authority.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                relayer_fee,
+            )?;
+        }
+    }
+
+    // Transfer the protocol's share to the fee vault.
+    if protocol_fee > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                token::Transfer {
+                    from: src_token,
+                    to: fee_vault_custody_token,
+                    authority,
+                },
+                &[signer_seeds],
+            ),
+            protocol_fee,
+        )?;
+    }
+
+    Ok(protocol_fee)
+}
+
 /// Prepared fill account with associated peer.
 #[derive(Accounts)]
 pub struct ConsumeSwapLayerFill<'info> {
@@ -170,9 +376,6 @@ pub struct ConsumeSwapLayerFill<'info> {
     #[account(
         mut,
         constraint = {
-            let swap_msg = SwapMessageV1::read_slice(&fill.redeemer_message)
-                .map_err(|_| SwapLayerError::InvalidSwapMessage)?;
-
             require_eq!(
                 source_peer.seeds.chain,
                 fill.source_chain,
@@ -206,8 +409,14 @@ pub struct ConsumeSwapLayerFill<'info> {
 }
 
 impl<'info> ConsumeSwapLayerFill<'info> {
-    pub fn read_message_unchecked(&self) -> SwapMessageV1 {
-        SwapMessageV1::read_slice(&self.fill.redeemer_message).unwrap()
+    /// Parses and validates the swap message, returning a borrowed view of it. Nothing is copied
+    /// out of the prepared fill. Instruction handlers should read the message once and pass this
+    /// view to the checks below.
+    pub fn read_message(&self) -> Result<SwapMessageV1Ref<'_>> {
+        SwapMessageV1Ref::parse(&self.fill.redeemer_message).map_err(|err| {
+            msg!("Failed to decode swap message: {}", err);
+            error!(SwapLayerError::from(err))
+        })
     }
 
     pub fn prepared_fill_key(&self) -> Pubkey {
@@ -240,65 +449,146 @@ impl<'info> ConsumeSwapLayerFill<'info> {
             .map(|token| token.amount)
     }
 
+    /// If the output token is not USDC, the swap time limit must be exceeded in order to redeem
+    /// USDC instead of performing the swap.
+    pub fn verify_swap_time_limit(&self, output_token: OutputTokenRef) -> Result<()> {
+        match output_token {
+            OutputTokenRef::Usdc => Ok(()),
+            OutputTokenRef::Gas(_) | OutputTokenRef::Other { .. } => {
+                require!(
+                    self.is_swap_time_limit_exceeded()?,
+                    SwapLayerError::SwapTimeLimitNotExceeded
+                );
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns whether the source peer's swap time limit for this fill's type has elapsed since the
+    /// fill was prepared.
+    pub fn is_swap_time_limit_exceeded(&self) -> Result<bool> {
+        let time_diff = Clock::get()?
+            .unix_timestamp
+            .saturating_sub(self.fill.timestamp);
+        let swap_time_limit = &self.source_peer.relay_params.swap_time_limit;
+
+        let limit = match self.fill.fill_type {
+            FillType::FastFill => swap_time_limit.fast_limit,
+            FillType::WormholeCctpDeposit => swap_time_limit.finalized_limit,
+            FillType::Unset => return err!(SwapLayerError::UnsupportedFillType),
+        };
+
+        Ok(time_diff >= i64::from(limit))
+    }
+
+    /// During the source peer's exclusivity window, only registered relayers can execute relay
+    /// completions. The recipient can always redeem the transfer himself.
+    pub fn verify_relayer_exclusivity(
+        &self,
+        payer: &Pubkey,
+        recipient: &Pubkey,
+        is_registered_relayer: bool,
+    ) -> Result<()> {
+        let exclusivity_window = self.source_peer.relayer_exclusivity_window;
+
+        if payer == recipient || is_registered_relayer || exclusivity_window == 0 {
+            return Ok(());
+        }
+
+        let time_diff = Clock::get()?
+            .unix_timestamp
+            .saturating_sub(self.fill.timestamp);
+        require!(
+            time_diff >= i64::from(exclusivity_window),
+            SwapLayerError::RelayerExclusivityWindowActive
+        );
+
+        Ok(())
+    }
+
+    /// Returns the deadline of an output swap after applying the source peer's deadline policy. If
+    /// there is no deadline, `None` is returned.
+    pub fn output_swap_deadline(&self, deadline: u32) -> Result<Option<i64>> {
+        let fill_timestamp = self.fill.timestamp;
+
+        if deadline == 0 {
+            match self.source_peer.output_swap_deadline_policy {
+                OutputSwapDeadlinePolicy::Accept => Ok(None),
+                OutputSwapDeadlinePolicy::Reject => {
+                    err!(SwapLayerError::OutputSwapDeadlineRequired)
+                }
+                OutputSwapDeadlinePolicy::DefaultMaxAge(max_age) => {
+                    Ok(fill_timestamp.saturating_add(max_age.into()).into())
+                }
+            }
+        } else {
+            let deadline = i64::from(deadline);
+            let max_horizon = self.source_peer.max_output_swap_deadline_horizon;
+
+            require!(
+                max_horizon == 0 || deadline <= fill_timestamp.saturating_add(max_horizon.into()),
+                SwapLayerError::OutputSwapDeadlineTooFar
+            );
+
+            Ok(deadline.into())
+        }
+    }
+
     /// Ensure that the output token is a swap token for Jupiter V6. If swap is not encoded, we
     /// allow the recipient to perform the swap himself in a direct transfer.
     ///
     /// NOTE: The recipient must be equal to the payer if OutputToken::Usdc! This check is not
     /// performed here, but should be performed with the account context composing with this
     /// composite.
-    pub fn is_valid_output_swap(
-        &self,
-        dst_mint: &InterfaceAccount<'info, token_interface::Mint>,
-    ) -> Result<bool> {
-        let swap_msg = self.read_message_unchecked();
-
-        let (expected_dst_mint, swap) = match swap_msg.output_token {
-            OutputToken::Usdc => {
+    pub fn verify_output_swap(&self, swap_msg: &SwapMessageV1Ref, dst_mint: &Pubkey) -> Result<()> {
+        let (expected_dst_mint, swap) = match swap_msg.output_token() {
+            OutputTokenRef::Usdc => {
                 require!(
-                    matches!(swap_msg.redeem_mode, RedeemMode::Direct),
+                    matches!(swap_msg.redeem_mode(), RedeemModeRef::Direct),
                     SwapLayerError::InvalidRedeemMode,
                 );
 
                 (Default::default(), None)
             }
-            OutputToken::Gas(swap) => (token::spl_token::native_mint::id(), swap.into()),
-            OutputToken::Other { address, swap } => (address.into(), swap.into()),
+            OutputTokenRef::Gas(swap) => (token::spl_token::native_mint::id(), swap.into()),
+            OutputTokenRef::Other { address, swap } => (address.into(), swap.into()),
         };
 
         if let Some(swap) = swap {
             // Verify the address matches the destination mint.
             require_keys_eq!(
-                dst_mint.key(),
+                *dst_mint,
                 expected_dst_mint,
                 SwapLayerError::InvalidDestinationMint
             );
 
-            let OutputSwap {
+            let OutputSwapRef {
                 limit_amount,
                 deadline,
                 swap_type,
             } = swap;
 
             require!(
-                matches!(swap_type, SwapType::JupiterV6(_)),
+                matches!(swap_type, SwapTypeRef::JupiterV6(_)),
                 SwapLayerError::InvalidSwapType,
             );
 
             // Check the deadline for the swap. There may not be a deadline check with the
             // dex that this instruction composes with, so we will check it here.
-            //
-            // TODO: Do we accept deadline == 0?
-            require!(
-                deadline == 0 || Clock::get().unwrap().unix_timestamp <= i64::from(deadline),
-                SwapLayerError::SwapPastDeadline,
-            );
+            if let Some(deadline) = self.output_swap_deadline(deadline)? {
+                require!(
+                    Clock::get()?.unix_timestamp <= deadline,
+                    SwapLayerError::SwapPastDeadline,
+                );
+            }
 
             // Just in case the encoded limit amount exceeds u64, we have nothing to do if
             // this message were misconfigured.
             u64::try_from(limit_amount).map_err(|_| SwapLayerError::InvalidLimitAmount)?;
         }
 
-        Ok(true)
+        Ok(())
     }
 }
 
@@ -315,7 +605,6 @@ pub struct CompleteSwap<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    #[account(constraint = consume_swap_layer_fill.is_valid_output_swap(&dst_mint)?)]
     consume_swap_layer_fill: ConsumeSwapLayerFill<'info>,
 
     /// CHECK: Seeds must be \["swap-authority", prepared_fill.key()\].
@@ -350,24 +639,31 @@ pub struct CompleteSwap<'info> {
     )]
     pub dst_swap_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
-    /// CHECK: In case the exact in swap does not use all tokens, we send residual back to this
-    /// token account.
-    #[account(
-        mut,
-        address = consume_swap_layer_fill.custodian.fee_recipient_token,
-    )]
-    pub fee_recipient_token: UncheckedAccount<'info>,
+    /// In case the exact in swap does not use all tokens, we send residual to this fee vault.
+    pub fee_vault: UsdcFeeVault<'info>,
 
     /// This account must be verified as the source mint for the swap.
     pub usdc: Usdc<'info>,
 
-    /// CHECK: This account must be verified as the destination mint for the swap.
-    #[account(constraint = usdc.key() != dst_mint.key() @ SwapLayerError::SameMint)]
+    /// CHECK: This account must be verified as the destination mint for the swap. This mint must be
+    /// mutable if it withholds transfer fees, which are harvested before closing the destination
+    /// swap token account.
+    #[account(
+        constraint = usdc.key() != dst_mint.key() @ SwapLayerError::SameMint,
+        constraint = {
+            utils::token_2022::verify_mint_extensions(
+                &dst_mint.to_account_info(),
+                &consume_swap_layer_fill.custodian.token_2022_extension_policy,
+            )?;
+
+            true
+        }
+    )]
     pub dst_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     pub token_program: Program<'info, token::Token>,
     pub dst_token_program: Interface<'info, token_interface::TokenInterface>,
-    associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -377,7 +673,7 @@ pub struct HandleCompleteSwap<'ctx, 'info> {
     pub swap_authority: &'ctx AccountInfo<'info>,
     pub src_swap_token: &'ctx Account<'info, token::TokenAccount>,
     pub dst_swap_token: &'ctx InterfaceAccount<'info, token_interface::TokenAccount>,
-    pub fee_recipient_token: &'ctx UncheckedAccount<'info>,
+    pub fee_vault_custody_token: &'ctx UncheckedAccount<'info>,
     pub dst_mint: &'ctx InterfaceAccount<'info, token_interface::Mint>,
     pub token_program: &'ctx Program<'info, token::Token>,
     pub dst_token_program: &'ctx Interface<'info, token_interface::TokenInterface>,
@@ -385,7 +681,17 @@ pub struct HandleCompleteSwap<'ctx, 'info> {
 }
 
 impl<'info> CompleteSwap<'info> {
-    pub fn consume_prepared_fill(&mut self) -> Result<u64> {
+    /// Parses the swap message and verifies that its output swap can be executed into the
+    /// destination mint.
+    pub fn read_and_verify_message(&self) -> Result<SwapMessageV1Ref<'_>> {
+        let swap_msg = self.consume_swap_layer_fill.read_message()?;
+        self.consume_swap_layer_fill
+            .verify_output_swap(&swap_msg, &self.dst_mint.key())?;
+
+        Ok(swap_msg)
+    }
+
+    pub fn consume_prepared_fill(&self) -> Result<u64> {
         self.consume_swap_layer_fill
             .consume_prepared_fill(self.src_swap_token.as_ref().as_ref(), &self.token_program)
     }
@@ -399,6 +705,7 @@ impl<'info> Deref for CompleteSwap<'info> {
     }
 }
 
+/// Returns the USDC residual transferred to the fee vault.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn complete_swap_jup_v6<'info>(
     complete_swap: &CompleteSwap<'info>,
@@ -406,18 +713,19 @@ pub(crate) fn complete_swap_jup_v6<'info>(
     remaining_accounts: &'info [AccountInfo<'info>],
     ix_data: Vec<u8>,
     in_amount: u64,
-    swap_message: SwapMessageV1,
+    expected_recipient: UniversalAddress,
+    output_token: OutputTokenRef,
     recipient: &AccountInfo<'info>,
     recipient_token: &AccountInfo<'info>,
     gas_dropoff: Option<u64>,
-) -> Result<()> {
+) -> Result<u64> {
     let CompleteSwap {
         payer,
         consume_swap_layer_fill,
         authority,
         src_swap_token,
         dst_swap_token,
-        fee_recipient_token,
+        fee_vault,
         dst_mint,
         token_program,
         dst_token_program,
@@ -432,7 +740,7 @@ pub(crate) fn complete_swap_jup_v6<'info>(
             swap_authority: authority,
             src_swap_token,
             dst_swap_token,
-            fee_recipient_token,
+            fee_vault_custody_token: &fee_vault.custody_token,
             dst_mint,
             token_program,
             dst_token_program,
@@ -443,7 +751,8 @@ pub(crate) fn complete_swap_jup_v6<'info>(
         remaining_accounts,
         ix_data,
         in_amount,
-        swap_message,
+        expected_recipient,
+        output_token,
         RecipientAccounts {
             recipient,
             recipient_token,
@@ -453,11 +762,164 @@ pub(crate) fn complete_swap_jup_v6<'info>(
     )
 }
 
+/// Verify that the recipient token account can receive inbound tokens. The recipient's associated
+/// token account is always allowed. Any other token account must be owned by the recipient, who
+/// must have opted in via its [RecipientConfig].
+///
+/// An existing token account's mint and owner are always checked. Only the associated token
+/// account may not exist yet, in which case it is created with the expected mint and owner.
+pub(crate) fn verify_recipient_token(
+    recipient_token: &AccountInfo,
+    recipient: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+    recipient_config: Option<&RecipientConfig>,
+) -> Result<bool> {
+    let is_associated_token = recipient_token.key()
+        == associated_token::get_associated_token_address_with_program_id(
+            recipient,
+            mint,
+            token_program_id,
+        );
+
+    if is_associated_token {
+        if recipient_token.data_is_empty() {
+            return Ok(true);
+        }
+    } else {
+        require!(
+            RecipientConfig::allows_any_token_account(recipient_config),
+            SwapLayerError::RecipientTokenNotAllowed
+        );
+    }
+
+    require_keys_eq!(
+        *recipient_token.owner,
+        *token_program_id,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let token =
+        token_interface::TokenAccount::try_deserialize(&mut &recipient_token.data.borrow()[..])?;
+    require_keys_eq!(token.owner, *recipient, ErrorCode::ConstraintTokenOwner);
+    require_keys_eq!(token.mint, *mint, ErrorCode::ConstraintTokenMint);
+
+    Ok(true)
+}
+
+/// Create the recipient's associated token account if the recipient token account does not exist
+/// yet. Non-associated token accounts must already exist.
+pub(crate) fn create_recipient_token_if_needed<'info>(
+    associated_token_program: &AccountInfo<'info>,
+    accounts: associated_token::Create<'info>,
+) -> Result<()> {
+    if accounts.associated_token.data_is_empty() {
+        associated_token::create(CpiContext::new(
+            associated_token_program.to_account_info(),
+            accounts,
+        ))?;
+    }
+
+    Ok(())
+}
+
+pub struct WsolGasDropoffAccounts<'info> {
+    pub recipient_wsol_token: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> WsolGasDropoffAccounts<'info> {
+    /// Returns the accounts required to deliver the gas dropoff as WSOL if the recipient prefers
+    /// it (see [RecipientConfig]).
+    pub fn try_new(
+        recipient_config: Option<&RecipientConfig>,
+        recipient_wsol_token: Option<&AccountInfo<'info>>,
+        token_program: &AccountInfo<'info>,
+    ) -> Result<Option<Self>> {
+        if !RecipientConfig::wants_gas_dropoff_as_wsol(recipient_config) {
+            return Ok(None);
+        }
+
+        match recipient_wsol_token {
+            Some(recipient_wsol_token) => Ok(Some(Self {
+                recipient_wsol_token: recipient_wsol_token.to_account_info(),
+                token_program: token_program.to_account_info(),
+            })),
+            None => err!(SwapLayerError::RecipientWsolTokenRequired),
+        }
+    }
+}
+
+pub struct GasDropoffAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub recipient: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    /// If specified, the gas dropoff is wrapped into the recipient's WSOL token account.
+    pub wsol: Option<WsolGasDropoffAccounts<'info>>,
+}
+
+/// Transfer the gas dropoff from the payer to the recipient. If WSOL accounts are specified, the
+/// lamports are wrapped into the recipient's WSOL associated token account.
+///
+/// The WSOL account is created when the recipient opts in (see [RecipientConfig]) because relaying
+/// fees do not account for its rent. If the recipient closed this account, the gas dropoff is
+/// transferred to the recipient as lamports instead, so the recipient always receives the full gas
+/// dropoff.
+pub(crate) fn transfer_gas_dropoff(accounts: GasDropoffAccounts, gas_dropoff: u64) -> Result<()> {
+    if gas_dropoff == 0 {
+        return Ok(());
+    }
+
+    let GasDropoffAccounts {
+        payer,
+        recipient,
+        system_program,
+        wsol,
+    } = accounts;
+
+    match wsol {
+        Some(WsolGasDropoffAccounts {
+            recipient_wsol_token,
+            token_program,
+        }) if !recipient_wsol_token.data_is_empty() => {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program,
+                    system_program::Transfer {
+                        from: payer,
+                        to: recipient_wsol_token.to_account_info(),
+                    },
+                ),
+                gas_dropoff,
+            )?;
+
+            token::sync_native(CpiContext::new(
+                token_program,
+                token::SyncNative {
+                    account: recipient_wsol_token,
+                },
+            ))
+        }
+        _ => system_program::transfer(
+            CpiContext::new(
+                system_program,
+                system_program::Transfer {
+                    from: payer,
+                    to: recipient,
+                },
+            ),
+            gas_dropoff,
+        ),
+    }
+}
+
 pub struct RecipientAccounts<'ctx, 'info> {
     pub recipient: &'ctx AccountInfo<'info>,
     pub recipient_token: &'ctx AccountInfo<'info>,
 }
 
+/// Swaps USDC for the destination token via Jupiter V6. Any USDC left in the source swap token
+/// account after the swap is transferred to the fee vault and this amount is returned.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
     accounts: HandleCompleteSwap<'ctx, 'info>,
@@ -466,29 +928,24 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
     remaining_accounts: &'info [AccountInfo<'info>],
     ix_data: Vec<u8>,
     in_amount: u64,
-    swap_message: SwapMessageV1,
+    expected_recipient: UniversalAddress,
+    output_token: OutputTokenRef,
     recipient: Option<RecipientAccounts<'ctx, 'info>>,
     gas_dropoff: Option<u64>,
-) -> Result<()> {
+) -> Result<u64> {
     let HandleCompleteSwap {
         payer,
         consume_swap_layer_fill,
         swap_authority,
         src_swap_token,
         dst_swap_token,
-        fee_recipient_token,
+        fee_vault_custody_token,
         dst_mint,
         token_program,
         dst_token_program,
         system_program,
     } = accounts;
 
-    let SwapMessageV1 {
-        recipient: expected_recipient,
-        output_token,
-        redeem_mode: _,
-    } = swap_message;
-
     let recipient_key = recipient
         .as_ref()
         .map(|accts: &RecipientAccounts<'ctx, 'info>| accts.recipient.key());
@@ -501,7 +958,7 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
     }
 
     let (limit_and_params, is_native) = match output_token {
-        OutputToken::Usdc => match recipient_key {
+        OutputTokenRef::Usdc => match recipient_key {
             Some(recipient_key) => {
                 // In this case, we require that the signer of the instruction (the payer) is the
                 // recipient himself.
@@ -511,18 +968,18 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
             }
             None => return err!(SwapLayerError::InvalidOutputToken),
         },
-        OutputToken::Gas(OutputSwap {
+        OutputTokenRef::Gas(OutputSwapRef {
             deadline: _,
             limit_amount,
-            swap_type: SwapType::JupiterV6(swap_params),
+            swap_type: SwapTypeRef::JupiterV6(swap_params),
         }) => ((limit_amount.try_into().unwrap(), swap_params).into(), true),
-        OutputToken::Other {
+        OutputTokenRef::Other {
             address: _,
             swap:
-                OutputSwap {
+                OutputSwapRef {
                     deadline: _,
                     limit_amount,
-                    swap_type: SwapType::JupiterV6(swap_params),
+                    swap_type: SwapTypeRef::JupiterV6(swap_params),
                 },
         } => (
             (limit_amount.try_into().unwrap(), swap_params).into(),
@@ -542,33 +999,13 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
         JupiterV6SharedAccountsRoute::set_up(remaining_accounts, &ix_data[..])?;
 
     // Verify remaining accounts.
-    {
-        require_keys_eq!(
-            shared_accounts_route.transfer_authority.key(),
-            swap_authority.key(),
-            SwapLayerError::InvalidSwapAuthority
-        );
-        require_keys_eq!(
-            shared_accounts_route.src_custody_token.key(),
-            src_swap_token.key(),
-            SwapLayerError::InvalidSourceSwapToken
-        );
-        require_keys_eq!(
-            shared_accounts_route.dst_custody_token.key(),
-            dst_swap_token.key(),
-            SwapLayerError::InvalidDestinationSwapToken
-        );
-        require_keys_eq!(
-            shared_accounts_route.src_mint.key(),
-            common::USDC_MINT,
-            SwapLayerError::InvalidSourceMint
-        );
-        require_keys_eq!(
-            shared_accounts_route.dst_mint.key(),
-            dst_mint.key(),
-            SwapLayerError::InvalidDestinationMint
-        );
-    }
+    shared_accounts_route.verify_swap_accounts(
+        &swap_authority.key(),
+        &src_swap_token.key(),
+        &dst_swap_token.key(),
+        &common::USDC_MINT,
+        &dst_mint.key(),
+    )?;
 
     let limit_amount = match limit_and_params {
         // If the limit amount is some value (meaning that the OutputToken is Gas or Other), we
@@ -623,7 +1060,7 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
         limit_amount,
     )?;
 
-    // Transfer residual to the fee recipient token if there is any.
+    // Transfer residual to the fee vault if there is any.
     if usdc_dust > 0 {
         msg!("USDC dust: {}", usdc_dust);
 
@@ -632,7 +1069,7 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
                 token_program.to_account_info(),
                 token::Transfer {
                     from: src_swap_token.to_account_info(),
-                    to: fee_recipient_token.to_account_info(),
+                    to: fee_vault_custody_token.to_account_info(),
                     authority: swap_authority.to_account_info(),
                 },
                 &[swap_authority_seeds],
@@ -699,20 +1136,26 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
                 );
             }
 
-            // Transfer destination tokens to recipient.
-            token_interface::transfer_checked(
-                CpiContext::new_with_signer(
-                    dst_token_program.to_account_info(),
-                    token_interface::TransferChecked {
-                        from: dst_swap_token.to_account_info(),
-                        to: recipient_token.to_account_info(),
-                        authority: swap_authority.to_account_info(),
-                        mint: dst_mint.to_account_info(),
-                    },
-                    &[swap_authority_seeds],
-                ),
+            // Transfer destination tokens to recipient. Token-2022 mints with the transfer hook
+            // extension require extra accounts, which are resolved from the remaining accounts
+            // (appended after the Jupiter V6 accounts).
+            spl_token_2022::onchain::invoke_transfer_checked(
+                &dst_token_program.key(),
+                dst_swap_token.to_account_info(),
+                dst_mint.to_account_info(),
+                recipient_token.to_account_info(),
+                swap_authority.to_account_info(),
+                remaining_accounts,
                 amount_out,
                 dst_mint.decimals,
+                &[swap_authority_seeds],
+            )?;
+
+            // Fees withheld by the destination mint must be harvested before closing the token account.
+            utils::token_2022::harvest_withheld_tokens_to_mint(
+                &dst_token_program.to_account_info(),
+                &dst_mint.to_account_info(),
+                &dst_swap_token.to_account_info(),
             )?;
 
             // Close the destination swap token account.
@@ -744,7 +1187,7 @@ pub(crate) fn handle_complete_swap_jup_v6<'ctx, 'info>(
     }
 
     // Done.
-    Ok(())
+    Ok(usdc_dust)
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -856,6 +1299,45 @@ impl<'info> JupiterV6SharedAccountsRoute<'info> {
         Ok((accounts, args, cpi_account_infos[0].key()))
     }
 
+    /// Verify that the route swaps between the expected swap token accounts, which are owned by
+    /// the swap authority.
+    pub fn verify_swap_accounts(
+        &self,
+        swap_authority: &Pubkey,
+        src_swap_token: &Pubkey,
+        dst_swap_token: &Pubkey,
+        src_mint: &Pubkey,
+        dst_mint: &Pubkey,
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.transfer_authority.key(),
+            *swap_authority,
+            SwapLayerError::InvalidSwapAuthority
+        );
+        require_keys_eq!(
+            self.src_custody_token.key(),
+            *src_swap_token,
+            SwapLayerError::InvalidSourceSwapToken
+        );
+        require_keys_eq!(
+            self.dst_custody_token.key(),
+            *dst_swap_token,
+            SwapLayerError::InvalidDestinationSwapToken
+        );
+        require_keys_eq!(
+            self.src_mint.key(),
+            *src_mint,
+            SwapLayerError::InvalidSourceMint
+        );
+        require_keys_eq!(
+            self.dst_mint.key(),
+            *dst_mint,
+            SwapLayerError::InvalidDestinationMint
+        );
+
+        Ok(())
+    }
+
     pub fn swap_exact_in(
         &self,
         args: SharedAccountsRouteArgs,
@@ -1,3 +1,5 @@
+use swap_layer_messages::error::{DecodeError, FieldPath};
+
 #[anchor_lang::error_code]
 pub enum SwapLayerError {
     DummyError = 0x0,
@@ -32,6 +34,20 @@ pub enum SwapLayerError {
     UnsupportedFillType = 0x115,
     SwapTimeLimitNotExceeded = 0x116,
     ImmutableProgram = 0x118,
+    InvalidRelayingFeeShare = 0x119,
+    RelayerExclusivityWindowActive = 0x11a,
+    InvalidOutputSwapDeadlinePolicy = 0x11b,
+    RecipientTokenNotAllowed = 0x11c,
+    RecipientWsolTokenRequired = 0x11d,
+    InvalidSupportedSwapTypes = 0x11e,
+    InvalidLookupTable = 0x11f,
+    LookupTableAccountsRequired = 0x120,
+    TruncatedSwapMessage = 0x121,
+    SwapMessageTrailingBytes = 0x122,
+    InvalidSwapParameters = 0x123,
+    PeerAlreadyMigrated = 0x124,
+    CustodianAlreadyMigrated = 0x125,
+    StagedOutboundAlreadyMigrated = 0x126,
 
     // EVM Execution Param errors
     InvalidBaseFee = 0x200,
@@ -42,15 +58,43 @@ pub enum SwapLayerError {
     InvalidMargin = 0x205,
     EvmGasCalculationFailed = 0x206,
 
+    // Price feed errors
+    InvalidPriceFeed = 0x220,
+    InvalidPriceFeedConfig = 0x221,
+    PriceFeedRequired = 0x222,
+    PriceFeedStale = 0x223,
+    PriceFeedConfidenceTooWide = 0x224,
+
+    // Token-2022
+    Token2022ExtensionNotAllowed = 0x230,
+    TransferFeeCalculationFailed = 0x231,
+
     // Staged outbound
     EitherSenderOrProgramTransferAuthority = 0x240,
     SenderTokenRequired = 0x242,
     SenderRequired = 0x244,
+    PreparedByRequired = 0x246,
     RelayingFeeExceedsMinAmountOut = 0x260,
     ZeroMinAmountOut = 0x262,
     DelegatedAmountMismatch = 0x264,
     ExactInRequired = 0x266,
     InsufficientAmountIn = 0x268,
+    StagedOutboundExpired = 0x26a,
+    StagedOutboundNotExpired = 0x26c,
+    UnsupportedSwapType = 0x26e,
+    InvalidEvmAddress = 0x270,
+    SwapPathTooLong = 0x272,
+    Ed25519InstructionRequired = 0x274,
+    InvalidEd25519Instruction = 0x276,
+    InvalidIntentSigner = 0x278,
+    InvalidIntentMessage = 0x27a,
+    InvalidIntentNonce = 0x27c,
+    IntentExpired = 0x27d,
+    RelayPayloadNotSupported = 0x27e,
+    StagedOutboundAlreadyInitiated = 0x27f,
+
+    // Fee vault
+    InvalidFeeVaultDestination = 0x280,
 
     // Swap
     SwapPastDeadline = 0x300,
@@ -59,6 +103,9 @@ pub enum SwapLayerError {
     InsufficientAmountOut = 0x306,
     InvalidSourceResidual = 0x308,
     SourceResidualMismatch = 0x30a,
+    SwapDeadlineNotExceeded = 0x30e,
+    OutputSwapDeadlineRequired = 0x310,
+    OutputSwapDeadlineTooFar = 0x312,
 
     // Jupiter V6
     #[msg("Jupiter V6 Authority ID must be >= 0 and < 8")]
@@ -83,3 +130,20 @@ pub enum SwapLayerError {
     OwnerOnly = 0x404,
     OwnerOrAssistantOnly = 0x405,
 }
+
+impl From<DecodeError> for SwapLayerError {
+    fn from(err: DecodeError) -> Self {
+        match err {
+            DecodeError::Truncated { .. } => Self::TruncatedSwapMessage,
+            DecodeError::TrailingBytes { .. } => Self::SwapMessageTrailingBytes,
+            DecodeError::UnknownDiscriminant { field, .. } => match field {
+                FieldPath::RedeemMode => Self::InvalidRedeemMode,
+                FieldPath::OutputToken => Self::InvalidOutputToken,
+                FieldPath::SwapType => Self::InvalidSwapType,
+                FieldPath::SwapParameters => Self::InvalidSwapParameters,
+                _ => Self::InvalidSwapMessage,
+            },
+            DecodeError::Io { .. } => Self::InvalidSwapMessage,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when fees are transferred to a fee vault's custody token account.
+#[event]
+#[derive(Debug)]
+pub struct FeesCollected {
+    pub fee_vault: Pubkey,
+
+    /// Wormhole chain ID of the peer network this transfer came from or is going to.
+    pub chain: u16,
+
+    pub relaying_fee: u64,
+    pub dust: u64,
+
+    /// Whether a relayed transfer was redeemed by the recipient himself.
+    pub self_redemption: bool,
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when a fee vault's balance is moved to its destination token account.
+#[event]
+#[derive(Debug)]
+pub struct FeesSwept {
+    pub fee_vault: Pubkey,
+    pub destination_token: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,11 @@
+mod fees_collected;
+pub use fees_collected::*;
+
+mod fees_swept;
+pub use fees_swept::*;
+
+mod staged_outbound_refunded;
+pub use staged_outbound_refunded::*;
+
+mod swap_fallback_triggered;
+pub use swap_fallback_triggered::*;
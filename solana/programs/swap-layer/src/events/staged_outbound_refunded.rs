@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when residual source tokens of an initiated outbound swap are returned to the sender.
+#[event]
+#[derive(Debug)]
+pub struct StagedOutboundRefunded {
+    pub staged_outbound: Pubkey,
+    pub sender: Pubkey,
+    pub sender_token: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
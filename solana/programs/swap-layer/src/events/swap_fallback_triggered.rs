@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when an inbound swap was not executed and the recipient received USDC instead of the
+/// output token. This happens when the source peer's swap time limit has elapsed or when the
+/// recipient's quote cannot meet the limit amount.
+#[event]
+#[derive(Debug)]
+pub struct SwapFallbackTriggered {
+    pub prepared_fill: Pubkey,
+    pub recipient: Pubkey,
+    pub recipient_token: Pubkey,
+
+    /// Amount of USDC delivered to the recipient.
+    pub amount: u64,
+
+    /// Limit amount encoded in the swap message.
+    pub limit_amount: u64,
+
+    /// Recipient's quoted amount out (after slippage) if it was below the limit amount. This is
+    /// `None` if the fallback was taken because the swap time limit elapsed.
+    pub quoted_min_amount_out: Option<u64>,
+}
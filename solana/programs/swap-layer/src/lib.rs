@@ -7,6 +7,8 @@ mod composite;
 
 mod error;
 
+pub mod events;
+
 pub mod state;
 
 pub mod utils;
@@ -18,9 +20,12 @@ const COMPLETE_TOKEN_SEED_PREFIX: &[u8] = b"complete";
 
 const SWAP_AUTHORITY_SEED_PREFIX: &[u8] = b"swap-authority";
 const TRANSFER_AUTHORITY_SEED_PREFIX: &[u8] = b"transfer-authority";
+const INTENT_AUTHORITY_SEED_PREFIX: &[u8] = b"intent-authority";
 
 const PREPARED_ORDER_SEED_PREFIX: &[u8] = b"prepared-order";
 const STAGED_CUSTODY_TOKEN_SEED_PREFIX: &[u8] = b"staged-custody";
+const OUTBOUND_CUSTODY_TOKEN_SEED_PREFIX: &[u8] = b"outbound-custody";
+const FEE_VAULT_CUSTODY_TOKEN_SEED_PREFIX: &[u8] = b"fee-vault-custody";
 
 const MAX_BPS: u32 = 1_000_000; // 10,000.00 bps (100%)
 
@@ -44,7 +49,16 @@ pub mod swap_layer {
     /// Adds a peer to the swap layer. The peer is identified by the `chain` and
     /// `address` fields. The `relay_params` field is used to configure the relay
     /// parameters for the peer (i.e., the gas dropoff and relaying fee) as well
-    /// as the execution parameters for the peer (i.e., chain specific execution costs).
+    /// as the execution parameters for the peer (i.e., chain specific execution costs). The
+    /// optional `price_feed` field configures an oracle for the peer's native token price. The
+    /// `relaying_fee_protocol_share` field determines how much of the relaying fee is kept by the
+    /// protocol when a relayer is paid on-chain. During the `relayer_exclusivity_window`, only
+    /// registered relayers can execute relay completions. The `output_swap_deadline_policy` and
+    /// `max_output_swap_deadline_horizon` fields determine how output swap deadlines encoded by
+    /// this peer are enforced. The `supported_swap_types` and `max_swap_path_length` fields
+    /// determine which output swaps can be staged for this peer, and the `supports_relay_payload`
+    /// field determines whether relayed payloads can be staged for it. If the program lookup table
+    /// accounts are provided, the peer is added to the program-managed address lookup table.
     ///
     /// # Arguments
     ///
@@ -65,6 +79,48 @@ pub mod swap_layer {
         processor::update_peer(ctx, args)
     }
 
+    /// Migrates a peer registered with a previous account layout. The peer account is reallocated
+    /// to fit the current layout (paid by the `payer`) and registered again with the provided
+    /// arguments, which must include the fields missing from the previous layout. This instruction
+    /// can only be called by the `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for migrating the peer.
+    /// * `args` - The arguments for registering the peer with the current layout.
+    pub fn migrate_peer(ctx: Context<MigratePeer>, args: AddPeerArgs) -> Result<()> {
+        processor::migrate_peer(ctx, args)
+    }
+
+    /// Creates the program-managed address lookup table, whose authority is the program lookup
+    /// table account. The lookup table is seeded with the `Custodian`, USDC mint, Token Router
+    /// accounts and Jupiter V6 program accounts so clients can build v0 transactions for swaps.
+    /// Once it exists, `add_peer` adds each new peer and its Matching Engine router endpoint to this
+    /// lookup table. This instruction can only be called by the `owner` or `owner_assistant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for creating the lookup table.
+    /// * `recent_slot` - Recent slot used to derive the lookup table address.
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        processor::create_lookup_table(ctx, recent_slot)
+    }
+
+    /// Extends the program-managed address lookup table with arbitrary addresses (e.g. peers
+    /// registered before the lookup table was created). This instruction can only be called by the
+    /// `owner` or `owner_assistant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for extending the lookup table.
+    /// * `addresses` - The addresses to add to the lookup table.
+    pub fn extend_lookup_table(
+        ctx: Context<ExtendLookupTable>,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        processor::extend_lookup_table(ctx, addresses)
+    }
+
     /// Submits an ownership transfer request.
     ///
     /// This instruction sets the `pending_owner` field in the `Custodian` account. This instruction
@@ -113,7 +169,7 @@ pub mod swap_layer {
     ///
     /// This instruction is used to update the `fee_recipient` field in the `Custodian` account. This
     /// instruction can only be called by the `owner`. The `fee_recipient` must be a valid token
-    /// account. The `fee_recipient` receives any relayer fees received by the swap layer.
+    /// account. The `fee_recipient` is the default sweep destination of the USDC fee vault.
     ///
     /// # Arguments
     ///
@@ -143,6 +199,35 @@ pub mod swap_layer {
         processor::update_fee_updater(ctx)
     }
 
+    /// Updates the Token-2022 extensions allowed for source and destination mints. This
+    /// instruction can only be called by the `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for updating the extension policy.
+    /// * `policy` - The new Token-2022 extension policy.
+    pub fn update_token_2022_extension_policy(
+        ctx: Context<UpdateToken2022ExtensionPolicy>,
+        policy: state::Token2022ExtensionPolicy,
+    ) -> Result<()> {
+        processor::update_token_2022_extension_policy(ctx, policy)
+    }
+
+    /// Migrates the custodian initialized with a previous account layout. The custodian account is
+    /// reallocated to fit the current layout (paid by the `payer`) and its Token-2022 extension
+    /// policy is set. This instruction can only be called by the `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for migrating the custodian.
+    /// * `policy` - The Token-2022 extension policy.
+    pub fn migrate_custodian(
+        ctx: Context<MigrateCustodian>,
+        policy: state::Token2022ExtensionPolicy,
+    ) -> Result<()> {
+        processor::migrate_custodian(ctx, policy)
+    }
+
     /// Updates the relay parameters in the swap layer.
     ///
     /// # Arguments
@@ -156,10 +241,65 @@ pub mod swap_layer {
         processor::update_relay_parameters(ctx, args)
     }
 
+    /// Registers a relayer, which allows it to execute relay completions during a peer's
+    /// exclusivity window. This instruction can only be called by the `owner` or
+    /// `owner_assistant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for registering the relayer.
+    pub fn register_relayer(ctx: Context<RegisterRelayer>) -> Result<()> {
+        processor::register_relayer(ctx)
+    }
+
+    /// Deregisters a relayer. This instruction can only be called by the `owner` or
+    /// `owner_assistant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for deregistering the relayer.
+    pub fn deregister_relayer(ctx: Context<DeregisterRelayer>) -> Result<()> {
+        processor::deregister_relayer(ctx)
+    }
+
+    /// Adds a fee vault for a specific mint. Fees collected in this mint (relaying fees and swap
+    /// dust) are held in the vault's custody token account until they are swept. This instruction
+    /// can only be called by the `owner` or `owner_assistant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for adding the fee vault.
+    pub fn add_fee_vault(ctx: Context<AddFeeVault>) -> Result<()> {
+        processor::add_fee_vault(ctx)
+    }
+
+    /// Updates the token account that receives swept fees for a fee vault. This instruction can
+    /// only be called by the `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for updating the fee vault destination.
+    pub fn update_fee_vault_destination(ctx: Context<UpdateFeeVaultDestination>) -> Result<()> {
+        processor::update_fee_vault_destination(ctx)
+    }
+
+    /// Sweeps the balance of a fee vault's custody token account to its destination token
+    /// account. This instruction can only be called by the `owner` or `owner_assistant`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for sweeping fees.
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        processor::sweep_fees(ctx)
+    }
+
     /// Completes a transfer with relay in the swap layer. If gas dropoff is
     /// specified, the program will transfer the requested number of lamports
-    /// from the payer to the relayer. In return, the program will transfer
-    /// the specified number of USDC to the `fee_recipient_token` account.
+    /// from the payer to the relayer. In return, the relayer is paid its share
+    /// of the relaying fee if it provides a USDC token account. The protocol's
+    /// share (configured per peer) is transferred to the USDC fee vault. The
+    /// recipient's USDC associated token account is created if it does not
+    /// exist.
     ///
     /// # Arguments
     ///
@@ -168,7 +308,9 @@ pub mod swap_layer {
         processor::complete_transfer_relay(ctx)
     }
 
-    /// Completes a direct transfer in the swap layer.
+    /// Completes a direct transfer in the swap layer. The recipient's USDC
+    /// associated token account is created (paid for by the payer) if it does
+    /// not exist.
     ///
     /// # Arguments
     ///
@@ -188,16 +330,53 @@ pub mod swap_layer {
         processor::complete_transfer_payload(ctx)
     }
 
+    /// Completes a payload transfer with relay in the swap layer. The payer
+    /// (usually a relayer) pays for the `staged_inbound` and its custody token
+    /// account, transfers the requested gas dropoff to the recipient and is
+    /// paid its share of the relaying fee from the fill if it provides a USDC
+    /// token account. The protocol's share is transferred to the USDC fee
+    /// vault. Swap outputs are only redeemed as USDC via this instruction
+    /// after the swap time limit has been exceeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for completing the payload transfer with relay.
+    pub fn complete_transfer_relay_payload(
+        ctx: Context<CompleteTransferRelayPayload>,
+    ) -> Result<()> {
+        processor::complete_transfer_relay_payload(ctx)
+    }
+
     /// Releases an inbound transfer in the swap layer. Only the encoded
     /// recipient can release the inbound transfer.
     ///
     /// # Arguments
     ///
-    /// * `ctx` - The context for releasing the inbound transfer.
+    /// * `ctx` - The context for releasing the inbound transfer. If the mint has the Token-2022
+    ///   transfer hook extension, the extra accounts required by the hook must be passed in as
+    ///   remaining accounts.
     pub fn release_inbound(ctx: Context<ReleaseInbound>) -> Result<()> {
         processor::release_inbound(ctx)
     }
 
+    /// Sets the redeem preferences of an inbound transfer recipient. The recipient must sign for
+    /// this instruction (programs can sign with their PDA). If `allow_any_token_account` is set,
+    /// direct and relay completions can deliver to any token account owned by the recipient
+    /// instead of only its associated token account. If `gas_dropoff_as_wsol` is set, relay
+    /// completions deliver the gas dropoff as WSOL into the recipient's WSOL associated token
+    /// account, which the payer creates if it does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for setting the recipient config.
+    /// * `args` - The recipient's redeem preferences.
+    pub fn set_recipient_config(
+        ctx: Context<SetRecipientConfig>,
+        args: SetRecipientConfigArgs,
+    ) -> Result<()> {
+        processor::set_recipient_config(ctx, args)
+    }
+
     /// Stages an outbound transfer or swap in the swap layer.
     ///
     /// # Arguments
@@ -208,6 +387,67 @@ pub mod swap_layer {
         processor::stage_outbound(ctx, args)
     }
 
+    /// Stages an outbound transfer or swap in the swap layer. Unlike `stage_outbound`, the staged
+    /// outbound account is a PDA derived from the sender and the sender's next nonce (see
+    /// `StagedOutboundCounter`), so a sender's pending outbound transfers can be found without
+    /// scanning program accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for staging the outbound transfer.
+    /// * `args` - The arguments for staging the outbound transfer.
+    pub fn stage_outbound_indexed(
+        ctx: Context<StageOutboundIndexed>,
+        args: StageOutboundArgs,
+    ) -> Result<()> {
+        processor::stage_outbound_indexed(ctx, args)
+    }
+
+    /// Stages an outbound transfer or swap on behalf of a sender, who signed the transfer intent
+    /// off-chain. The instruction preceding this one must be an Ed25519 program instruction that
+    /// verifies the sender's signature of the intent message. The sender must have delegated the
+    /// source tokens to the program's intent authority. The staged outbound account is derived
+    /// from the sender's next nonce (like [stage_outbound_indexed]), so each intent can only be
+    /// used once.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for staging the outbound transfer.
+    /// * `intent` - The sender's signed intent.
+    pub fn stage_outbound_with_intent(
+        ctx: Context<StageOutboundWithIntent>,
+        intent: StageOutboundIntent,
+    ) -> Result<()> {
+        processor::stage_outbound_with_intent(ctx, intent)
+    }
+
+    /// Amends a staged outbound transfer before it is initiated. The sender can change the
+    /// recipient, redeem option and output token. If a relay is specified, the relaying fee is
+    /// recalculated and must not exceed the sender's maximum. The `staged_outbound` account is
+    /// resized if more space is needed, in which case `prepared_by` must sign to pay for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for amending the staged outbound.
+    /// * `args` - The arguments for amending the staged outbound.
+    pub fn amend_staged_outbound(
+        ctx: Context<AmendStagedOutbound>,
+        args: AmendStagedOutboundArgs,
+    ) -> Result<()> {
+        processor::amend_staged_outbound(ctx, args)
+    }
+
+    /// Migrates a staged outbound account created with a previous account layout. The staged
+    /// outbound account is reallocated to fit the current layout (paid by the `payer`). This
+    /// instruction can only be called by the `sender`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for migrating the staged outbound.
+    pub fn migrate_staged_outbound(ctx: Context<MigrateStagedOutbound>) -> Result<()> {
+        processor::migrate_staged_outbound(ctx)
+    }
+
     /// Closes the `staged_outbound` account in the swap layer. This should be executed
     /// when the user decides to cancel the staged outbound transfer. This could be the
     /// result of a failed swap when initiating an outbound swap.
@@ -219,6 +459,32 @@ pub mod swap_layer {
         processor::close_staged_outbound(ctx)
     }
 
+    /// Refunds source tokens left in the staged custody token account after an outbound swap has
+    /// been initiated (i.e. the swap did not use all of the staged tokens) or after the staged
+    /// outbound has expired. The tokens are sent to the sender's associated token account and the
+    /// staged accounts are closed, with rent returned to `prepared_by`. This instruction is
+    /// permissionless.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for refunding the staged outbound.
+    pub fn refund_staged_outbound(ctx: Context<RefundStagedOutbound>) -> Result<()> {
+        processor::refund_staged_outbound(ctx)
+    }
+
+    /// Transfers USDC from the sender to the target network in one instruction, without staging
+    /// the outbound transfer first. The relaying fee (if any) is computed the same way as in
+    /// `stage_outbound`, and the market order is prepared with the Token Router program
+    /// immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for transferring USDC.
+    /// * `args` - The arguments for transferring USDC.
+    pub fn transfer_usdc(ctx: Context<TransferUsdc>, args: TransferUsdcArgs) -> Result<()> {
+        processor::transfer_usdc(ctx, args)
+    }
+
     /// Initiates a USDC transfer in the swap layer.
     ///
     /// # Arguments
@@ -244,7 +510,30 @@ pub mod swap_layer {
         processor::initiate_swap_exact_in(ctx, instruction_data)
     }
 
-    /// Completes a direct swap in the swap layer.
+    /// Swaps the sender's source tokens into USDC with Jupiter V6 and prepares the market order in
+    /// one instruction, without staging the outbound transfer first. This instruction is meant for
+    /// small routes whose accounts fit in a single transaction. Large routes should still use
+    /// `stage_outbound` followed by `initiate_swap_exact_in`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for swapping and initiating the transfer.
+    /// * `args` - The arguments for swapping and initiating the transfer.
+    /// * `instruction_data` - The instruction data for the Jupiter V6 swap.
+    pub fn swap_and_initiate<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapAndInitiate<'info>>,
+        args: SwapAndInitiateArgs,
+        instruction_data: Vec<u8>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        processor::swap_and_initiate(ctx, args, instruction_data)
+    }
+
+    /// Completes a direct swap in the swap layer. If the destination mint has the Token-2022
+    /// transfer hook extension, the extra accounts required by the hook must be appended to the
+    /// remaining accounts after the Jupiter V6 accounts.
     ///
     /// # Arguments
     ///
@@ -260,10 +549,55 @@ pub mod swap_layer {
         processor::complete_swap_direct(ctx, instruction_data)
     }
 
+    /// Completes a direct swap in the swap layer or delivers the bridged USDC to the recipient's
+    /// USDC token account instead. The fallback is taken once the source peer's swap time limit
+    /// has elapsed since the fill, or earlier if the recipient executes this instruction with a
+    /// quote whose amount out (after slippage) is below the encoded limit amount. Otherwise the
+    /// swap must meet the limit amount. A `SwapFallbackTriggered` event is emitted when the
+    /// fallback is taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for completing the swap.
+    /// * `instruction_data` - The instruction data for completing the swap. If the fallback is
+    ///   taken, this is only used to check the recipient's quote.
+    pub fn complete_swap_or_fallback<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CompleteSwapOrFallback<'info>>,
+        instruction_data: Vec<u8>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        processor::complete_swap_or_fallback(ctx, instruction_data)
+    }
+
+    /// Completes a swap whose encoded deadline has passed. The recipient signs for this
+    /// instruction and provides a new limit amount and Jupiter V6 route. The destination mint may
+    /// differ from the one encoded in the swap message.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context for completing the swap.
+    /// * `limit_amount` - The minimum amount of the destination token the recipient will accept.
+    /// * `instruction_data` - The instruction data for completing the swap.
+    pub fn complete_swap_override<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CompleteSwapOverride<'info>>,
+        limit_amount: u64,
+        instruction_data: Vec<u8>,
+    ) -> Result<()>
+    where
+        'c: 'info,
+    {
+        processor::complete_swap_override(ctx, limit_amount, instruction_data)
+    }
+
     /// Completes a relay swap in the swap layer. If gas dropoff is
     /// specified, the program will transfer the requested number of lamports
-    /// from the payer to the relayer. In return, the program will transfer
-    /// the specified number of USDC to the `fee_recipient_token` account.
+    /// from the payer to the relayer. In return, the relayer is paid its share
+    /// of the relaying fee if it provides a USDC token account. The protocol's
+    /// share (configured per peer) is transferred to the USDC fee vault. Extra
+    /// accounts for Token-2022 transfer hooks are passed in the same way as
+    /// for `complete_swap_direct`.
     ///
     /// # Arguments
     ///
@@ -0,0 +1,82 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    state::{FeeVault, FeeVaultSeeds},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+#[derive(Accounts)]
+pub struct AddFeeVault<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    admin: Admin<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    fee_vault: Box<Account<'info, FeeVault>>,
+
+    /// Custody token account, which holds collected fees until they are swept.
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = fee_vault,
+        token::token_program = token_program,
+        seeds = [
+            crate::FEE_VAULT_CUSTODY_TOKEN_SEED_PREFIX,
+            fee_vault.key().as_ref(),
+        ],
+        bump,
+    )]
+    custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Token account that receives swept fees. If not provided for the USDC fee vault, the
+    /// custodian's fee recipient token account is used.
+    #[account(
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    destination_token: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    token_program: Interface<'info, token_interface::TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+pub fn add_fee_vault(ctx: Context<AddFeeVault>) -> Result<()> {
+    let destination_token = match &ctx.accounts.destination_token {
+        Some(destination_token) => destination_token.key(),
+        None => {
+            require_keys_eq!(
+                ctx.accounts.mint.key(),
+                common::USDC_MINT,
+                SwapLayerError::InvalidFeeVaultDestination
+            );
+
+            ctx.accounts.admin.custodian.fee_recipient_token
+        }
+    };
+
+    ctx.accounts.fee_vault.set_inner(FeeVault {
+        seeds: FeeVaultSeeds {
+            mint: ctx.accounts.mint.key(),
+            bump: ctx.bumps.fee_vault,
+        },
+        custody_token_bump: ctx.bumps.custody_token,
+        destination_token,
+        accounting: Default::default(),
+    });
+
+    Ok(())
+}
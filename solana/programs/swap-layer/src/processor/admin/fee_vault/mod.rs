@@ -0,0 +1,8 @@
+mod add;
+pub use add::*;
+
+mod sweep;
+pub use sweep::*;
+
+mod update_destination;
+pub use update_destination::*;
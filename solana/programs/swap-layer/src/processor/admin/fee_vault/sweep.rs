@@ -0,0 +1,81 @@
+use crate::{composite::*, events::FeesSwept, state::FeeVault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    admin: Admin<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            fee_vault.seeds.mint.as_ref(),
+        ],
+        bump = fee_vault.seeds.bump,
+    )]
+    fee_vault: Box<Account<'info, FeeVault>>,
+
+    #[account(
+        mut,
+        seeds = [
+            crate::FEE_VAULT_CUSTODY_TOKEN_SEED_PREFIX,
+            fee_vault.key().as_ref(),
+        ],
+        bump = fee_vault.custody_token_bump,
+    )]
+    custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: Mutable. This account must equal the fee vault's destination token account.
+    #[account(
+        mut,
+        address = fee_vault.destination_token,
+    )]
+    destination_token: UncheckedAccount<'info>,
+
+    #[account(address = fee_vault.seeds.mint)]
+    mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+    let amount = ctx.accounts.custody_token.amount;
+
+    // Nothing to do.
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let fee_vault = &ctx.accounts.fee_vault;
+    let mint = &ctx.accounts.mint;
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.custody_token.to_account_info(),
+                to: ctx.accounts.destination_token.to_account_info(),
+                authority: fee_vault.to_account_info(),
+                mint: mint.to_account_info(),
+            },
+            &[&[
+                FeeVault::SEED_PREFIX,
+                fee_vault.seeds.mint.as_ref(),
+                &[fee_vault.seeds.bump],
+            ]],
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    emit!(FeesSwept {
+        fee_vault: fee_vault.key(),
+        destination_token: fee_vault.destination_token,
+        amount,
+    });
+
+    ctx.accounts.fee_vault.sweep(amount);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::{composite::*, state::FeeVault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+#[derive(Accounts)]
+pub struct UpdateFeeVaultDestination<'info> {
+    admin: OwnerOnly<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            fee_vault.seeds.mint.as_ref(),
+        ],
+        bump = fee_vault.seeds.bump,
+    )]
+    fee_vault: Account<'info, FeeVault>,
+
+    #[account(token::mint = fee_vault.seeds.mint)]
+    new_destination_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+}
+
+pub fn update_fee_vault_destination(ctx: Context<UpdateFeeVaultDestination>) -> Result<()> {
+    ctx.accounts.fee_vault.destination_token = ctx.accounts.new_destination_token.key();
+
+    Ok(())
+}
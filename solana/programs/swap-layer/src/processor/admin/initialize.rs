@@ -106,6 +106,7 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         owner_assistant: ctx.accounts.owner_assistant.key(),
         fee_updater: ctx.accounts.fee_updater.key(),
         fee_recipient_token: ctx.accounts.fee_recipient_token.key(),
+        token_2022_extension_policy: Default::default(),
     });
 
     // Done.
@@ -0,0 +1,125 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    processor::{extend_program_lookup_table, ExtendLookupTableAccounts},
+    state::ProgramLookupTable,
+    utils::jupiter_v6,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token, token_2022};
+use solana_program::{address_lookup_table, program::invoke_signed};
+
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    admin: Admin<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProgramLookupTable::INIT_SPACE,
+        seeds = [ProgramLookupTable::SEED_PREFIX],
+        bump,
+    )]
+    program_lookup_table: Account<'info, ProgramLookupTable>,
+
+    /// CHECK: Mutable, seeds must be \[program_lookup_table.key(), recent_slot.to_le_bytes()\]
+    /// (Address Lookup Table Program).
+    #[account(mut)]
+    lookup_table: UncheckedAccount<'info>,
+
+    usdc: Usdc<'info>,
+
+    /// CHECK: Seeds must be \["emitter"\] (Token Router Program).
+    #[account(
+        seeds = [b"emitter"],
+        bump,
+        seeds::program = token_router::id(),
+    )]
+    token_router_custodian: UncheckedAccount<'info>,
+
+    token_router_program: Program<'info, token_router::program::TokenRouter>,
+
+    /// CHECK: This account must be the Address Lookup Table program.
+    #[account(address = address_lookup_table::program::ID)]
+    address_lookup_table_program: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let program_lookup_table = &mut ctx.accounts.program_lookup_table;
+    let program_lookup_table_bump = ctx.bumps.program_lookup_table;
+    let lookup_table = &ctx.accounts.lookup_table;
+    let address_lookup_table_program = &ctx.accounts.address_lookup_table_program;
+    let system_program = &ctx.accounts.system_program;
+
+    let (ix, expected_lookup_table) = address_lookup_table::instruction::create_lookup_table(
+        program_lookup_table.key(),
+        payer.key(),
+        recent_slot,
+    );
+    require_keys_eq!(
+        lookup_table.key(),
+        expected_lookup_table,
+        SwapLayerError::InvalidLookupTable
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            lookup_table.to_account_info(),
+            program_lookup_table.to_account_info(),
+            payer.to_account_info(),
+            system_program.to_account_info(),
+            address_lookup_table_program.to_account_info(),
+        ],
+        &[&[
+            ProgramLookupTable::SEED_PREFIX,
+            &[program_lookup_table_bump],
+        ]],
+    )?;
+
+    program_lookup_table.set_inner(ProgramLookupTable {
+        bump: program_lookup_table_bump,
+        address: lookup_table.key(),
+    });
+
+    // Seed the lookup table with the accounts used by every outbound and inbound transfer. Peers
+    // and their Matching Engine router endpoints are added when they are registered (see
+    // `add_peer`).
+    let mut new_addresses = vec![
+        ctx.accounts.admin.custodian.key(),
+        ctx.accounts.usdc.key(),
+        ctx.accounts.token_router_custodian.key(),
+        ctx.accounts.token_router_program.key(),
+        jupiter_v6::JUPITER_V6_PROGRAM_ID,
+        Pubkey::find_program_address(&[b"__event_authority"], &jupiter_v6::JUPITER_V6_PROGRAM_ID).0,
+        token::ID,
+        token_2022::ID,
+        associated_token::ID,
+        system_program.key(),
+    ];
+    new_addresses.extend((0..jupiter_v6::AUTHORITY_COUNT).map(|authority_id| {
+        Pubkey::find_program_address(
+            &[b"authority", &[authority_id]],
+            &jupiter_v6::JUPITER_V6_PROGRAM_ID,
+        )
+        .0
+    }));
+
+    extend_program_lookup_table(
+        ExtendLookupTableAccounts {
+            payer,
+            program_lookup_table: program_lookup_table.as_ref(),
+            program_lookup_table_bump,
+            lookup_table,
+            address_lookup_table_program,
+            system_program,
+        },
+        new_addresses,
+    )
+}
@@ -0,0 +1,48 @@
+use crate::{
+    composite::*,
+    processor::{extend_program_lookup_table, ExtendLookupTableAccounts},
+    state::ProgramLookupTable,
+};
+use anchor_lang::prelude::*;
+use solana_program::address_lookup_table;
+
+#[derive(Accounts)]
+pub struct ExtendLookupTable<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    admin: Admin<'info>,
+
+    #[account(
+        seeds = [ProgramLookupTable::SEED_PREFIX],
+        bump = program_lookup_table.bump,
+    )]
+    program_lookup_table: Account<'info, ProgramLookupTable>,
+
+    /// CHECK: Mutable, must equal the lookup table address found in the program lookup table.
+    #[account(
+        mut,
+        address = program_lookup_table.address,
+    )]
+    lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: This account must be the Address Lookup Table program.
+    #[account(address = address_lookup_table::program::ID)]
+    address_lookup_table_program: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+    extend_program_lookup_table(
+        ExtendLookupTableAccounts {
+            payer: &ctx.accounts.payer,
+            program_lookup_table: ctx.accounts.program_lookup_table.as_ref(),
+            program_lookup_table_bump: ctx.accounts.program_lookup_table.bump,
+            lookup_table: &ctx.accounts.lookup_table,
+            address_lookup_table_program: &ctx.accounts.address_lookup_table_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        addresses,
+    )
+}
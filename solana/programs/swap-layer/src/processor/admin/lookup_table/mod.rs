@@ -0,0 +1,56 @@
+mod create;
+pub use create::*;
+
+mod extend;
+pub use extend::*;
+
+use crate::state::ProgramLookupTable;
+use anchor_lang::prelude::*;
+use solana_program::{address_lookup_table, program::invoke_signed};
+
+/// Accounts needed to extend the program-managed address lookup table.
+pub(crate) struct ExtendLookupTableAccounts<'ctx, 'info> {
+    pub payer: &'ctx Signer<'info>,
+    pub program_lookup_table: &'ctx AccountInfo<'info>,
+    pub program_lookup_table_bump: u8,
+    pub lookup_table: &'ctx AccountInfo<'info>,
+    pub address_lookup_table_program: &'ctx AccountInfo<'info>,
+    pub system_program: &'ctx Program<'info, System>,
+}
+
+/// Extend the program-managed address lookup table with the given addresses. The payer funds the
+/// additional rent required by the lookup table.
+pub(crate) fn extend_program_lookup_table(
+    accounts: ExtendLookupTableAccounts,
+    new_addresses: Vec<Pubkey>,
+) -> Result<()> {
+    let ExtendLookupTableAccounts {
+        payer,
+        program_lookup_table,
+        program_lookup_table_bump,
+        lookup_table,
+        address_lookup_table_program,
+        system_program,
+    } = accounts;
+
+    invoke_signed(
+        &address_lookup_table::instruction::extend_lookup_table(
+            lookup_table.key(),
+            program_lookup_table.key(),
+            Some(payer.key()),
+            new_addresses,
+        ),
+        &[
+            lookup_table.to_account_info(),
+            program_lookup_table.to_account_info(),
+            payer.to_account_info(),
+            system_program.to_account_info(),
+            address_lookup_table_program.to_account_info(),
+        ],
+        &[&[
+            ProgramLookupTable::SEED_PREFIX,
+            &[program_lookup_table_bump],
+        ]],
+    )
+    .map_err(Into::into)
+}
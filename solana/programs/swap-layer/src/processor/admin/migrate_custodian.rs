@@ -0,0 +1,79 @@
+use crate::{
+    error::SwapLayerError,
+    state::{Custodian, CustodianV0, Token2022ExtensionPolicy},
+    utils,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigrateCustodian<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// Owner of the program, which is read from the custodian's V0 layout in the instruction
+    /// handler.
+    owner: Signer<'info>,
+
+    /// Custodian account initialized with the [CustodianV0] layout. This account is reallocated to
+    /// fit the current layout.
+    ///
+    /// CHECK: This account must be owned by this program and its data must have the V0 layout,
+    /// which is checked in the instruction handler.
+    #[account(
+        mut,
+        owner = crate::id(),
+        seeds = [Custodian::SEED_PREFIX],
+        bump = Custodian::BUMP,
+    )]
+    custodian: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_custodian(
+    ctx: Context<MigrateCustodian>,
+    policy: Token2022ExtensionPolicy,
+) -> Result<()> {
+    let custodian = &ctx.accounts.custodian;
+
+    // Only a custodian initialized with the V0 layout needs to be migrated. Each layout has a
+    // distinct account size, so the size determines the version.
+    let CustodianV0 {
+        owner,
+        pending_owner,
+        owner_assistant,
+        fee_updater,
+        fee_recipient_token,
+    } = {
+        let data = custodian.try_borrow_data()?;
+        require!(
+            data.len() == Custodian::V0_SPACE && data.starts_with(&Custodian::DISCRIMINATOR),
+            SwapLayerError::CustodianAlreadyMigrated
+        );
+
+        CustodianV0::deserialize(&mut &data[8..])?
+    };
+
+    // The custodian cannot be loaded as a [CheckedCustodian] until it is migrated, so the owner is
+    // checked against the V0 layout.
+    require_keys_eq!(ctx.accounts.owner.key(), owner, SwapLayerError::OwnerOnly);
+
+    utils::migrate::realloc_account(
+        custodian,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        Custodian::SPACE,
+    )?;
+    Custodian {
+        owner,
+        pending_owner,
+        owner_assistant,
+        fee_updater,
+        fee_recipient_token,
+        token_2022_extension_policy: policy,
+    }
+    .try_serialize(&mut &mut custodian.try_borrow_mut_data()?[..])?;
+
+    // Done.
+    Ok(())
+}
@@ -1,11 +1,23 @@
+mod fee_vault;
+pub use fee_vault::*;
+
 mod initialize;
 pub use initialize::*;
 
 mod peer;
 pub use peer::*;
 
+mod migrate_custodian;
+pub use migrate_custodian::*;
+
+mod lookup_table;
+pub use lookup_table::*;
+
 mod ownership_transfer_request;
 pub use ownership_transfer_request::*;
 
+mod relayer;
+pub use relayer::*;
+
 mod update;
 pub use update::*;
@@ -1,8 +1,14 @@
 use crate::{
     composite::*,
-    state::{Peer, RelayParams},
+    error::SwapLayerError,
+    processor::{extend_program_lookup_table, ExtendLookupTableAccounts},
+    state::{
+        OutputSwapDeadlinePolicy, Peer, PriceFeedConfig, ProgramLookupTable, RelayParams,
+        SupportedSwapTypes,
+    },
 };
 use anchor_lang::prelude::*;
+use solana_program::address_lookup_table;
 
 #[derive(Accounts)]
 #[instruction(args: AddPeerArgs)]
@@ -24,6 +30,35 @@ pub struct AddPeer<'info> {
     )]
     peer: Account<'info, Peer>,
 
+    /// CHECK: Seeds must be \["lookup-table"\]. This account may not exist yet (see
+    /// `create_lookup_table`). Once it exists, the program-managed lookup table is extended with the
+    /// new peer, so the lookup table, target router endpoint and Address Lookup Table program are
+    /// required.
+    #[account(
+        seeds = [ProgramLookupTable::SEED_PREFIX],
+        bump,
+    )]
+    program_lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: Mutable, must equal the lookup table address found in the program lookup table.
+    #[account(mut)]
+    lookup_table: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Seeds must be \["endpoint", args.chain.to_be_bytes()\] (Matching Engine Program).
+    #[account(
+        seeds = [
+            matching_engine::state::RouterEndpoint::SEED_PREFIX,
+            &args.chain.to_be_bytes()
+        ],
+        bump,
+        seeds::program = matching_engine::id(),
+    )]
+    target_router_endpoint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: This account must be the Address Lookup Table program.
+    #[account(address = address_lookup_table::program::ID)]
+    address_lookup_table_program: Option<UncheckedAccount<'info>>,
+
     system_program: Program<'info, System>,
 }
 
@@ -32,8 +67,62 @@ pub struct AddPeerArgs {
     pub chain: u16,
     pub address: [u8; 32],
     pub relay_params: RelayParams,
+    pub price_feed: Option<PriceFeedConfig>,
+    pub relaying_fee_protocol_share: u32,
+    pub relayer_exclusivity_window: u32,
+    pub output_swap_deadline_policy: OutputSwapDeadlinePolicy,
+    pub max_output_swap_deadline_horizon: u32,
+    pub supported_swap_types: SupportedSwapTypes,
+    pub max_swap_path_length: u8,
+    pub supports_relay_payload: bool,
 }
 
 pub fn add_peer(ctx: Context<AddPeer>, args: AddPeerArgs) -> Result<()> {
-    crate::handle_add_peer(&mut ctx.accounts.peer, args, ctx.bumps.peer.into())
+    crate::handle_add_peer(&mut ctx.accounts.peer, args, ctx.bumps.peer.into())?;
+
+    // Keep the program-managed lookup table in sync with registered peers once it exists.
+    let program_lookup_table = &ctx.accounts.program_lookup_table;
+    if !program_lookup_table.data_is_empty() {
+        let lookup_table_address = {
+            let data = program_lookup_table.try_borrow_data()?;
+            ProgramLookupTable::try_deserialize(&mut &data[..])?.address
+        };
+
+        let (lookup_table, target_router_endpoint, address_lookup_table_program) = match (
+            &ctx.accounts.lookup_table,
+            &ctx.accounts.target_router_endpoint,
+            &ctx.accounts.address_lookup_table_program,
+        ) {
+            (
+                Some(lookup_table),
+                Some(target_router_endpoint),
+                Some(address_lookup_table_program),
+            ) => (
+                lookup_table,
+                target_router_endpoint,
+                address_lookup_table_program,
+            ),
+            _ => return err!(SwapLayerError::LookupTableAccountsRequired),
+        };
+        require_keys_eq!(
+            lookup_table.key(),
+            lookup_table_address,
+            SwapLayerError::InvalidLookupTable
+        );
+
+        extend_program_lookup_table(
+            ExtendLookupTableAccounts {
+                payer: &ctx.accounts.payer,
+                program_lookup_table,
+                program_lookup_table_bump: ctx.bumps.program_lookup_table,
+                lookup_table,
+                address_lookup_table_program,
+                system_program: &ctx.accounts.system_program,
+            },
+            vec![ctx.accounts.peer.key(), target_router_endpoint.key()],
+        )?;
+    }
+
+    // Done.
+    Ok(())
 }
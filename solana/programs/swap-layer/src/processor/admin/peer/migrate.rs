@@ -0,0 +1,56 @@
+use crate::{composite::*, error::SwapLayerError, state::Peer, utils};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(args: crate::AddPeerArgs)]
+pub struct MigratePeer<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    admin: OwnerOnly<'info>,
+
+    /// Peer account registered with the [PeerV0](crate::state::PeerV0) layout. This account is
+    /// reallocated to fit the current layout and registered again with the provided arguments.
+    ///
+    /// CHECK: This account must be owned by this program and its data must have the V0 layout,
+    /// which is checked in the instruction handler.
+    #[account(
+        mut,
+        owner = crate::id(),
+        seeds = [
+            Peer::SEED_PREFIX,
+            &args.chain.to_be_bytes()
+        ],
+        bump,
+    )]
+    peer: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_peer(ctx: Context<MigratePeer>, args: crate::AddPeerArgs) -> Result<()> {
+    let peer = &ctx.accounts.peer;
+
+    // Only peers registered with the V0 layout need to be migrated. Each layout has a distinct
+    // account size, so the size determines the version.
+    {
+        let data = peer.try_borrow_data()?;
+        require!(
+            data.len() == Peer::V0_SPACE && data.starts_with(&Peer::DISCRIMINATOR),
+            SwapLayerError::PeerAlreadyMigrated
+        );
+    }
+
+    let peer_data = crate::new_peer(&peer.key(), args, ctx.bumps.peer)?;
+
+    utils::migrate::realloc_account(
+        peer,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        Peer::SPACE,
+    )?;
+    peer_data.try_serialize(&mut &mut peer.try_borrow_mut_data()?[..])?;
+
+    // Done.
+    Ok(())
+}
@@ -1,53 +1,117 @@
 mod add;
 pub use add::*;
 
+mod migrate;
+pub use migrate::*;
+
 mod update;
 pub use update::*;
 
 use crate::{
     error::SwapLayerError,
-    state::{Peer, PeerSeeds},
-    utils::relay_parameters::verify_relay_params,
+    state::{ExecutionParams, OutputSwapDeadlinePolicy, Peer, PeerSeeds, SupportedSwapTypes},
+    utils::{price_feed::verify_price_feed_config, relay_parameters::verify_relay_params},
 };
 use anchor_lang::prelude::*;
 use common::wormhole_cctp_solana::wormhole::SOLANA_CHAIN;
+use swap_layer_messages::types::UniversalAddress;
 
 pub fn handle_add_peer(
     peer: &mut Account<Peer>,
     args: AddPeerArgs,
     bump_seed: Option<u8>,
 ) -> Result<()> {
+    let bump = bump_seed.unwrap_or(peer.seeds.bump);
+    let peer_data = new_peer(&peer.key(), args, bump)?;
+    peer.set_inner(peer_data);
+
+    Ok(())
+}
+
+/// Verify the peer arguments and build the peer account data for the peer account at `peer_key`.
+pub(crate) fn new_peer(peer_key: &Pubkey, args: AddPeerArgs, bump: u8) -> Result<Peer> {
     require!(
         args.chain != 0 && args.chain != SOLANA_CHAIN,
         SwapLayerError::ChainNotAllowed
     );
-    require!(args.address != [0; 32], SwapLayerError::InvalidPeer);
+
+    // EVM peers must have EVM addresses.
+    let is_evm = matches!(
+        args.relay_params.execution_params,
+        ExecutionParams::Evm { .. }
+    );
+    UniversalAddress::from(args.address)
+        .validate(is_evm)
+        .map_err(|_| SwapLayerError::InvalidPeer)?;
 
     // Verify the relay parameters.
     verify_relay_params(&args.relay_params)?;
 
+    require!(
+        args.relaying_fee_protocol_share <= crate::MAX_BPS,
+        SwapLayerError::InvalidRelayingFeeShare
+    );
+
+    // A default max age must be nonzero and cannot exceed the deadline horizon (if there is one).
+    if let OutputSwapDeadlinePolicy::DefaultMaxAge(max_age) = args.output_swap_deadline_policy {
+        require!(
+            max_age != 0
+                && (args.max_output_swap_deadline_horizon == 0
+                    || max_age <= args.max_output_swap_deadline_horizon),
+            SwapLayerError::InvalidOutputSwapDeadlinePolicy
+        );
+    }
+
+    // EVM swap types can only be supported by EVM peers, and Solana swap types cannot be supported
+    // by any peer.
+    let SupportedSwapTypes {
+        uniswap_v3,
+        trader_joe,
+        jupiter_v6,
+    } = args.supported_swap_types;
+    require!(
+        !jupiter_v6 && (!(uniswap_v3 || trader_joe) || is_evm),
+        SwapLayerError::InvalidSupportedSwapTypes
+    );
+
+    // Verify the price feed configuration if there is one.
+    if let Some(price_feed) = &args.price_feed {
+        verify_price_feed_config(price_feed)?;
+    }
+
     let AddPeerArgs {
         chain,
         address,
         relay_params,
+        price_feed,
+        relaying_fee_protocol_share,
+        relayer_exclusivity_window,
+        output_swap_deadline_policy,
+        max_output_swap_deadline_horizon,
+        supported_swap_types,
+        max_swap_path_length,
+        supports_relay_payload,
     } = args;
 
-    let seeds = PeerSeeds {
-        chain,
-        bump: bump_seed.unwrap_or(peer.seeds.bump),
-    };
+    let seeds = PeerSeeds { chain, bump };
     let expected = Pubkey::create_program_address(
         &[Peer::SEED_PREFIX, &seeds.chain.to_be_bytes(), &[seeds.bump]],
         &crate::id(),
     )
     .map_err(|_| ErrorCode::ConstraintSeeds)?;
-    require_keys_eq!(peer.key(), expected, ErrorCode::ConstraintSeeds);
+    require_keys_eq!(*peer_key, expected, ErrorCode::ConstraintSeeds);
 
-    peer.set_inner(Peer {
+    Ok(Peer {
         seeds,
         address,
         relay_params,
-    });
-
-    Ok(())
+        price_feed,
+        relaying_fee_protocol_share,
+        relayer_exclusivity_window,
+        output_swap_deadline_policy,
+        max_output_swap_deadline_horizon,
+        supported_swap_types,
+        max_swap_path_length,
+        supports_relay_payload,
+    })
 }
@@ -0,0 +1,27 @@
+use crate::{composite::*, state::RegisteredRelayer};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DeregisterRelayer<'info> {
+    admin: Admin<'info>,
+
+    /// CHECK: Recipient of lamports from closing the registered relayer account.
+    #[account(mut)]
+    beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [
+            RegisteredRelayer::SEED_PREFIX,
+            registered_relayer.relayer.as_ref(),
+        ],
+        bump = registered_relayer.bump,
+    )]
+    registered_relayer: Account<'info, RegisteredRelayer>,
+}
+
+pub fn deregister_relayer(_ctx: Context<DeregisterRelayer>) -> Result<()> {
+    // The registered relayer account is closed by Anchor.
+    Ok(())
+}
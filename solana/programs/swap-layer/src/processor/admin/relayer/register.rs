@@ -0,0 +1,38 @@
+use crate::{composite::*, state::RegisteredRelayer};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    admin: Admin<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RegisteredRelayer::INIT_SPACE,
+        seeds = [
+            RegisteredRelayer::SEED_PREFIX,
+            relayer.key().as_ref(),
+        ],
+        bump,
+    )]
+    registered_relayer: Account<'info, RegisteredRelayer>,
+
+    /// CHECK: Relayer who will pay for relay completions.
+    relayer: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn register_relayer(ctx: Context<RegisterRelayer>) -> Result<()> {
+    ctx.accounts
+        .registered_relayer
+        .set_inner(RegisteredRelayer {
+            bump: ctx.bumps.registered_relayer,
+            relayer: ctx.accounts.relayer.key(),
+        });
+
+    Ok(())
+}
@@ -9,3 +9,6 @@ pub use fee_updater::*;
 
 mod relay_parameters;
 pub use relay_parameters::*;
+
+mod token_2022_extension_policy;
+pub use token_2022_extension_policy::*;
@@ -0,0 +1,17 @@
+use crate::{composite::*, state::Token2022ExtensionPolicy};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateToken2022ExtensionPolicy<'info> {
+    admin: OwnerOnlyMut<'info>,
+}
+
+pub fn update_token_2022_extension_policy(
+    ctx: Context<UpdateToken2022ExtensionPolicy>,
+    policy: Token2022ExtensionPolicy,
+) -> Result<()> {
+    ctx.accounts.admin.custodian.token_2022_extension_policy = policy;
+
+    // Done.
+    Ok(())
+}
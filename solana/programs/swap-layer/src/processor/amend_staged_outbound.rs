@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    processor::{compute_relaying_fee, verify_relay_payload},
+    state::{Peer, RedeemOption, StagedOutbound, StagedRedeem},
+    utils, PREPARED_ORDER_SEED_PREFIX,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+use common::wormhole_io::Writeable;
+use swap_layer_messages::types::OutputToken;
+
+#[derive(Accounts)]
+#[instruction(args: AmendStagedOutboundArgs)]
+pub struct AmendStagedOutbound<'info> {
+    /// This signer must be the sender encoded in the staged outbound account.
+    #[account(address = staged_outbound.sender)]
+    sender: Signer<'info>,
+
+    /// This signer must be the account that prepared the staged outbound account. Because this
+    /// account recovers the rent when the staged outbound account is closed, it pays for any
+    /// additional space needed for the amended staged outbound account. This account is only
+    /// required if the staged outbound account needs more space.
+    #[account(
+        mut,
+        address = staged_outbound.prepared_by,
+    )]
+    prepared_by: Option<Signer<'info>>,
+
+    /// Staged outbound account, which will be resized if the amended instructions require more
+    /// space. This account never shrinks so that `prepared_by` recovers its rent when it is closed.
+    #[account(
+        mut,
+        constraint = !staged_outbound.info.is_initiated @ SwapLayerError::StagedOutboundAlreadyInitiated,
+        constraint = {
+            require!(
+                !staged_outbound.is_expired(Clock::get()?.unix_timestamp),
+                SwapLayerError::StagedOutboundExpired
+            );
+
+            true
+        }
+    )]
+    staged_outbound: Account<'info, StagedOutbound>,
+
+    /// Custody token account for the staged outbound transfer.
+    #[account(
+        mut,
+        token::mint = src_mint,
+        token::authority = target_peer,
+        token::token_program = src_token_program,
+        seeds = [
+            crate::STAGED_CUSTODY_TOKEN_SEED_PREFIX,
+            staged_outbound.key().as_ref(),
+        ],
+        bump = staged_outbound.info.custody_token_bump,
+    )]
+    staged_custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: Seeds must be \["prepared-order", staged_outbound.key()\]. The outbound transfer
+    /// cannot be amended once it has been initiated.
+    #[account(
+        seeds = [
+            PREPARED_ORDER_SEED_PREFIX,
+            staged_outbound.key().as_ref(),
+        ],
+        bump,
+        constraint = prepared_order.data_is_empty() @ SwapLayerError::InvalidPreparedOrder,
+    )]
+    prepared_order: UncheckedAccount<'info>,
+
+    /// Peer used to recompute the relaying fee and to verify the amended recipient.
+    #[account(
+        constraint = {
+            target_peer.verify_destination(staged_outbound.target_chain, args.recipient)?;
+
+            true
+        }
+    )]
+    target_peer: OutboundTarget<'info>,
+
+    /// This token account is required if the amended relaying fee changes the amount of USDC that
+    /// must be staged (i.e. the source mint is USDC and exact in is not specified). The sender
+    /// either pays the additional relaying fee from or is refunded the difference to this account.
+    #[account(
+        mut,
+        token::mint = src_mint,
+        token::token_program = src_token_program,
+    )]
+    sender_token: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
+
+    src_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    src_token_program: Interface<'info, token_interface::TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+/// Arguments for [amend_staged_outbound]. These arguments replace the ones specified when the
+/// outbound transfer was staged.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct AmendStagedOutboundArgs {
+    /// The recipient of the transfer.
+    pub recipient: [u8; 32],
+
+    pub redeem_option: Option<RedeemOption>,
+
+    pub encoded_output_token: Option<Vec<u8>>,
+}
+
+pub fn amend_staged_outbound(
+    ctx: Context<AmendStagedOutbound>,
+    args: AmendStagedOutboundArgs,
+) -> Result<()> {
+    // Only resize the staged outbound account if the amended data does not fit.
+    let new_len =
+        StagedOutbound::try_compute_size(&args.redeem_option, &args.encoded_output_token)?;
+    if new_len > ctx.accounts.staged_outbound.to_account_info().data_len() {
+        let prepared_by = ctx
+            .accounts
+            .prepared_by
+            .as_ref()
+            .ok_or_else(|| error!(SwapLayerError::PreparedByRequired))?;
+
+        utils::migrate::realloc_account(
+            &ctx.accounts.staged_outbound.to_account_info(),
+            prepared_by,
+            &ctx.accounts.system_program,
+            new_len,
+        )?;
+    }
+
+    let AmendStagedOutboundArgs {
+        recipient,
+        redeem_option,
+        encoded_output_token,
+    } = args;
+
+    // Replace None with OutputToken::USDC encoded.
+    let encoded_output_token = encoded_output_token.unwrap_or({
+        let mut buf = Vec::with_capacity(1);
+        OutputToken::Usdc.write(&mut buf).unwrap();
+        buf
+    });
+    let output_token = utils::output_token::decode_output_token(&encoded_output_token)?;
+
+    // The output token must be redeemable on the target network.
+    utils::output_token::verify_output_token(&ctx.accounts.target_peer, &output_token)?;
+
+    let relaying_fee = |gas_dropoff, max_relayer_fee| {
+        compute_relaying_fee(
+            &ctx.accounts.target_peer,
+            ctx.accounts.target_peer.native_token_price_feed(),
+            gas_dropoff,
+            max_relayer_fee,
+            &output_token,
+        )
+    };
+    let staged_redeem = match redeem_option {
+        Some(RedeemOption::Relay {
+            gas_dropoff,
+            max_relayer_fee,
+        }) => StagedRedeem::Relay {
+            gas_dropoff,
+            relaying_fee: relaying_fee(gas_dropoff, max_relayer_fee)?,
+        },
+        Some(RedeemOption::RelayPayload {
+            gas_dropoff,
+            max_relayer_fee,
+            payload,
+        }) => {
+            verify_relay_payload(&ctx.accounts.target_peer, &output_token)?;
+
+            StagedRedeem::RelayPayload {
+                gas_dropoff,
+                relaying_fee: relaying_fee(gas_dropoff, max_relayer_fee)?,
+                payload,
+            }
+        }
+        Some(RedeemOption::Payload(buf)) => StagedRedeem::Payload(buf),
+        None => StagedRedeem::Direct,
+    };
+
+    // Only USDC transfers pay for the relaying fee up front. Otherwise the relaying fee is deducted
+    // from the USDC after the swap from the source mint.
+    if ctx.accounts.src_mint.key() == common::USDC_MINT {
+        let custody_amount = ctx.accounts.staged_custody_token.amount;
+
+        if ctx.accounts.staged_outbound.is_exact_in {
+            if let Some(relaying_fee) = staged_redeem.relaying_fee() {
+                require!(
+                    custody_amount > relaying_fee,
+                    SwapLayerError::InsufficientAmountIn
+                );
+            }
+        } else {
+            let amount_in = custody_amount
+                .checked_sub(
+                    ctx.accounts
+                        .staged_outbound
+                        .staged_redeem
+                        .relaying_fee()
+                        .unwrap_or_default(),
+                )
+                .ok_or_else(|| SwapLayerError::InvalidRelayerFee)?;
+            let transfer_amount = amount_in
+                .checked_add(staged_redeem.relaying_fee().unwrap_or_default())
+                .ok_or_else(|| SwapLayerError::U64Overflow)?;
+
+            settle_custody_difference(&ctx, custody_amount, transfer_amount)?;
+        }
+    }
+
+    let staged_outbound = &mut ctx.accounts.staged_outbound;
+    staged_outbound.info.recipient = recipient;
+    staged_outbound.staged_redeem = staged_redeem;
+    staged_outbound.encoded_output_token = encoded_output_token;
+
+    // Done.
+    Ok(())
+}
+
+/// Transfer the difference between the required amount and the custody token account's balance
+/// between the sender and the custody token account.
+fn settle_custody_difference(
+    ctx: &Context<AmendStagedOutbound>,
+    custody_amount: u64,
+    transfer_amount: u64,
+) -> Result<()> {
+    let ordering = transfer_amount.cmp(&custody_amount);
+    if ordering == Ordering::Equal {
+        return Ok(());
+    }
+
+    let sender_token = ctx
+        .accounts
+        .sender_token
+        .as_ref()
+        .ok_or_else(|| error!(SwapLayerError::SenderTokenRequired))?;
+    let src_token_program = &ctx.accounts.src_token_program;
+    let custody_token = &ctx.accounts.staged_custody_token;
+    let src_mint = &ctx.accounts.src_mint;
+
+    match ordering {
+        Ordering::Greater => token_interface::transfer_checked(
+            CpiContext::new(
+                src_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: sender_token.to_account_info(),
+                    to: custody_token.to_account_info(),
+                    authority: ctx.accounts.sender.to_account_info(),
+                    mint: src_mint.to_account_info(),
+                },
+            ),
+            transfer_amount.saturating_sub(custody_amount),
+            src_mint.decimals,
+        ),
+        _ => {
+            let peer_seeds = &ctx.accounts.target_peer.seeds;
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    src_token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: custody_token.to_account_info(),
+                        to: sender_token.to_account_info(),
+                        authority: ctx.accounts.target_peer.to_account_info(),
+                        mint: src_mint.to_account_info(),
+                    },
+                    &[&[
+                        Peer::SEED_PREFIX,
+                        &peer_seeds.chain.to_be_bytes(),
+                        &[peer_seeds.bump],
+                    ]],
+                ),
+                custody_amount.saturating_sub(transfer_amount),
+                src_mint.decimals,
+            )
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
     composite::*,
     state::{Peer, StagedOutbound},
+    utils,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token;
+use anchor_spl::token_interface;
 
 #[derive(Accounts)]
 pub struct CloseStagedOutbound<'info> {
@@ -31,21 +32,27 @@ pub struct CloseStagedOutbound<'info> {
     /// This custody token account will be closed by the end of the instruction.
     #[account(
         mut,
+        token::mint = src_mint,
         token::authority = target_peer,
+        token::token_program = src_token_program,
         seeds = [
             crate::STAGED_CUSTODY_TOKEN_SEED_PREFIX,
             staged_outbound.key().as_ref(),
         ],
         bump = staged_outbound.info.custody_token_bump,
     )]
-    staged_custody_token: Account<'info, token::TokenAccount>,
+    staged_custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
     /// CHECK: Where the refund will be sent after the staged outbound is closed. We
     /// already check that the sender is the same as the prepared_by account.
     #[account(mut)]
     sender_token: Option<UncheckedAccount<'info>>,
 
-    token_program: Program<'info, token::Token>,
+    /// This mint must be mutable if it withholds transfer fees, which are harvested before closing
+    /// the custody token account.
+    src_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    src_token_program: Interface<'info, token_interface::TokenInterface>,
     system_program: Program<'info, System>,
 }
 
@@ -56,26 +63,38 @@ pub fn close_staged_outbound(ctx: Context<CloseStagedOutbound>) -> Result<()> {
         &[ctx.accounts.target_peer.seeds.bump],
     ];
 
+    let src_token_program = &ctx.accounts.src_token_program;
+    let src_mint = &ctx.accounts.src_mint;
+
+    // Fees withheld by the source mint must be harvested before closing the token account.
+    utils::token_2022::harvest_withheld_tokens_to_mint(
+        &src_token_program.to_account_info(),
+        &src_mint.to_account_info(),
+        &ctx.accounts.staged_custody_token.to_account_info(),
+    )?;
+
     match &ctx.accounts.sender_token {
         Some(sender_token) => {
             // Transfer the custody token to the sender.
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    token::Transfer {
+                    src_token_program.to_account_info(),
+                    token_interface::TransferChecked {
                         from: ctx.accounts.staged_custody_token.to_account_info(),
                         to: sender_token.to_account_info(),
                         authority: ctx.accounts.target_peer.to_account_info(),
+                        mint: src_mint.to_account_info(),
                     },
                     &[target_peer_signer_seeds],
                 ),
                 ctx.accounts.staged_custody_token.amount,
+                src_mint.decimals,
             )?;
 
             // Finally close token account.
-            token::close_account(CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::CloseAccount {
+            token_interface::close_account(CpiContext::new_with_signer(
+                src_token_program.to_account_info(),
+                token_interface::CloseAccount {
                     account: ctx.accounts.staged_custody_token.to_account_info(),
                     destination: ctx.accounts.prepared_by.to_account_info(),
                     authority: ctx.accounts.target_peer.to_account_info(),
@@ -84,15 +103,18 @@ pub fn close_staged_outbound(ctx: Context<CloseStagedOutbound>) -> Result<()> {
             ))
         }
         None => {
-            let lamports =
-                AsRef::<AccountInfo>::as_ref(&ctx.accounts.staged_custody_token).lamports();
+            let lamports = ctx
+                .accounts
+                .staged_custody_token
+                .to_account_info()
+                .lamports();
             let sender = ctx.accounts.sender.to_account_info();
             let prepared_by = ctx.accounts.prepared_by.to_account_info();
 
             // Finally close token account.
-            token::close_account(CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::CloseAccount {
+            token_interface::close_account(CpiContext::new_with_signer(
+                src_token_program.to_account_info(),
+                token_interface::CloseAccount {
                     account: ctx.accounts.staged_custody_token.to_account_info(),
                     destination: sender.to_account_info(),
                     authority: ctx.accounts.target_peer.to_account_info(),
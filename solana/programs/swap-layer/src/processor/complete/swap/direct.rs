@@ -1,7 +1,6 @@
-use crate::{composite::*, error::SwapLayerError};
+use crate::{composite::*, error::SwapLayerError, state::RecipientConfig};
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token;
-use swap_layer_messages::types::RedeemMode;
+use swap_layer_messages::types::RedeemModeRef;
 
 #[derive(Accounts)]
 pub struct CompleteSwapDirect<'info> {
@@ -9,25 +8,38 @@ pub struct CompleteSwapDirect<'info> {
 
     #[account(
         mut,
-        address = associated_token::get_associated_token_address_with_program_id(
+        constraint = verify_recipient_token(
+            &recipient_token,
             &recipient.key(),
             &complete_swap.dst_mint.key(),
-            &complete_swap.dst_token_program.key()
-        )
+            &complete_swap.dst_token_program.key(),
+            recipient_config.as_deref(),
+        )?
     )]
-    /// Recipient associated token account. The recipient authority check is necessary to ensure
-    /// that the recipient is the intended recipient of the bridged tokens.
+    /// Recipient token account. This account is the recipient's associated token account unless the
+    /// recipient allows any token account in its recipient config.
     ///
     /// If OutputToken::Other, this account will be deserialized to ensure that the recipient is
     /// the owner of this token account.
     ///
-    /// CHECK: Mutable ATA whose owner is the recipient and mint is the destination mint.
+    /// CHECK: Mutable token account whose owner is the recipient and mint is the destination mint.
     recipient_token: UncheckedAccount<'info>,
 
     /// CHECK: This account must be the owner of the recipient token account. The recipient token
     /// account must be encoded in the prepared fill.
     #[account(mut)]
     recipient: UncheckedAccount<'info>,
+
+    /// Recipient's redeem preferences. If the recipient allows it, the recipient token account can
+    /// be any token account owned by the recipient.
+    #[account(
+        seeds = [
+            RecipientConfig::SEED_PREFIX,
+            recipient.key().as_ref(),
+        ],
+        bump = recipient_config.bump,
+    )]
+    recipient_config: Option<Account<'info, RecipientConfig>>,
 }
 
 pub fn complete_swap_direct<'a, 'b, 'c, 'info>(
@@ -40,21 +52,34 @@ where
     let complete_swap_accounts = &mut ctx.accounts.complete_swap;
 
     // Read message to determine redeem mode and consume prepared fill.
-    let swap_msg = complete_swap_accounts.read_message_unchecked();
+    let swap_msg = complete_swap_accounts.read_and_verify_message()?;
     let in_amount = complete_swap_accounts.consume_prepared_fill()?;
 
-    match &swap_msg.redeem_mode {
-        RedeemMode::Direct => complete_swap_jup_v6(
-            complete_swap_accounts,
-            &ctx.bumps.complete_swap,
-            ctx.remaining_accounts,
-            instruction_data,
-            in_amount,
-            swap_msg,
-            &ctx.accounts.recipient,
-            &ctx.accounts.recipient_token,
-            Default::default(),
-        ),
+    match swap_msg.redeem_mode() {
+        RedeemModeRef::Direct => {
+            let usdc_dust = complete_swap_jup_v6(
+                complete_swap_accounts,
+                &ctx.bumps.complete_swap,
+                ctx.remaining_accounts,
+                instruction_data,
+                in_amount,
+                swap_msg.recipient(),
+                swap_msg.output_token(),
+                &ctx.accounts.recipient,
+                &ctx.accounts.recipient_token,
+                Default::default(),
+            )?;
+
+            let source_chain = complete_swap_accounts.fill.source_chain;
+            complete_swap_accounts.fee_vault.collect(
+                source_chain,
+                Default::default(),
+                usdc_dust,
+                false,
+            );
+
+            Ok(())
+        }
         _ => err!(SwapLayerError::InvalidRedeemMode),
     }
 }
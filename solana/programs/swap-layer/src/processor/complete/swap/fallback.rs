@@ -0,0 +1,215 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    events::SwapFallbackTriggered,
+    state::RecipientConfig,
+    utils::{
+        jupiter_v6::{self, cpi::SharedAccountsRouteArgs},
+        AnchorInstructionData,
+    },
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token, token_interface};
+use swap_layer_messages::types::{OutputSwapRef, OutputTokenRef, RedeemModeRef};
+
+#[derive(Accounts)]
+pub struct CompleteSwapOrFallback<'info> {
+    complete_swap: CompleteSwap<'info>,
+
+    #[account(
+        mut,
+        constraint = verify_recipient_token(
+            &recipient_token,
+            &recipient.key(),
+            &complete_swap.dst_mint.key(),
+            &complete_swap.dst_token_program.key(),
+            recipient_config.as_deref(),
+        )?
+    )]
+    /// Recipient token account for the destination mint. This account is the recipient's
+    /// associated token account unless the recipient allows any token account in its recipient
+    /// config.
+    ///
+    /// CHECK: Mutable token account whose owner is the recipient and mint is the destination mint.
+    recipient_token: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = verify_recipient_token(
+            &recipient_usdc_token,
+            &recipient.key(),
+            &complete_swap.usdc.key(),
+            &complete_swap.token_program.key(),
+            recipient_config.as_deref(),
+        )?
+    )]
+    /// Recipient USDC token account. If the fallback is taken, the bridged USDC is sent to this
+    /// account instead of performing the swap. This account is the recipient's associated
+    /// token account (created if it does not exist) unless the recipient allows any token account
+    /// in its recipient config.
+    ///
+    /// CHECK: Mutable USDC token account whose owner is the recipient.
+    recipient_usdc_token: UncheckedAccount<'info>,
+
+    /// CHECK: This account must be the owner of the recipient token account. The recipient token
+    /// account must be encoded in the prepared fill.
+    #[account(mut)]
+    recipient: UncheckedAccount<'info>,
+
+    /// Recipient's redeem preferences. If the recipient allows it, the recipient token accounts can
+    /// be any token accounts owned by the recipient.
+    #[account(
+        seeds = [
+            RecipientConfig::SEED_PREFIX,
+            recipient.key().as_ref(),
+        ],
+        bump = recipient_config.bump,
+    )]
+    recipient_config: Option<Account<'info, RecipientConfig>>,
+}
+
+pub fn complete_swap_or_fallback<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CompleteSwapOrFallback<'info>>,
+    instruction_data: Vec<u8>,
+) -> Result<()>
+where
+    'c: 'info,
+{
+    let complete_swap_accounts = &mut ctx.accounts.complete_swap;
+
+    // Read message to determine redeem mode.
+    let swap_msg = complete_swap_accounts.read_message()?;
+
+    require!(
+        matches!(swap_msg.redeem_mode(), RedeemModeRef::Direct),
+        SwapLayerError::InvalidRedeemMode
+    );
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        Pubkey::from(swap_msg.recipient()),
+        SwapLayerError::InvalidRecipient
+    );
+
+    let limit_amount = match swap_msg.output_token() {
+        OutputTokenRef::Gas(OutputSwapRef { limit_amount, .. })
+        | OutputTokenRef::Other {
+            swap: OutputSwapRef { limit_amount, .. },
+            ..
+        } => u64::try_from(limit_amount).map_err(|_| SwapLayerError::InvalidLimitAmount)?,
+        OutputTokenRef::Usdc => return err!(SwapLayerError::InvalidOutputToken),
+    };
+
+    // Jupiter V6 CPI failures cannot be caught, so whether to swap is decided before the swap is
+    // attempted. Once the source peer's swap time limit has elapsed, the bridged USDC is delivered
+    // instead. Before then, the recipient can also take the fallback if its quote (after slippage)
+    // cannot meet the encoded limit amount. Only the recipient can do this so that a relayer cannot
+    // grief the swap by providing a bad quote.
+    let is_swap_time_limit_exceeded = complete_swap_accounts.is_swap_time_limit_exceeded()?;
+    let quoted_min_amount_out = if !is_swap_time_limit_exceeded
+        && complete_swap_accounts.payer.key() == ctx.accounts.recipient.key()
+    {
+        let swap_args = SharedAccountsRouteArgs::deserialize_checked(&instruction_data)?;
+        Some(jupiter_v6::compute_min_amount_out(&swap_args)).filter(|&amount| amount < limit_amount)
+    } else {
+        None
+    };
+
+    // Otherwise the swap must be executed with the encoded limit amount, which this program
+    // enforces regardless of the provided quote.
+    if !is_swap_time_limit_exceeded && quoted_min_amount_out.is_none() {
+        complete_swap_accounts
+            .verify_output_swap(&swap_msg, &complete_swap_accounts.dst_mint.key())?;
+
+        let in_amount = complete_swap_accounts.consume_prepared_fill()?;
+        let usdc_dust = complete_swap_jup_v6(
+            complete_swap_accounts,
+            &ctx.bumps.complete_swap,
+            ctx.remaining_accounts,
+            instruction_data,
+            in_amount,
+            swap_msg.recipient(),
+            swap_msg.output_token(),
+            &ctx.accounts.recipient,
+            &ctx.accounts.recipient_token,
+            Default::default(),
+        )?;
+
+        let source_chain = complete_swap_accounts.fill.source_chain;
+        complete_swap_accounts.fee_vault.collect(
+            source_chain,
+            Default::default(),
+            usdc_dust,
+            false,
+        );
+    } else {
+        let in_amount = complete_swap_accounts.consume_prepared_fill()?;
+
+        let prepared_fill_key = complete_swap_accounts.prepared_fill_key();
+        let swap_authority_seeds = &[
+            crate::SWAP_AUTHORITY_SEED_PREFIX,
+            prepared_fill_key.as_ref(),
+            &[ctx.bumps.complete_swap.authority],
+        ];
+
+        let payer = &complete_swap_accounts.payer;
+        let swap_authority = &complete_swap_accounts.authority;
+        let token_program = &complete_swap_accounts.token_program;
+        let dst_token_program = &complete_swap_accounts.dst_token_program;
+
+        create_recipient_token_if_needed(
+            &complete_swap_accounts.associated_token_program,
+            associated_token::Create {
+                payer: payer.to_account_info(),
+                associated_token: ctx.accounts.recipient_usdc_token.to_account_info(),
+                authority: ctx.accounts.recipient.to_account_info(),
+                mint: complete_swap_accounts.usdc.to_account_info(),
+                system_program: complete_swap_accounts.system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+        )?;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: complete_swap_accounts.src_swap_token.to_account_info(),
+                    to: ctx.accounts.recipient_usdc_token.to_account_info(),
+                    authority: swap_authority.to_account_info(),
+                },
+                &[swap_authority_seeds],
+            ),
+            in_amount,
+        )?;
+
+        // Close both swap token accounts.
+        token::close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::CloseAccount {
+                account: complete_swap_accounts.src_swap_token.to_account_info(),
+                destination: payer.to_account_info(),
+                authority: swap_authority.to_account_info(),
+            },
+            &[swap_authority_seeds],
+        ))?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            dst_token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: complete_swap_accounts.dst_swap_token.to_account_info(),
+                destination: payer.to_account_info(),
+                authority: swap_authority.to_account_info(),
+            },
+            &[swap_authority_seeds],
+        ))?;
+
+        emit!(SwapFallbackTriggered {
+            prepared_fill: prepared_fill_key,
+            recipient: ctx.accounts.recipient.key(),
+            recipient_token: ctx.accounts.recipient_usdc_token.key(),
+            amount: in_amount,
+            limit_amount,
+            quoted_min_amount_out,
+        });
+    }
+
+    Ok(())
+}
@@ -6,3 +6,9 @@ pub use payload::*;
 
 mod relay;
 pub use relay::*;
+
+mod fallback;
+pub use fallback::*;
+
+mod recipient_override;
+pub use recipient_override::*;
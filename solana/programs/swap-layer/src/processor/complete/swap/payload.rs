@@ -5,17 +5,13 @@ use crate::{
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token, token, token_interface};
-use swap_layer_messages::{
-    messages::SwapMessageV1,
-    types::{OutputToken, RedeemMode},
-};
+use swap_layer_messages::types::{OutputTokenRef, RedeemModeRef};
 
 #[derive(Accounts)]
 pub struct CompleteSwapPayload<'info> {
     #[account(mut)]
     payer: Signer<'info>,
 
-    #[account(constraint = consume_swap_layer_fill.is_valid_output_swap(&dst_mint)?)]
     consume_swap_layer_fill: ConsumeSwapLayerFill<'info>,
 
     #[account(
@@ -23,7 +19,7 @@ pub struct CompleteSwapPayload<'info> {
         payer = payer,
         space = StagedInbound::try_compute_size_if_needed(
             staged_inbound,
-            consume_swap_layer_fill.read_message_unchecked()
+            consume_swap_layer_fill.read_message()?
         )?,
         seeds = [
             StagedInbound::SEED_PREFIX,
@@ -55,13 +51,8 @@ pub struct CompleteSwapPayload<'info> {
     )]
     dst_swap_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
-    /// CHECK: In case the exact in swap does not use all tokens, we send residual back to this
-    /// token account.
-    #[account(
-        mut,
-        address = consume_swap_layer_fill.custodian.fee_recipient_token,
-    )]
-    fee_recipient_token: UncheckedAccount<'info>,
+    /// In case the exact in swap does not use all tokens, we send residual to this fee vault.
+    fee_vault: UsdcFeeVault<'info>,
 
     /// This account must be verified as the source mint for the swap.
     usdc: Usdc<'info>,
@@ -85,22 +76,21 @@ where
 {
     let staged_inbound = &mut ctx.accounts.staged_inbound;
 
+    let swap_msg = ctx.accounts.consume_swap_layer_fill.read_message()?;
+    ctx.accounts
+        .consume_swap_layer_fill
+        .verify_output_swap(&swap_msg, &ctx.accounts.dst_mint.key())?;
+
     let in_amount = ctx.accounts.consume_swap_layer_fill.consume_prepared_fill(
         ctx.accounts.src_swap_token.as_ref().as_ref(),
         &ctx.accounts.token_program,
     )?;
 
-    let SwapMessageV1 {
-        recipient,
-        redeem_mode,
-        output_token,
-    } = ctx
-        .accounts
-        .consume_swap_layer_fill
-        .read_message_unchecked();
+    let recipient = swap_msg.recipient();
+    let output_token = swap_msg.output_token();
 
-    match redeem_mode {
-        RedeemMode::Payload { sender, buf } => staged_inbound.set_inner(StagedInbound {
+    match swap_msg.redeem_mode() {
+        RedeemModeRef::Payload { sender, buf } => staged_inbound.set_inner(StagedInbound {
             seeds: StagedInboundSeeds {
                 prepared_fill: ctx.accounts.consume_swap_layer_fill.prepared_fill_key(),
                 bump: ctx.bumps.staged_inbound,
@@ -111,21 +101,21 @@ where
                 source_chain: ctx.accounts.consume_swap_layer_fill.fill.source_chain,
                 sender,
                 recipient: Pubkey::from(recipient),
-                is_native: matches!(&output_token, OutputToken::Gas(_)),
+                is_native: matches!(&output_token, OutputTokenRef::Gas(_)),
             },
             recipient_payload: buf.into(),
         }),
         _ => return err!(SwapLayerError::InvalidRedeemMode),
     };
 
-    handle_complete_swap_jup_v6(
+    let usdc_dust = handle_complete_swap_jup_v6(
         HandleCompleteSwap {
             payer: &ctx.accounts.payer,
             consume_swap_layer_fill: &ctx.accounts.consume_swap_layer_fill,
             swap_authority: ctx.accounts.staged_inbound.as_ref().as_ref(),
             src_swap_token: &ctx.accounts.src_swap_token,
             dst_swap_token: &ctx.accounts.dst_swap_token,
-            fee_recipient_token: &ctx.accounts.fee_recipient_token,
+            fee_vault_custody_token: &ctx.accounts.fee_vault.custody_token,
             dst_mint: &ctx.accounts.dst_mint,
             token_program: &ctx.accounts.token_program,
             system_program: &ctx.accounts.system_program,
@@ -136,12 +126,16 @@ where
         ctx.remaining_accounts,
         instruction_data,
         in_amount,
-        SwapMessageV1 {
-            recipient,
-            redeem_mode: Default::default(), // RedeemMode is not handled in this method.
-            output_token,
-        },
+        recipient,
+        output_token,
         Default::default(),
         Default::default(),
-    )
+    )?;
+
+    let source_chain = ctx.accounts.consume_swap_layer_fill.fill.source_chain;
+    ctx.accounts
+        .fee_vault
+        .collect(source_chain, Default::default(), usdc_dust, false);
+
+    Ok(())
 }
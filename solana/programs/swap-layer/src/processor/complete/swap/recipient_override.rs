@@ -0,0 +1,233 @@
+use crate::{composite::*, error::SwapLayerError, state::RecipientConfig, utils};
+use anchor_lang::{error::Error, prelude::*};
+use anchor_spl::{associated_token, token, token_interface};
+use swap_layer_messages::types::{
+    JupiterV6SwapParameters, OutputSwapRef, OutputTokenRef, RedeemModeRef, SwapTypeRef,
+};
+
+#[derive(Accounts)]
+pub struct CompleteSwapOverride<'info> {
+    /// The recipient encoded in the swap message. Only the recipient can override the output swap.
+    #[account(mut)]
+    recipient: Signer<'info>,
+
+    consume_swap_layer_fill: ConsumeSwapLayerFill<'info>,
+
+    /// CHECK: Seeds must be \["swap-authority", prepared_fill.key()\].
+    #[account(
+        seeds = [
+            crate::SWAP_AUTHORITY_SEED_PREFIX,
+            consume_swap_layer_fill.key().as_ref(),
+        ],
+        bump,
+    )]
+    authority: UncheckedAccount<'info>,
+
+    /// Temporary swap token account to receive USDC from the prepared fill. This account will be
+    /// closed at the end of this instruction.
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = usdc,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
+    )]
+    src_swap_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// Temporary swap token account to receive destination mint after the swap. This account will
+    /// be closed at the end of this instruction.
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = dst_mint,
+        associated_token::authority = authority,
+        associated_token::token_program = dst_token_program
+    )]
+    dst_swap_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = verify_recipient_token(
+            &recipient_token,
+            &recipient.key(),
+            &dst_mint.key(),
+            &dst_token_program.key(),
+            recipient_config.as_deref(),
+        )?
+    )]
+    /// Recipient token account for the chosen destination mint. This account is the recipient's
+    /// associated token account unless the recipient allows any token account in its recipient
+    /// config. The associated token account is created if it does not exist.
+    ///
+    /// CHECK: Mutable token account whose owner is the recipient and mint is the destination mint.
+    recipient_token: UncheckedAccount<'info>,
+
+    /// Recipient's redeem preferences. If the recipient allows it, the recipient token account can
+    /// be any token account owned by the recipient.
+    #[account(
+        seeds = [
+            RecipientConfig::SEED_PREFIX,
+            recipient.key().as_ref(),
+        ],
+        bump = recipient_config.bump,
+    )]
+    recipient_config: Option<Account<'info, RecipientConfig>>,
+
+    /// In case the exact in swap does not use all tokens, we send residual to this fee vault.
+    fee_vault: UsdcFeeVault<'info>,
+
+    /// This account must be verified as the source mint for the swap.
+    usdc: Usdc<'info>,
+
+    /// Destination mint chosen by the recipient. This mint may differ from the one encoded in the
+    /// swap message. If this mint is the native mint, the recipient receives lamports. This mint
+    /// must be mutable if it withholds transfer fees.
+    #[account(
+        constraint = usdc.key() != dst_mint.key() @ SwapLayerError::SameMint,
+        constraint = {
+            utils::token_2022::verify_mint_extensions(
+                &dst_mint.to_account_info(),
+                &consume_swap_layer_fill.custodian.token_2022_extension_policy,
+            )?;
+
+            true
+        }
+    )]
+    dst_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    token_program: Program<'info, token::Token>,
+    dst_token_program: Interface<'info, token_interface::TokenInterface>,
+    associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+pub fn complete_swap_override<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CompleteSwapOverride<'info>>,
+    limit_amount: u64,
+    instruction_data: Vec<u8>,
+) -> Result<()>
+where
+    'c: 'info,
+{
+    require!(limit_amount > 0, SwapLayerError::InvalidLimitAmount);
+
+    let consume_swap_layer_fill = &ctx.accounts.consume_swap_layer_fill;
+    let swap_msg = consume_swap_layer_fill.read_message()?;
+
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        Pubkey::from(swap_msg.recipient()),
+        SwapLayerError::InvalidRecipient
+    );
+
+    // A recipient relaying his own transfer pays no relaying fee, so we can allow both redeem
+    // modes here. Payloads must be staged for the recipient program, which does not apply to this
+    // instruction.
+    require!(
+        matches!(
+            swap_msg.redeem_mode(),
+            RedeemModeRef::Direct | RedeemModeRef::Relay { .. }
+        ),
+        SwapLayerError::InvalidRedeemMode
+    );
+
+    // The encoded swap can only be overridden once its deadline has passed.
+    let deadline = match swap_msg.output_token() {
+        OutputTokenRef::Gas(OutputSwapRef { deadline, .. })
+        | OutputTokenRef::Other {
+            swap: OutputSwapRef { deadline, .. },
+            ..
+        } => deadline,
+        OutputTokenRef::Usdc => return err!(SwapLayerError::InvalidOutputToken),
+    };
+    match consume_swap_layer_fill.output_swap_deadline(deadline) {
+        Ok(Some(deadline)) => require!(
+            Clock::get()?.unix_timestamp > deadline,
+            SwapLayerError::SwapDeadlineNotExceeded
+        ),
+        Ok(None) => return err!(SwapLayerError::SwapDeadlineNotExceeded),
+        // The source peer's deadline policy does not allow this swap to be executed as encoded,
+        // so the recipient can override it.
+        Err(err)
+            if err == Error::from(SwapLayerError::OutputSwapDeadlineRequired)
+                || err == Error::from(SwapLayerError::OutputSwapDeadlineTooFar) => {}
+        Err(err) => return Err(err),
+    }
+
+    let self_redemption = matches!(swap_msg.redeem_mode(), RedeemModeRef::Relay { .. });
+
+    // Consume the prepared fill. The recipient redeems his own transfer, so there is no relaying
+    // fee to deduct.
+    let in_amount = ctx.accounts.consume_swap_layer_fill.consume_prepared_fill(
+        ctx.accounts.src_swap_token.as_ref().as_ref(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // Replace the encoded output swap with the recipient's. The deadline has already been checked,
+    // so it is disabled here.
+    let swap = OutputSwapRef {
+        deadline: 0,
+        limit_amount: limit_amount.into(),
+        swap_type: SwapTypeRef::JupiterV6(JupiterV6SwapParameters {
+            dex_program_id: None,
+        }),
+    };
+    let dst_mint = ctx.accounts.dst_mint.key();
+    let output_token = if dst_mint == token::spl_token::native_mint::id() {
+        OutputTokenRef::Gas(swap)
+    } else {
+        // The recipient receives lamports if the output token is gas, so its token account is only
+        // needed otherwise.
+        create_recipient_token_if_needed(
+            &ctx.accounts.associated_token_program,
+            associated_token::Create {
+                payer: ctx.accounts.recipient.to_account_info(),
+                associated_token: ctx.accounts.recipient_token.to_account_info(),
+                authority: ctx.accounts.recipient.to_account_info(),
+                mint: ctx.accounts.dst_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.dst_token_program.to_account_info(),
+            },
+        )?;
+
+        OutputTokenRef::Other {
+            address: dst_mint.to_bytes(),
+            swap,
+        }
+    };
+
+    let usdc_dust = handle_complete_swap_jup_v6(
+        HandleCompleteSwap {
+            payer: &ctx.accounts.recipient,
+            consume_swap_layer_fill: &ctx.accounts.consume_swap_layer_fill,
+            swap_authority: &ctx.accounts.authority,
+            src_swap_token: &ctx.accounts.src_swap_token,
+            dst_swap_token: &ctx.accounts.dst_swap_token,
+            fee_vault_custody_token: &ctx.accounts.fee_vault.custody_token,
+            dst_mint: &ctx.accounts.dst_mint,
+            token_program: &ctx.accounts.token_program,
+            dst_token_program: &ctx.accounts.dst_token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        crate::SWAP_AUTHORITY_SEED_PREFIX,
+        ctx.bumps.authority,
+        ctx.remaining_accounts,
+        instruction_data,
+        in_amount,
+        swap_msg.recipient(),
+        output_token,
+        RecipientAccounts {
+            recipient: &ctx.accounts.recipient,
+            recipient_token: &ctx.accounts.recipient_token,
+        }
+        .into(),
+        Default::default(),
+    )?;
+
+    let source_chain = ctx.accounts.consume_swap_layer_fill.fill.source_chain;
+    ctx.accounts
+        .fee_vault
+        .collect(source_chain, Default::default(), usdc_dust, self_redemption);
+
+    Ok(())
+}
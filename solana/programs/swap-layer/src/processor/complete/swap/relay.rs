@@ -1,8 +1,12 @@
-use crate::utils::gas_dropoff;
-use crate::{composite::*, error::SwapLayerError};
+use crate::utils::{self, gas_dropoff};
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    state::{RecipientConfig, RegisteredRelayer},
+};
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token;
-use swap_layer_messages::{messages::SwapMessageV1, types::RedeemMode};
+use anchor_spl::{associated_token, token};
+use swap_layer_messages::types::RedeemModeRef;
 
 #[derive(Accounts)]
 pub struct CompleteSwapRelay<'info> {
@@ -10,25 +14,71 @@ pub struct CompleteSwapRelay<'info> {
 
     #[account(
         mut,
-        address = associated_token::get_associated_token_address_with_program_id(
+        constraint = verify_recipient_token(
+            &recipient_token,
             &recipient.key(),
             &complete_swap.dst_mint.key(),
-            &complete_swap.dst_token_program.key()
-        )
+            &complete_swap.dst_token_program.key(),
+            recipient_config.as_deref(),
+        )?
     )]
-    /// Recipient associated token account. The recipient authority check is necessary to ensure
-    /// that the recipient is the intended recipient of the bridged tokens.
+    /// Recipient token account. This account is the recipient's associated token account unless the
+    /// recipient allows any token account in its recipient config.
     ///
     /// If OutputToken::Other, this account will be deserialized to ensure that the recipient is
     /// the owner of this token account.
     ///
-    /// CHECK: Mutable ATA whose owner is the recipient and mint is the destination mint.
+    /// CHECK: Mutable token account whose owner is the recipient and mint is the destination mint.
     recipient_token: UncheckedAccount<'info>,
 
     /// CHECK: This account must be the owner of the recipient token account. The recipient token
     /// account must be encoded in the prepared fill.
     #[account(mut)]
     recipient: UncheckedAccount<'info>,
+
+    /// Recipient's redeem preferences. If the recipient allows it, the recipient token account can
+    /// be any token account owned by the recipient.
+    #[account(
+        seeds = [
+            RecipientConfig::SEED_PREFIX,
+            recipient.key().as_ref(),
+        ],
+        bump = recipient_config.bump,
+    )]
+    recipient_config: Option<Account<'info, RecipientConfig>>,
+
+    /// Recipient's WSOL associated token account. This account is required if the recipient
+    /// receives the gas dropoff as WSOL (see its recipient config).
+    ///
+    /// CHECK: Mutable WSOL ATA of the recipient. If this account does not exist, the gas dropoff is
+    /// transferred to the recipient as lamports.
+    #[account(
+        mut,
+        address = associated_token::get_associated_token_address(
+            &recipient.key(),
+            &token::spl_token::native_mint::ID,
+        )
+    )]
+    recipient_wsol_token: Option<UncheckedAccount<'info>>,
+
+    /// Relayer's USDC token account. If provided, the relayer's share of the relaying fee is paid
+    /// to this account. Otherwise the entire relaying fee is collected by the fee vault.
+    #[account(
+        mut,
+        token::mint = common::USDC_MINT,
+    )]
+    relayer_fee_token: Option<Box<Account<'info, token::TokenAccount>>>,
+
+    /// Registration of the payer as a relayer. This account is required to execute the relay
+    /// during the source peer's exclusivity window.
+    #[account(
+        seeds = [
+            RegisteredRelayer::SEED_PREFIX,
+            complete_swap.payer.key().as_ref(),
+        ],
+        bump = registered_relayer.bump,
+    )]
+    registered_relayer: Option<Account<'info, RegisteredRelayer>>,
 }
 
 pub fn complete_swap_relay<'a, 'b, 'c, 'info>(
@@ -38,33 +88,25 @@ pub fn complete_swap_relay<'a, 'b, 'c, 'info>(
 where
     'c: 'info,
 {
-    let swap_msg = ctx.accounts.complete_swap.read_message_unchecked();
+    let swap_msg = ctx.accounts.complete_swap.read_and_verify_message()?;
 
-    match swap_msg.redeem_mode {
-        RedeemMode::Relay {
+    let (gas_dropoff, relaying_fee) = match swap_msg.redeem_mode() {
+        RedeemModeRef::Relay {
             gas_dropoff,
             relaying_fee,
-        } => handle_complete_swap_relay(
-            ctx,
-            instruction_data,
-            swap_msg,
+        } => (
             gas_dropoff::denormalize_gas_dropoff(gas_dropoff),
-            relaying_fee.into(),
+            u64::from(relaying_fee),
         ),
-        _ => err!(SwapLayerError::InvalidRedeemMode),
-    }
-}
+        _ => return err!(SwapLayerError::InvalidRedeemMode),
+    };
+
+    ctx.accounts.complete_swap.verify_relayer_exclusivity(
+        &ctx.accounts.complete_swap.payer.key(),
+        &ctx.accounts.recipient.key(),
+        ctx.accounts.registered_relayer.is_some(),
+    )?;
 
-pub fn handle_complete_swap_relay<'a, 'b, 'c, 'info>(
-    ctx: Context<'a, 'b, 'c, 'info, CompleteSwapRelay<'info>>,
-    instruction_data: Vec<u8>,
-    swap_msg: SwapMessageV1,
-    gas_dropoff: u64,
-    relaying_fee: u64,
-) -> Result<()>
-where
-    'c: 'info,
-{
     // Consume prepared fill.
     let fill_amount = ctx.accounts.complete_swap.consume_prepared_fill()?;
     let payer = &ctx.accounts.complete_swap.payer;
@@ -72,26 +114,109 @@ where
 
     // Handle the relayer fee and gas dropoff. Override the relaying fee to zero
     // if the payer is the recipient (self redemption).
-    let (in_amount, gas_dropoff) = if payer.key() != recipient.key() {
-        (
-            fill_amount
-                .checked_sub(relaying_fee)
-                .ok_or_else(|| SwapLayerError::InvalidRelayerFee)?,
-            gas_dropoff.into(),
-        )
+    let self_redemption = payer.key() == recipient.key();
+    let (relaying_fee, gas_dropoff) = if !self_redemption {
+        (relaying_fee, gas_dropoff.into())
     } else {
-        (fill_amount, None)
+        (0, None)
+    };
+    let in_amount = fill_amount
+        .checked_sub(relaying_fee)
+        .ok_or_else(|| SwapLayerError::InvalidRelayerFee)?;
+
+    // Split the relaying fee between the relayer (if it provided a token account) and the
+    // protocol.
+    let (protocol_fee, relayer_fee) = match &ctx.accounts.relayer_fee_token {
+        Some(_) => utils::relayer_fees::split_relaying_fee(
+            relaying_fee,
+            ctx.accounts
+                .complete_swap
+                .source_peer
+                .relaying_fee_protocol_share,
+        ),
+        None => (relaying_fee, 0),
+    };
+
+    // Transfer the relayer's share to the relayer before the swap.
+    if let Some(relayer_fee_token) = &ctx.accounts.relayer_fee_token {
+        if relayer_fee > 0 {
+            let complete_swap = &ctx.accounts.complete_swap;
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    complete_swap.token_program.to_account_info(),
+                    token::Transfer {
+                        from: complete_swap.src_swap_token.to_account_info(),
+                        to: relayer_fee_token.to_account_info(),
+                        authority: complete_swap.authority.to_account_info(),
+                    },
+                    &[&[
+                        crate::SWAP_AUTHORITY_SEED_PREFIX,
+                        complete_swap.prepared_fill_key().as_ref(),
+                        &[ctx.bumps.complete_swap.authority],
+                    ]],
+                ),
+                relayer_fee,
+            )?;
+        }
+    }
+
+    // If the recipient prefers WSOL, the gas dropoff is delivered after the swap instead of with
+    // the swap output.
+    let wsol_gas_dropoff = match gas_dropoff {
+        Some(amount) if amount > 0 => WsolGasDropoffAccounts::try_new(
+            ctx.accounts.recipient_config.as_deref(),
+            ctx.accounts
+                .recipient_wsol_token
+                .as_ref()
+                .map(AsRef::as_ref),
+            &ctx.accounts.complete_swap.token_program,
+        )?
+        .map(|wsol| (wsol, amount)),
+        _ => None,
+    };
+    let gas_dropoff = match wsol_gas_dropoff {
+        Some(_) => None,
+        None => gas_dropoff,
     };
 
-    complete_swap_jup_v6(
+    // The protocol's share of the relaying fee is not swapped, so it is transferred to the fee
+    // vault along with any residual from the swap.
+    let usdc_residual = complete_swap_jup_v6(
         &ctx.accounts.complete_swap,
         &ctx.bumps.complete_swap,
         ctx.remaining_accounts,
         instruction_data,
         in_amount,
-        swap_msg,
+        swap_msg.recipient(),
+        swap_msg.output_token(),
         &ctx.accounts.recipient,
         &ctx.accounts.recipient_token,
         gas_dropoff,
-    )
+    )?;
+
+    if let Some((wsol, gas_dropoff)) = wsol_gas_dropoff {
+        let complete_swap = &ctx.accounts.complete_swap;
+
+        transfer_gas_dropoff(
+            GasDropoffAccounts {
+                payer: complete_swap.payer.to_account_info(),
+                recipient: ctx.accounts.recipient.to_account_info(),
+                system_program: complete_swap.system_program.to_account_info(),
+                wsol: wsol.into(),
+            },
+            gas_dropoff,
+        )?;
+    }
+
+    let complete_swap = &mut ctx.accounts.complete_swap;
+    let source_chain = complete_swap.fill.source_chain;
+    complete_swap.fee_vault.collect(
+        source_chain,
+        protocol_fee,
+        usdc_residual.saturating_sub(protocol_fee),
+        self_redemption,
+    );
+
+    Ok(())
 }
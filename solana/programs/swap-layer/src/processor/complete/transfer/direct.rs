@@ -1,66 +1,94 @@
-use crate::{composite::*, error::SwapLayerError};
+use crate::{composite::*, error::SwapLayerError, state::RecipientConfig};
 use anchor_lang::prelude::*;
-use anchor_spl::token;
-use swap_layer_messages::types::{OutputToken, RedeemMode};
+use anchor_spl::{associated_token, token};
+use swap_layer_messages::types::{OutputTokenRef, RedeemModeRef};
 
 #[derive(Accounts)]
 pub struct CompleteTransferDirect<'info> {
-    #[account(
-        constraint = {
-            let swap_msg = consume_swap_layer_fill.read_message_unchecked();
-
-            require_keys_eq!(
-                recipient.key(),
-                Pubkey::from(swap_msg.recipient),
-                SwapLayerError::InvalidRecipient
-            );
+    /// The payer of the transaction. This payer pays for the recipient associated token account if
+    /// it does not exist yet.
+    #[account(mut)]
+    payer: Signer<'info>,
 
-            require!(
-                matches!(
-                    swap_msg.output_token,
-                    OutputToken::Usdc
-                ),
-                SwapLayerError::InvalidOutputToken
-            );
-
-            true
-        }
-    )]
     consume_swap_layer_fill: ConsumeSwapLayerFill<'info>,
 
     #[account(
         mut,
-        associated_token::mint = common::USDC_MINT,
-        associated_token::authority = recipient
+        constraint = verify_recipient_token(
+            &recipient_token_account,
+            &recipient.key(),
+            &common::USDC_MINT,
+            &token_program.key(),
+            recipient_config.as_deref(),
+        )?
     )]
-    /// Recipient associated token account. The recipient authority check
-    /// is necessary to ensure that the recipient is the intended recipient
-    /// of the bridged tokens. Mutable.
-    recipient_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// Recipient token account. This account is the recipient's associated
+    /// token account (created if it does not exist) unless the recipient
+    /// allows any token account in its recipient config. The recipient
+    /// authority check is necessary to ensure that the recipient is the
+    /// intended recipient of the bridged tokens.
+    ///
+    /// CHECK: Mutable USDC token account whose owner is the recipient.
+    recipient_token_account: UncheckedAccount<'info>,
 
     /// CHECK: This account must be the owner of the recipient token account. The
     /// recipient token account must be encoded in the prepared fill. This recipient
     /// must also be encoded in the swap message.
     recipient: UncheckedAccount<'info>,
 
+    /// Recipient's redeem preferences. If the recipient allows it, the recipient token account can
+    /// be any token account owned by the recipient.
+    #[account(
+        seeds = [
+            RecipientConfig::SEED_PREFIX,
+            recipient.key().as_ref(),
+        ],
+        bump = recipient_config.bump,
+    )]
+    recipient_config: Option<Account<'info, RecipientConfig>>,
+
+    usdc: Usdc<'info>,
+
     token_program: Program<'info, token::Token>,
+    associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    system_program: Program<'info, System>,
 }
 
 pub fn complete_transfer_direct(ctx: Context<CompleteTransferDirect>) -> Result<()> {
-    match ctx
-        .accounts
-        .consume_swap_layer_fill
-        .read_message_unchecked()
-        .redeem_mode
-    {
-        RedeemMode::Direct => ctx
-            .accounts
-            .consume_swap_layer_fill
-            .consume_prepared_fill(
-                ctx.accounts.recipient_token_account.as_ref().as_ref(),
-                &ctx.accounts.token_program,
-            )
-            .map(|_| ()),
+    let swap_msg = ctx.accounts.consume_swap_layer_fill.read_message()?;
+
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        Pubkey::from(swap_msg.recipient()),
+        SwapLayerError::InvalidRecipient
+    );
+    require!(
+        matches!(swap_msg.output_token(), OutputTokenRef::Usdc),
+        SwapLayerError::InvalidOutputToken
+    );
+
+    match swap_msg.redeem_mode() {
+        RedeemModeRef::Direct => {
+            create_recipient_token_if_needed(
+                &ctx.accounts.associated_token_program,
+                associated_token::Create {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    associated_token: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.recipient.to_account_info(),
+                    mint: ctx.accounts.usdc.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            )?;
+
+            ctx.accounts
+                .consume_swap_layer_fill
+                .consume_prepared_fill(
+                    &ctx.accounts.recipient_token_account,
+                    &ctx.accounts.token_program,
+                )
+                .map(|_| ())
+        }
         _ => err!(SwapLayerError::InvalidRedeemMode),
     }
 }
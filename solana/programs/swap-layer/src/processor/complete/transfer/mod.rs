@@ -6,3 +6,6 @@ pub use payload::*;
 
 mod relay;
 pub use relay::*;
+
+mod relay_payload;
+pub use relay_payload::*;
@@ -5,28 +5,13 @@ use crate::{
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token;
-use swap_layer_messages::types::{OutputToken, RedeemMode};
+use swap_layer_messages::types::{OutputTokenRef, RedeemModeRef};
 
 #[derive(Accounts)]
 pub struct CompleteTransferPayload<'info> {
     #[account(mut)]
     payer: Signer<'info>,
 
-    #[account(
-        constraint = {
-            let swap_msg = consume_swap_layer_fill.read_message_unchecked();
-
-            require!(
-                matches!(
-                    swap_msg.output_token,
-                    OutputToken::Usdc
-                ),
-                SwapLayerError::InvalidOutputToken
-            );
-
-            true
-        }
-    )]
     consume_swap_layer_fill: ConsumeSwapLayerFill<'info>,
 
     #[account(
@@ -34,7 +19,7 @@ pub struct CompleteTransferPayload<'info> {
         payer = payer,
         space = StagedInbound::try_compute_size_if_needed(
             staged_inbound,
-            consume_swap_layer_fill.read_message_unchecked()
+            consume_swap_layer_fill.read_message()?
         )?,
         seeds = [
             StagedInbound::SEED_PREFIX,
@@ -70,7 +55,12 @@ pub struct CompleteTransferPayload<'info> {
 }
 
 pub fn complete_transfer_payload(ctx: Context<CompleteTransferPayload>) -> Result<()> {
-    let staged_inbound = &mut ctx.accounts.staged_inbound;
+    let swap_msg = ctx.accounts.consume_swap_layer_fill.read_message()?;
+
+    require!(
+        matches!(swap_msg.output_token(), OutputTokenRef::Usdc),
+        SwapLayerError::InvalidOutputToken
+    );
 
     // Consume the prepared fill, and send the tokens to the staged custody account.
     ctx.accounts.consume_swap_layer_fill.consume_prepared_fill(
@@ -78,14 +68,9 @@ pub fn complete_transfer_payload(ctx: Context<CompleteTransferPayload>) -> Resul
         &ctx.accounts.token_program,
     )?;
 
-    let swap_msg = ctx
-        .accounts
-        .consume_swap_layer_fill
-        .read_message_unchecked();
-
-    match swap_msg.redeem_mode {
-        RedeemMode::Payload { sender, buf } => {
-            staged_inbound.set_inner(StagedInbound {
+    match swap_msg.redeem_mode() {
+        RedeemModeRef::Payload { sender, buf } => {
+            ctx.accounts.staged_inbound.set_inner(StagedInbound {
                 seeds: StagedInboundSeeds {
                     prepared_fill: ctx.accounts.consume_swap_layer_fill.prepared_fill_key(),
                     bump: ctx.bumps.staged_inbound,
@@ -95,7 +80,7 @@ pub fn complete_transfer_payload(ctx: Context<CompleteTransferPayload>) -> Resul
                     staged_by: ctx.accounts.payer.key(),
                     source_chain: ctx.accounts.consume_swap_layer_fill.fill.source_chain,
                     sender,
-                    recipient: Pubkey::from(swap_msg.recipient),
+                    recipient: Pubkey::from(swap_msg.recipient()),
                     is_native: false,
                 },
                 recipient_payload: buf.into(),
@@ -1,13 +1,12 @@
 use crate::{
     composite::*,
     error::SwapLayerError,
-    state::Custodian,
+    state::{Custodian, RecipientConfig, RegisteredRelayer},
     utils::{self},
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token;
-use swap_layer_messages::types::{OutputToken, RedeemMode};
-use token_router::state::FillType;
+use anchor_spl::{associated_token, token};
+use swap_layer_messages::types::RedeemModeRef;
 
 #[derive(Accounts)]
 pub struct CompleteTransferRelay<'info> {
@@ -15,53 +14,6 @@ pub struct CompleteTransferRelay<'info> {
     /// The payer of the transaction. This could either be the recipient or a relayer.
     payer: Signer<'info>,
 
-    #[account(
-        constraint = {
-            let swap_msg = consume_swap_layer_fill.read_message_unchecked();
-
-            require_keys_eq!(
-                recipient.key(),
-                Pubkey::from(swap_msg.recipient),
-                SwapLayerError::InvalidRecipient
-            );
-
-            // Ensure that the swap time limit has been exceeded if the
-            // relayer is attempting to redeem an output token that is not USDC.
-            match swap_msg.output_token {
-                OutputToken::Usdc => {}
-                OutputToken::Gas(_) | OutputToken::Other {
-                    address: _,
-                    swap: _,
-                } => {
-                    let time_diff = Clock::get()?
-                        .unix_timestamp
-                        .saturating_sub(consume_swap_layer_fill.fill.timestamp);
-                    let swap_time_limit = &consume_swap_layer_fill
-                        .source_peer
-                        .relay_params
-                        .swap_time_limit;
-
-                    match consume_swap_layer_fill.fill.fill_type {
-                        FillType::FastFill => {
-                            require!(
-                                time_diff >= i64::from(swap_time_limit.fast_limit),
-                                SwapLayerError::SwapTimeLimitNotExceeded
-                            );
-                        }
-                        FillType::WormholeCctpDeposit => {
-                            require!(
-                                time_diff >= i64::from(swap_time_limit.finalized_limit),
-                                SwapLayerError::SwapTimeLimitNotExceeded
-                            );
-                        }
-                        FillType::Unset => return Err(SwapLayerError::UnsupportedFillType.into()),
-                    }
-                }
-            }
-
-            true
-        }
-    )]
     consume_swap_layer_fill: ConsumeSwapLayerFill<'info>,
 
     #[account(
@@ -79,13 +31,25 @@ pub struct CompleteTransferRelay<'info> {
 
     #[account(
         mut,
-        associated_token::mint = usdc,
-        associated_token::authority = recipient
+        constraint = verify_recipient_token(
+            &recipient_token_account,
+            &recipient.key(),
+            &common::USDC_MINT,
+            &token_program.key(),
+            recipient_config.as_deref(),
+        )?
     )]
-    /// Recipient associated token account. The recipient authority check
-    /// is necessary to ensure that the recipient is the intended recipient
-    /// of the bridged tokens.
-    recipient_token_account: Box<Account<'info, token::TokenAccount>>,
+    /// Recipient token account. This account is the recipient's associated
+    /// token account (created if it does not exist) unless the recipient
+    /// allows any token account in its recipient config. The recipient
+    /// authority check is necessary to ensure that the recipient is the
+    /// intended recipient of the bridged tokens.
+    /// If the payer creates the ATA, its rent is covered by the relaying fee,
+    /// which the source chain's Swap Layer quotes with the cost of an ATA's
+    /// rent for transfers to Solana.
+    ///
+    /// CHECK: Mutable USDC token account whose owner is the recipient.
+    recipient_token_account: UncheckedAccount<'info>,
 
     /// CHECK: recipient may differ from payer if a relayer paid for this
     /// transaction. This instruction verifies that the recipient key
@@ -93,27 +57,77 @@ pub struct CompleteTransferRelay<'info> {
     #[account(mut)]
     recipient: UncheckedAccount<'info>,
 
+    /// Recipient's redeem preferences. If the recipient allows it, the recipient token account can
+    /// be any token account owned by the recipient.
+    #[account(
+        seeds = [
+            RecipientConfig::SEED_PREFIX,
+            recipient.key().as_ref(),
+        ],
+        bump = recipient_config.bump,
+    )]
+    recipient_config: Option<Account<'info, RecipientConfig>>,
+
+    /// Recipient's WSOL associated token account. This account is required if the recipient
+    /// receives the gas dropoff as WSOL (see its recipient config).
+    ///
+    /// CHECK: Mutable WSOL ATA of the recipient. If this account does not exist, the gas dropoff is
+    /// transferred to the recipient as lamports.
     #[account(
         mut,
-        address = consume_swap_layer_fill.custodian.fee_recipient_token,
+        address = associated_token::get_associated_token_address(
+            &recipient.key(),
+            &token::spl_token::native_mint::ID,
+        )
     )]
-    fee_recipient_token: Account<'info, token::TokenAccount>,
+    recipient_wsol_token: Option<UncheckedAccount<'info>>,
+
+    /// Fee vault that collects the protocol's share of the relaying fee.
+    fee_vault: UsdcFeeVault<'info>,
+
+    /// Relayer's USDC token account. If provided, the relayer's share of the relaying fee is paid
+    /// to this account. Otherwise the entire relaying fee is collected by the fee vault.
+    #[account(
+        mut,
+        token::mint = usdc,
+    )]
+    relayer_fee_token: Option<Box<Account<'info, token::TokenAccount>>>,
+
+    /// Registration of the payer as a relayer. This account is required to execute the relay
+    /// during the source peer's exclusivity window.
+    #[account(
+        seeds = [
+            RegisteredRelayer::SEED_PREFIX,
+            payer.key().as_ref(),
+        ],
+        bump = registered_relayer.bump,
+    )]
+    registered_relayer: Option<Account<'info, RegisteredRelayer>>,
 
     usdc: Usdc<'info>,
 
     token_program: Program<'info, token::Token>,
+    associated_token_program: Program<'info, associated_token::AssociatedToken>,
     system_program: Program<'info, System>,
 }
 
 pub fn complete_transfer_relay(ctx: Context<CompleteTransferRelay>) -> Result<()> {
+    let consume_swap_layer_fill = &ctx.accounts.consume_swap_layer_fill;
+    let swap_msg = consume_swap_layer_fill.read_message()?;
+
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        Pubkey::from(swap_msg.recipient()),
+        SwapLayerError::InvalidRecipient
+    );
+
+    // Ensure that the swap time limit has been exceeded if the relayer is attempting to redeem an
+    // output token that is not USDC.
+    consume_swap_layer_fill.verify_swap_time_limit(swap_msg.output_token())?;
+
     // Gas dropoff needs to be scaled by 1e3 to convert into lamports.
-    match ctx
-        .accounts
-        .consume_swap_layer_fill
-        .read_message_unchecked()
-        .redeem_mode
-    {
-        RedeemMode::Relay {
+    match swap_msg.redeem_mode() {
+        RedeemModeRef::Relay {
             gas_dropoff,
             relaying_fee,
         } => handle_complete_transfer_relay(
@@ -130,6 +144,14 @@ fn handle_complete_transfer_relay(
     gas_dropoff: u64,
     relaying_fee: u64,
 ) -> Result<()> {
+    ctx.accounts
+        .consume_swap_layer_fill
+        .verify_relayer_exclusivity(
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.recipient.key(),
+            ctx.accounts.registered_relayer.is_some(),
+        )?;
+
     let complete_token = &ctx.accounts.complete_token_account;
     let token_program = &ctx.accounts.token_program;
 
@@ -149,14 +171,20 @@ fn handle_complete_transfer_relay(
             fill_amount
         } else {
             if gas_dropoff > 0 {
-                anchor_lang::system_program::transfer(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: payer.to_account_info(),
-                            to: recipient.to_account_info(),
-                        },
-                    ),
+                transfer_gas_dropoff(
+                    GasDropoffAccounts {
+                        payer: payer.to_account_info(),
+                        recipient: recipient.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        wsol: WsolGasDropoffAccounts::try_new(
+                            ctx.accounts.recipient_config.as_deref(),
+                            ctx.accounts
+                                .recipient_wsol_token
+                                .as_ref()
+                                .map(AsRef::as_ref),
+                            token_program,
+                        )?,
+                    },
                     gas_dropoff,
                 )?;
             }
@@ -168,6 +196,18 @@ fn handle_complete_transfer_relay(
         }
     };
 
+    create_recipient_token_if_needed(
+        &ctx.accounts.associated_token_program,
+        associated_token::Create {
+            payer: payer.to_account_info(),
+            associated_token: ctx.accounts.recipient_token_account.to_account_info(),
+            authority: recipient.to_account_info(),
+            mint: ctx.accounts.usdc.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    )?;
+
     // Transfer the tokens to the recipient.
     anchor_spl::token::transfer(
         CpiContext::new_with_signer(
@@ -182,21 +222,27 @@ fn handle_complete_transfer_relay(
         user_amount,
     )?;
 
-    // Transfer eligible USDC to the fee recipient.
-    if user_amount != fill_amount {
-        anchor_spl::token::transfer(
-            CpiContext::new_with_signer(
-                token_program.to_account_info(),
-                anchor_spl::token::Transfer {
-                    from: complete_token.to_account_info(),
-                    to: ctx.accounts.fee_recipient_token.to_account_info(),
-                    authority: custodian.to_account_info(),
-                },
-                &[Custodian::SIGNER_SEEDS],
-            ),
-            fill_amount.checked_sub(user_amount).unwrap(),
-        )?;
-    }
+    // Split the relaying fee between the relayer (if it provided a token account) and the
+    // protocol.
+    let protocol_fee = transfer_relaying_fee(
+        RelayingFeeAccounts {
+            token_program: token_program.to_account_info(),
+            src_token: complete_token.to_account_info(),
+            authority: custodian.to_account_info(),
+            fee_vault_custody_token: ctx.accounts.fee_vault.custody_token.to_account_info(),
+            relayer_fee_token: ctx
+                .accounts
+                .relayer_fee_token
+                .as_ref()
+                .map(|token| token.to_account_info()),
+        },
+        Custodian::SIGNER_SEEDS,
+        fill_amount.checked_sub(user_amount).unwrap(),
+        ctx.accounts
+            .consume_swap_layer_fill
+            .source_peer
+            .relaying_fee_protocol_share,
+    )?;
 
     // Finally close token account.
     token::close_account(CpiContext::new_with_signer(
@@ -207,5 +253,12 @@ fn handle_complete_transfer_relay(
             authority: custodian.to_account_info(),
         },
         &[Custodian::SIGNER_SEEDS],
-    ))
+    ))?;
+
+    let source_chain = ctx.accounts.consume_swap_layer_fill.fill.source_chain;
+    ctx.accounts
+        .fee_vault
+        .collect(source_chain, protocol_fee, Default::default(), false);
+
+    Ok(())
 }
@@ -0,0 +1,225 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    state::{RegisteredRelayer, StagedInbound, StagedInboundInfo, StagedInboundSeeds},
+    utils,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use swap_layer_messages::types::RedeemModeRef;
+
+#[derive(Accounts)]
+pub struct CompleteTransferRelayPayload<'info> {
+    #[account(mut)]
+    /// The payer of the transaction. This could either be the recipient or a relayer. This payer
+    /// pays for the staged inbound and staged custody token accounts.
+    payer: Signer<'info>,
+
+    consume_swap_layer_fill: ConsumeSwapLayerFill<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = StagedInbound::try_compute_size_if_needed(
+            staged_inbound,
+            consume_swap_layer_fill.read_message()?
+        )?,
+        seeds = [
+            StagedInbound::SEED_PREFIX,
+            consume_swap_layer_fill.prepared_fill_key().as_ref(),
+        ],
+        bump
+    )]
+    /// The staged inbound account that will be created to hold the arbitrary
+    /// payload that the recipient will receive. This account also warehouses
+    /// the seeds necessary to derive the staged custody token account.
+    staged_inbound: Account<'info, StagedInbound>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = usdc,
+        token::authority = staged_inbound,
+        seeds = [
+            crate::STAGED_CUSTODY_TOKEN_SEED_PREFIX,
+            staged_inbound.key().as_ref(),
+        ],
+        bump,
+    )]
+    /// The staged custody token account that will be created to hold the USDC
+    /// that the recipient will receive (less the relaying fee). This account is
+    /// derived from the staged inbound account.
+    staged_custody_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// CHECK: recipient may differ from payer if a relayer paid for this
+    /// transaction. This instruction verifies that the recipient key
+    /// passed in this context matches the intended recipient in the fill.
+    #[account(mut)]
+    recipient: UncheckedAccount<'info>,
+
+    /// Fee vault that collects the protocol's share of the relaying fee.
+    fee_vault: UsdcFeeVault<'info>,
+
+    /// Relayer's USDC token account. If provided, the relayer's share of the relaying fee is paid
+    /// to this account. Otherwise the entire relaying fee is collected by the fee vault.
+    #[account(
+        mut,
+        token::mint = usdc,
+    )]
+    relayer_fee_token: Option<Box<Account<'info, token::TokenAccount>>>,
+
+    /// Registration of the payer as a relayer. This account is required to execute the relay
+    /// during the source peer's exclusivity window.
+    #[account(
+        seeds = [
+            RegisteredRelayer::SEED_PREFIX,
+            payer.key().as_ref(),
+        ],
+        bump = registered_relayer.bump,
+    )]
+    registered_relayer: Option<Account<'info, RegisteredRelayer>>,
+
+    usdc: Usdc<'info>,
+
+    token_program: Program<'info, token::Token>,
+    system_program: Program<'info, System>,
+}
+
+pub fn complete_transfer_relay_payload(ctx: Context<CompleteTransferRelayPayload>) -> Result<()> {
+    let consume_swap_layer_fill = &ctx.accounts.consume_swap_layer_fill;
+    let swap_msg = consume_swap_layer_fill.read_message()?;
+
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        Pubkey::from(swap_msg.recipient()),
+        SwapLayerError::InvalidRecipient
+    );
+
+    // Ensure that the swap time limit has been exceeded if the relayer is attempting to redeem an
+    // output token that is not USDC.
+    consume_swap_layer_fill.verify_swap_time_limit(swap_msg.output_token())?;
+
+    match swap_msg.redeem_mode() {
+        RedeemModeRef::RelayPayload {
+            gas_dropoff,
+            relaying_fee,
+            sender,
+            buf,
+        } => {
+            let recipient_payload = buf.into();
+            let recipient = Pubkey::from(swap_msg.recipient());
+
+            handle_complete_transfer_relay_payload(
+                ctx,
+                utils::gas_dropoff::denormalize_gas_dropoff(gas_dropoff),
+                relaying_fee.into(),
+                sender,
+                recipient_payload,
+                recipient,
+            )
+        }
+        _ => err!(SwapLayerError::InvalidRedeemMode),
+    }
+}
+
+fn handle_complete_transfer_relay_payload(
+    ctx: Context<CompleteTransferRelayPayload>,
+    gas_dropoff: u64,
+    relaying_fee: u64,
+    sender: [u8; 32],
+    recipient_payload: Vec<u8>,
+    recipient: Pubkey,
+) -> Result<()> {
+    ctx.accounts
+        .consume_swap_layer_fill
+        .verify_relayer_exclusivity(
+            &ctx.accounts.payer.key(),
+            &ctx.accounts.recipient.key(),
+            ctx.accounts.registered_relayer.is_some(),
+        )?;
+
+    let staged_custody_token = &ctx.accounts.staged_custody_token;
+    let token_program = &ctx.accounts.token_program;
+
+    // Consume the prepared fill, and send the tokens to the staged custody account.
+    let fill_amount = ctx
+        .accounts
+        .consume_swap_layer_fill
+        .consume_prepared_fill(staged_custody_token.as_ref().as_ref(), token_program)?;
+
+    let payer = &ctx.accounts.payer;
+
+    // The relaying fee is only charged if a relayer completes this transfer on behalf of the
+    // recipient.
+    if payer.key() != recipient {
+        if gas_dropoff > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: ctx.accounts.recipient.to_account_info(),
+                    },
+                ),
+                gas_dropoff,
+            )?;
+        }
+
+        require!(
+            fill_amount >= relaying_fee,
+            SwapLayerError::InvalidRelayerFee
+        );
+
+        let prepared_fill = ctx.accounts.consume_swap_layer_fill.prepared_fill_key();
+
+        // Split the relaying fee between the relayer (if it provided a token account) and the
+        // protocol. The staged inbound account is the authority of the staged custody token.
+        let protocol_fee = transfer_relaying_fee(
+            RelayingFeeAccounts {
+                token_program: token_program.to_account_info(),
+                src_token: staged_custody_token.to_account_info(),
+                authority: ctx.accounts.staged_inbound.to_account_info(),
+                fee_vault_custody_token: ctx.accounts.fee_vault.custody_token.to_account_info(),
+                relayer_fee_token: ctx
+                    .accounts
+                    .relayer_fee_token
+                    .as_ref()
+                    .map(|token| token.to_account_info()),
+            },
+            &[
+                StagedInbound::SEED_PREFIX,
+                prepared_fill.as_ref(),
+                &[ctx.bumps.staged_inbound],
+            ],
+            relaying_fee,
+            ctx.accounts
+                .consume_swap_layer_fill
+                .source_peer
+                .relaying_fee_protocol_share,
+        )?;
+
+        let source_chain = ctx.accounts.consume_swap_layer_fill.fill.source_chain;
+        ctx.accounts
+            .fee_vault
+            .collect(source_chain, protocol_fee, Default::default(), false);
+    }
+
+    ctx.accounts.staged_inbound.set_inner(StagedInbound {
+        seeds: StagedInboundSeeds {
+            prepared_fill: ctx.accounts.consume_swap_layer_fill.prepared_fill_key(),
+            bump: ctx.bumps.staged_inbound,
+        },
+        info: StagedInboundInfo {
+            custody_token: ctx.accounts.staged_custody_token.key(),
+            staged_by: ctx.accounts.payer.key(),
+            source_chain: ctx.accounts.consume_swap_layer_fill.fill.source_chain,
+            sender,
+            recipient,
+            is_native: false,
+        },
+        recipient_payload,
+    });
+
+    // Done.
+    Ok(())
+}
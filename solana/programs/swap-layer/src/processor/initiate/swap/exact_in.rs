@@ -1,8 +1,8 @@
 use crate::{
     composite::*,
     error::SwapLayerError,
-    state::{Custodian, Peer, StagedOutbound},
-    PREPARED_ORDER_SEED_PREFIX,
+    state::{Custodian, FeeVault, Peer, StagedOutbound},
+    utils, PREPARED_ORDER_SEED_PREFIX,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token, token, token_interface};
@@ -30,6 +30,15 @@ pub struct InitiateSwapExactIn<'info> {
     #[account(
         mut,
         constraint = staged_outbound.info.is_exact_in @ SwapLayerError::ExactInRequired,
+        constraint = !staged_outbound.info.is_initiated @ SwapLayerError::StagedOutboundAlreadyInitiated,
+        constraint = {
+            require!(
+                !staged_outbound.is_expired(Clock::get()?.unix_timestamp),
+                SwapLayerError::StagedOutboundExpired
+            );
+
+            true
+        }
     )]
     staged_outbound: Account<'info, StagedOutbound>,
 
@@ -48,6 +57,24 @@ pub struct InitiateSwapExactIn<'info> {
     )]
     staged_custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
+    /// Fee vault for the source mint. If provided (along with its custody token account), any
+    /// residual after the swap is collected by this vault instead of being kept in the staged
+    /// custody token account. Because the residual belongs to the sender, this vault can only be
+    /// provided if the sender is the payer.
+    #[account(
+        mut,
+        seeds = [
+            FeeVault::SEED_PREFIX,
+            src_mint.key().as_ref(),
+        ],
+        bump = src_fee_vault.seeds.bump,
+    )]
+    src_fee_vault: Option<Box<Account<'info, FeeVault>>>,
+
+    /// CHECK: Mutable, seeds must be \["fee-vault-custody", src_fee_vault.key()\].
+    #[account(mut)]
+    src_fee_vault_custody_token: Option<UncheckedAccount<'info>>,
+
     /// CHECK: This account must equal the usdc refund token encoded in the staged outbound account.
     #[account(address = staged_outbound.usdc_refund_token)]
     usdc_refund_token: UncheckedAccount<'info>,
@@ -97,7 +124,8 @@ pub struct InitiateSwapExactIn<'info> {
     )]
     dst_swap_token: Box<Account<'info, token::TokenAccount>>,
 
-    /// This account must be verified as the source mint for the swap.
+    /// This account must be verified as the source mint for the swap. This mint must be mutable if
+    /// it withholds transfer fees, which are harvested before closing the token accounts.
     src_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
 
     /// This account must be verified as the destination mint for the swap.
@@ -139,6 +167,14 @@ where
     ];
 
     let src_mint = &ctx.accounts.src_mint;
+
+    // Moving the staged tokens to the source swap token account withholds the source mint's
+    // transfer fee (if any) again, so the swap can only spend what remains.
+    let swap_amount = utils::token_2022::amount_after_transfer_fee(
+        &src_mint.to_account_info(),
+        custody_token.amount,
+    )?;
+
     token_interface::transfer_checked(
         CpiContext::new_with_signer(
             src_token_program.to_account_info(),
@@ -156,37 +192,22 @@ where
 
     let (shared_accounts_route, swap_args, _) =
         JupiterV6SharedAccountsRoute::set_up(ctx.remaining_accounts, &instruction_data[..])?;
+    require_eq!(
+        swap_args.in_amount,
+        swap_amount,
+        SwapLayerError::InvalidSwapInAmount
+    );
 
     let swap_authority = &ctx.accounts.swap_authority;
 
     // Verify remaining accounts.
-    {
-        require_keys_eq!(
-            shared_accounts_route.transfer_authority.key(),
-            swap_authority.key(),
-            SwapLayerError::InvalidSwapAuthority
-        );
-        require_keys_eq!(
-            shared_accounts_route.src_custody_token.key(),
-            ctx.accounts.src_swap_token.key(),
-            SwapLayerError::InvalidSourceSwapToken
-        );
-        require_keys_eq!(
-            shared_accounts_route.dst_custody_token.key(),
-            ctx.accounts.dst_swap_token.key(),
-            SwapLayerError::InvalidDestinationSwapToken
-        );
-        require_keys_eq!(
-            shared_accounts_route.src_mint.key(),
-            ctx.accounts.src_mint.key(),
-            SwapLayerError::InvalidSourceMint
-        );
-        require_keys_eq!(
-            shared_accounts_route.dst_mint.key(),
-            common::USDC_MINT,
-            SwapLayerError::InvalidDestinationMint
-        );
-    }
+    shared_accounts_route.verify_swap_accounts(
+        &swap_authority.key(),
+        &ctx.accounts.src_swap_token.key(),
+        &ctx.accounts.dst_swap_token.key(),
+        &ctx.accounts.src_mint.key(),
+        &common::USDC_MINT,
+    )?;
 
     let swap_msg = ctx.accounts.staged_outbound.to_swap_message_v1()?;
 
@@ -210,10 +231,8 @@ where
 
     // Verify that the usdc_amount_out is larger than the encoded relaying fee
     // if the staged outbound is a relay.
-    if let RedeemMode::Relay {
-        gas_dropoff: _,
-        relaying_fee,
-    } = swap_msg.redeem_mode
+    if let RedeemMode::Relay { relaying_fee, .. } | RedeemMode::RelayPayload { relaying_fee, .. } =
+        swap_msg.redeem_mode
     {
         require!(
             usdc_amount_out > relaying_fee.into(),
@@ -294,17 +313,57 @@ where
         &[swap_authority_seeds],
     ))?;
 
-    // If there is residual, we keep the staged accounts open.
+    // If there is residual, we either send it to the source mint's fee vault (if the sender opted
+    // in) or keep the staged accounts open so the sender can reclaim it.
+    let src_fee_vault_custody_token = match (
+        &ctx.accounts.src_fee_vault,
+        &ctx.accounts.src_fee_vault_custody_token,
+    ) {
+        (Some(fee_vault), Some(fee_vault_custody_token)) => {
+            // Only the sender can opt into giving up the residual.
+            require_keys_eq!(
+                payer.key(),
+                staged_outbound.info.sender,
+                SwapLayerError::SenderRequired
+            );
+
+            let expected = Pubkey::create_program_address(
+                &[
+                    crate::FEE_VAULT_CUSTODY_TOKEN_SEED_PREFIX,
+                    fee_vault.key().as_ref(),
+                    &[fee_vault.custody_token_bump],
+                ],
+                &crate::id(),
+            )
+            .map_err(|_| ErrorCode::ConstraintSeeds)?;
+            require_keys_eq!(
+                fee_vault_custody_token.key(),
+                expected,
+                ErrorCode::ConstraintSeeds
+            );
+
+            Some(fee_vault_custody_token)
+        }
+        (None, None) => None,
+        _ => return err!(ErrorCode::ConstraintAccountIsNone),
+    };
+    let staged_dust = match src_fee_vault_custody_token {
+        Some(_) => 0,
+        None => src_dust,
+    };
+
     if src_dust > 0 {
-        msg!("Staged dust: {}", src_dust);
+        msg!("Source dust: {}", src_dust);
 
-        // Transfer dust back to the custody token.
+        // Transfer dust to the fee vault or back to the custody token.
         token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 src_token_program.to_account_info(),
                 token_interface::TransferChecked {
                     from: src_swap_token.to_account_info(),
-                    to: custody_token.to_account_info(),
+                    to: src_fee_vault_custody_token
+                        .map(|token| token.to_account_info())
+                        .unwrap_or_else(|| custody_token.to_account_info()),
                     authority: swap_authority.to_account_info(),
                     mint: src_mint.to_account_info(),
                 },
@@ -315,6 +374,13 @@ where
         )?;
     }
 
+    // Fees withheld by the source mint must be harvested before closing the token account.
+    utils::token_2022::harvest_withheld_tokens_to_mint(
+        &src_token_program.to_account_info(),
+        &src_mint.to_account_info(),
+        &src_swap_token.to_account_info(),
+    )?;
+
     // Close the source swap token account.
     token_interface::close_account(CpiContext::new_with_signer(
         src_token_program.to_account_info(),
@@ -326,9 +392,22 @@ where
         &[swap_authority_seeds],
     ))?;
 
-    if src_dust == 0 {
+    let target_chain = staged_outbound.target_chain;
+
+    // Record that the transfer has been initiated so that it cannot be initiated or amended again.
+    // If there is residual kept in the staged custody token account, it can then be refunded.
+    ctx.accounts.staged_outbound.info.is_initiated = true;
+
+    if staged_dust == 0 {
         let prepared_by = &ctx.accounts.prepared_by;
 
+        // Fees withheld by the source mint must be harvested before closing the token account.
+        utils::token_2022::harvest_withheld_tokens_to_mint(
+            &src_token_program.to_account_info(),
+            &src_mint.to_account_info(),
+            &custody_token.to_account_info(),
+        )?;
+
         // Close the custody token account.
         token_interface::close_account(CpiContext::new_with_signer(
             src_token_program.to_account_info(),
@@ -346,6 +425,10 @@ where
             .close(prepared_by.to_account_info())?;
     }
 
+    if let Some(fee_vault) = &mut ctx.accounts.src_fee_vault {
+        collect_fees(fee_vault, target_chain, Default::default(), src_dust, false);
+    }
+
     // Done.
     Ok(())
 }
@@ -1,2 +1,5 @@
 mod exact_in;
 pub use exact_in::*;
+
+mod swap_and_initiate;
+pub use swap_and_initiate::*;
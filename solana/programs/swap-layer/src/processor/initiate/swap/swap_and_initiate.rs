@@ -0,0 +1,330 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    processor::{prepare_staged_outbound, StagedOutboundParams},
+    state::{Custodian, RedeemOption},
+    utils,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token, token_interface};
+use common::wormhole_io::TypePrefixedPayload;
+use swap_layer_messages::{messages::SwapMessageV1, types::RedeemMode};
+
+#[derive(Accounts)]
+#[instruction(args: SwapAndInitiateArgs)]
+pub struct SwapAndInitiate<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    sender: Signer<'info>,
+
+    /// Sender's token account, whose mint must be equal to the source mint. Any residual after the
+    /// swap is returned to this account.
+    #[account(
+        mut,
+        token::mint = source.src_mint,
+        token::authority = sender,
+        token::token_program = src_token_program,
+    )]
+    sender_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: This account must be a USDC token account.
+    #[account(token::mint = usdc)]
+    usdc_refund_token: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(
+        constraint = {
+            target_peer.verify_destination(args.target_chain, args.recipient)?;
+
+            true
+        }
+    )]
+    target_peer: OutboundTarget<'info>,
+
+    /// Prepared order account, which is created by the Token Router program. This account must
+    /// sign because there is no staged outbound account to derive it from.
+    #[account(mut)]
+    prepared_order: Signer<'info>,
+
+    /// CHECK: Seeds must be \["swap-authority", prepared_order.key()\].
+    #[account(
+        seeds = [
+            crate::SWAP_AUTHORITY_SEED_PREFIX,
+            prepared_order.key().as_ref(),
+        ],
+        bump,
+    )]
+    swap_authority: UncheckedAccount<'info>,
+
+    /// Temporary swap token account to receive source mint from the sender. This account will be
+    /// closed at the end of this instruction.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = source.src_mint,
+        associated_token::authority = swap_authority,
+        associated_token::token_program = src_token_program
+    )]
+    src_swap_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Temporary swap token account to receive destination mint after the swap. This account will
+    /// be closed at the end of this instruction.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = usdc,
+        associated_token::authority = swap_authority
+    )]
+    dst_swap_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// Source mint for the swap.
+    source: OutboundSource<'info>,
+
+    /// This account must be verified as the destination mint for the swap.
+    #[account(constraint = source.src_mint.key() != usdc.key() @ SwapLayerError::SameMint)]
+    usdc: Usdc<'info>,
+
+    /// CHECK: Seeds must be \["emitter"] (Token Router Program).
+    token_router_custodian: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["endpoint"\, target_chain.to_be_bytes()] (Matching Engine Program).
+    target_router_endpoint: UncheckedAccount<'info>,
+
+    /// CHECK: Mutable, seeds must be \["prepared-custody", prepared_order.key()\]
+    #[account(mut)]
+    prepared_custody_token: UncheckedAccount<'info>,
+
+    token_router_program: Program<'info, token_router::program::TokenRouter>,
+    associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    src_token_program: Interface<'info, token_interface::TokenInterface>,
+    token_program: Program<'info, token::Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Arguments for [swap_and_initiate]. These arguments are the same as
+/// [StageOutboundArgs](crate::processor::StageOutboundArgs) without an expiration, where exact in
+/// is implied.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SwapAndInitiateArgs {
+    pub amount_in: u64,
+
+    /// The Wormhole chain ID of the network to transfer tokens to.
+    pub target_chain: u16,
+
+    /// The recipient of the transfer.
+    pub recipient: [u8; 32],
+
+    pub redeem_option: Option<RedeemOption>,
+
+    pub encoded_output_token: Option<Vec<u8>>,
+}
+
+pub fn swap_and_initiate<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapAndInitiate<'info>>,
+    args: SwapAndInitiateArgs,
+    instruction_data: Vec<u8>,
+) -> Result<()>
+where
+    'c: 'info,
+{
+    let SwapAndInitiateArgs {
+        amount_in,
+        target_chain,
+        recipient,
+        redeem_option,
+        encoded_output_token,
+    } = args;
+
+    let src_mint = &ctx.accounts.source.src_mint;
+
+    let StagedOutboundParams {
+        staged_redeem,
+        encoded_output_token,
+        transfer_amount,
+        staged_amount,
+    } = prepare_staged_outbound(
+        &ctx.accounts.target_peer,
+        &src_mint.to_account_info(),
+        amount_in,
+        true, // is_exact_in
+        redeem_option,
+        encoded_output_token,
+        None,
+    )?;
+
+    let sender = &ctx.accounts.sender;
+    let sender_token = &ctx.accounts.sender_token;
+    let src_token_program = &ctx.accounts.src_token_program;
+    let src_swap_token = &ctx.accounts.src_swap_token;
+
+    // Send the source tokens straight to the source swap token account.
+    token_interface::transfer_checked(
+        CpiContext::new(
+            src_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: sender_token.to_account_info(),
+                to: src_swap_token.to_account_info(),
+                authority: sender.to_account_info(),
+                mint: src_mint.to_account_info(),
+            },
+        ),
+        transfer_amount,
+        src_mint.decimals,
+    )?;
+
+    let (shared_accounts_route, swap_args, _) =
+        JupiterV6SharedAccountsRoute::set_up(ctx.remaining_accounts, &instruction_data[..])?;
+
+    // The swap can only spend what the source swap token account received after the source mint's
+    // transfer fee (if any) is withheld.
+    require_eq!(
+        swap_args.in_amount,
+        staged_amount,
+        SwapLayerError::InvalidSwapInAmount
+    );
+
+    let swap_authority = &ctx.accounts.swap_authority;
+    let dst_swap_token = &ctx.accounts.dst_swap_token;
+
+    // Verify remaining accounts.
+    shared_accounts_route.verify_swap_accounts(
+        &swap_authority.key(),
+        &src_swap_token.key(),
+        &dst_swap_token.key(),
+        &src_mint.key(),
+        &common::USDC_MINT,
+    )?;
+
+    let swap_msg = SwapMessageV1 {
+        recipient: recipient.into(),
+        redeem_mode: staged_redeem.try_into_redeem_mode(&sender.key())?,
+        output_token: utils::output_token::decode_output_token(&encoded_output_token)?,
+    };
+
+    let prepared_order_key = ctx.accounts.prepared_order.key();
+    let swap_authority_seeds = &[
+        crate::SWAP_AUTHORITY_SEED_PREFIX,
+        prepared_order_key.as_ref(),
+        &[ctx.bumps.swap_authority],
+    ];
+
+    // Execute swap. Keep in mind that exact in is not really exact in... so there may be residual.
+    let (usdc_amount_out, src_dust) = shared_accounts_route.swap_exact_in(
+        swap_args,
+        swap_authority_seeds,
+        ctx.remaining_accounts,
+        Default::default(),
+    )?;
+
+    // Verify that the usdc_amount_out is larger than the encoded relaying fee if this transfer is a
+    // relay.
+    if let RedeemMode::Relay { relaying_fee, .. } | RedeemMode::RelayPayload { relaying_fee, .. } =
+        swap_msg.redeem_mode
+    {
+        require!(
+            usdc_amount_out > relaying_fee.into(),
+            SwapLayerError::InsufficientAmountOut
+        );
+    }
+
+    let payer = &ctx.accounts.payer;
+    let token_program = &ctx.accounts.token_program;
+    let custodian = &ctx.accounts.source.custodian;
+
+    token::approve(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Approve {
+                to: dst_swap_token.to_account_info(),
+                delegate: custodian.to_account_info(),
+                authority: swap_authority.to_account_info(),
+            },
+            &[swap_authority_seeds],
+        ),
+        usdc_amount_out,
+    )?;
+
+    // Prepare market order as custodian.
+    token_router::cpi::prepare_market_order(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_router_program.to_account_info(),
+            token_router::cpi::accounts::PrepareMarketOrder {
+                payer: payer.to_account_info(),
+                custodian: token_router::cpi::accounts::CheckedCustodian {
+                    custodian: ctx.accounts.token_router_custodian.to_account_info(),
+                },
+                program_transfer_authority: Default::default(),
+                sender: custodian.to_account_info().into(),
+                prepared_order: ctx.accounts.prepared_order.to_account_info(),
+                sender_token: dst_swap_token.to_account_info(),
+                refund_token: ctx.accounts.usdc_refund_token.to_account_info(),
+                prepared_custody_token: ctx.accounts.prepared_custody_token.to_account_info(),
+                usdc: token_router::cpi::accounts::Usdc {
+                    mint: ctx.accounts.usdc.to_account_info(),
+                },
+                target_router_endpoint: token_router::cpi::accounts::RegisteredEndpoint {
+                    endpoint: ctx.accounts.target_router_endpoint.to_account_info(),
+                },
+                token_program: token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[Custodian::SIGNER_SEEDS],
+        ),
+        token_router::PrepareMarketOrderArgs {
+            amount_in: usdc_amount_out,
+            min_amount_out: Default::default(),
+            target_chain,
+            redeemer: ctx.accounts.target_peer.address,
+            redeemer_message: swap_msg.to_vec(),
+        },
+    )?;
+
+    //  Close the destination swap token account.
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::CloseAccount {
+            account: dst_swap_token.to_account_info(),
+            destination: payer.to_account_info(),
+            authority: swap_authority.to_account_info(),
+        },
+        &[swap_authority_seeds],
+    ))?;
+
+    // Return any residual to the sender.
+    if src_dust > 0 {
+        msg!("Source dust: {}", src_dust);
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                src_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: src_swap_token.to_account_info(),
+                    to: sender_token.to_account_info(),
+                    authority: swap_authority.to_account_info(),
+                    mint: src_mint.to_account_info(),
+                },
+                &[swap_authority_seeds],
+            ),
+            src_dust,
+            src_mint.decimals,
+        )?;
+    }
+
+    // Fees withheld by the source mint must be harvested before closing the token account.
+    utils::token_2022::harvest_withheld_tokens_to_mint(
+        &src_token_program.to_account_info(),
+        &src_mint.to_account_info(),
+        &src_swap_token.to_account_info(),
+    )?;
+
+    // Close the source swap token account.
+    token_interface::close_account(CpiContext::new_with_signer(
+        src_token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: src_swap_token.to_account_info(),
+            destination: payer.to_account_info(),
+            authority: swap_authority.to_account_info(),
+        },
+        &[swap_authority_seeds],
+    ))
+}
@@ -28,6 +28,15 @@ pub struct InitiateTransfer<'info> {
     #[account(
         mut,
         close = prepared_by,
+        constraint = !staged_outbound.info.is_initiated @ SwapLayerError::StagedOutboundAlreadyInitiated,
+        constraint = {
+            require!(
+                !staged_outbound.is_expired(Clock::get()?.unix_timestamp),
+                SwapLayerError::StagedOutboundExpired
+            );
+
+            true
+        }
     )]
     staged_outbound: Account<'info, StagedOutbound>,
 
@@ -162,6 +171,10 @@ pub fn initiate_transfer(ctx: Context<InitiateTransfer>) -> Result<()> {
         },
     )?;
 
+    // Record that the transfer has been initiated. This account is closed by the end of the
+    // instruction.
+    ctx.accounts.staged_outbound.info.is_initiated = true;
+
     // Finally close the custody token account.
     token::close_account(CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
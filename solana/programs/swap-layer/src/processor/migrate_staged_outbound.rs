@@ -0,0 +1,76 @@
+use crate::{
+    error::SwapLayerError,
+    state::{StagedOutbound, StagedOutboundV0},
+    utils,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigrateStagedOutbound<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// Sender of the staged outbound transfer, which is read from the staged outbound's V0 layout
+    /// in the instruction handler.
+    sender: Signer<'info>,
+
+    /// Staged outbound account created with the [StagedOutboundV0] layout. This account is
+    /// reallocated to fit the current layout. The migrated transfer has no expiration and is not
+    /// recorded as initiated.
+    ///
+    /// CHECK: This account must be owned by this program and its data must have the V0 layout,
+    /// which is checked in the instruction handler.
+    #[account(
+        mut,
+        owner = crate::id(),
+    )]
+    staged_outbound: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_staged_outbound(ctx: Context<MigrateStagedOutbound>) -> Result<()> {
+    let staged_outbound = &ctx.accounts.staged_outbound;
+
+    // Only staged outbound accounts created with the V0 layout need to be migrated. The size of a
+    // V0 account is determined by its staged redeem and encoded output token, and it is smaller
+    // than the size of an account with the current layout for the same data.
+    let staged_outbound_v0 = {
+        let data = staged_outbound.try_borrow_data()?;
+        require!(
+            data.starts_with(&StagedOutbound::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        match StagedOutboundV0::deserialize(&mut &data[8..]) {
+            Ok(staged_outbound_v0) if data.len() == staged_outbound_v0.space() => {
+                staged_outbound_v0
+            }
+            _ => return err!(SwapLayerError::StagedOutboundAlreadyMigrated),
+        }
+    };
+
+    // The staged outbound cannot be loaded as a [StagedOutbound] until it is migrated, so the
+    // sender is checked against the V0 layout.
+    require_keys_eq!(
+        ctx.accounts.sender.key(),
+        staged_outbound_v0.info.sender,
+        ErrorCode::ConstraintAddress
+    );
+
+    let staged_outbound_data = StagedOutbound::from(staged_outbound_v0);
+
+    utils::migrate::realloc_account(
+        staged_outbound,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        StagedOutbound::compute_size(
+            &staged_outbound_data.staged_redeem,
+            &staged_outbound_data.encoded_output_token,
+        ),
+    )?;
+    staged_outbound_data.try_serialize(&mut &mut staged_outbound.try_borrow_mut_data()?[..])?;
+
+    // Done.
+    Ok(())
+}
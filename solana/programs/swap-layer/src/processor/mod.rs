@@ -1,3 +1,6 @@
+mod amend_staged_outbound;
+pub use amend_staged_outbound::*;
+
 mod admin;
 pub use admin::*;
 
@@ -10,8 +13,26 @@ pub use complete::*;
 mod initiate;
 pub use initiate::*;
 
+mod migrate_staged_outbound;
+pub use migrate_staged_outbound::*;
+
+mod refund_staged_outbound;
+pub use refund_staged_outbound::*;
+
 mod release_inbound;
 pub use release_inbound::*;
 
+mod set_recipient_config;
+pub use set_recipient_config::*;
+
 mod stage_outbound;
 pub use stage_outbound::*;
+
+mod stage_outbound_indexed;
+pub use stage_outbound_indexed::*;
+
+mod stage_outbound_with_intent;
+pub use stage_outbound_with_intent::*;
+
+mod transfer_usdc;
+pub use transfer_usdc::*;
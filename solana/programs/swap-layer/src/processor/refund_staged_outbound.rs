@@ -0,0 +1,134 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    events::StagedOutboundRefunded,
+    state::{Peer, StagedOutbound},
+    utils,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+#[derive(Accounts)]
+pub struct RefundStagedOutbound<'info> {
+    /// CHECK: This account must be the one who paid to create the staged outbound account.
+    #[account(
+        mut,
+        address = staged_outbound.info.prepared_by,
+    )]
+    prepared_by: UncheckedAccount<'info>,
+
+    /// Staging for outbound transfer. This instruction closes this account. The outbound swap must
+    /// have been initiated (leaving residual in the staged custody token account). Otherwise the
+    /// staged outbound must have expired.
+    #[account(
+        mut,
+        close = prepared_by,
+        constraint = {
+            require!(
+                staged_outbound.is_initiated
+                    || staged_outbound.is_expired(Clock::get()?.unix_timestamp),
+                SwapLayerError::StagedOutboundNotExpired
+            );
+
+            true
+        }
+    )]
+    staged_outbound: Account<'info, StagedOutbound>,
+
+    /// This custody token account will be closed by the end of the instruction.
+    #[account(
+        mut,
+        token::mint = src_mint,
+        token::authority = target_peer,
+        token::token_program = src_token_program,
+        seeds = [
+            crate::STAGED_CUSTODY_TOKEN_SEED_PREFIX,
+            staged_outbound.key().as_ref(),
+        ],
+        bump = staged_outbound.info.custody_token_bump,
+    )]
+    staged_custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// Acts as the authority over the staged custody token account.
+    target_peer: RegisteredPeer<'info>,
+
+    /// CHECK: This account must be the sender encoded in the staged outbound account.
+    #[account(address = staged_outbound.info.sender)]
+    sender: UncheckedAccount<'info>,
+
+    /// Sender's associated token account, which receives the residual source tokens.
+    #[account(
+        mut,
+        associated_token::mint = src_mint,
+        associated_token::authority = sender,
+        associated_token::token_program = src_token_program,
+    )]
+    sender_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// This mint must be mutable if it withholds transfer fees, which are harvested before closing
+    /// the custody token account.
+    src_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    src_token_program: Interface<'info, token_interface::TokenInterface>,
+}
+
+pub fn refund_staged_outbound(ctx: Context<RefundStagedOutbound>) -> Result<()> {
+    let src_token_program = &ctx.accounts.src_token_program;
+    let custody_token = &ctx.accounts.staged_custody_token;
+    let src_mint = &ctx.accounts.src_mint;
+
+    let peer = &ctx.accounts.target_peer;
+    let peer_signer_seeds = &[
+        Peer::SEED_PREFIX,
+        &peer.seeds.chain.to_be_bytes(),
+        &[peer.seeds.bump],
+    ];
+
+    let amount = custody_token.amount;
+
+    if amount > 0 {
+        // Transfer the residual to the sender.
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                src_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: custody_token.to_account_info(),
+                    to: ctx.accounts.sender_token.to_account_info(),
+                    authority: peer.to_account_info(),
+                    mint: src_mint.to_account_info(),
+                },
+                &[peer_signer_seeds],
+            ),
+            amount,
+            src_mint.decimals,
+        )?;
+    }
+
+    // Fees withheld by the source mint must be harvested before closing the token account.
+    utils::token_2022::harvest_withheld_tokens_to_mint(
+        &src_token_program.to_account_info(),
+        &src_mint.to_account_info(),
+        &custody_token.to_account_info(),
+    )?;
+
+    // Close the custody token account. The staged outbound account is closed by Anchor.
+    token_interface::close_account(CpiContext::new_with_signer(
+        src_token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: custody_token.to_account_info(),
+            destination: ctx.accounts.prepared_by.to_account_info(),
+            authority: peer.to_account_info(),
+        },
+        &[peer_signer_seeds],
+    ))?;
+
+    emit!(StagedOutboundRefunded {
+        staged_outbound: ctx.accounts.staged_outbound.key(),
+        sender: ctx.accounts.sender.key(),
+        sender_token: ctx.accounts.sender_token.key(),
+        mint: src_mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
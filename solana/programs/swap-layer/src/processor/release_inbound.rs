@@ -1,6 +1,6 @@
-use crate::state::StagedInbound;
+use crate::{state::StagedInbound, utils};
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface;
+use anchor_spl::{token_2022::spl_token_2022, token_interface};
 
 /// Accounts required for [consume_inbound].
 #[derive(Accounts)]
@@ -38,6 +38,8 @@ pub struct ReleaseInbound<'info> {
     )]
     staged_custody_token: InterfaceAccount<'info, token_interface::TokenAccount>,
 
+    /// This mint must be mutable if it withholds transfer fees, which are harvested before closing
+    /// the custody token account.
     mint: InterfaceAccount<'info, token_interface::Mint>,
 
     token_program: Interface<'info, token_interface::TokenInterface>,
@@ -56,19 +58,25 @@ pub fn release_inbound(ctx: Context<ReleaseInbound>) -> Result<()> {
     let token_program = &ctx.accounts.token_program;
     let mint = &ctx.accounts.mint;
 
-    token_interface::transfer_checked(
-        CpiContext::new_with_signer(
-            token_program.to_account_info(),
-            token_interface::TransferChecked {
-                from: custody_token.to_account_info(),
-                to: ctx.accounts.dst_token.to_account_info(),
-                authority: staged_inbound.to_account_info(),
-                mint: mint.to_account_info(),
-            },
-            &[staged_inbound_signer_seeds],
-        ),
+    // Token-2022 mints with the transfer hook extension require extra accounts, which are resolved
+    // from the remaining accounts.
+    spl_token_2022::onchain::invoke_transfer_checked(
+        &token_program.key(),
+        custody_token.to_account_info(),
+        mint.to_account_info(),
+        ctx.accounts.dst_token.to_account_info(),
+        staged_inbound.to_account_info(),
+        ctx.remaining_accounts,
         custody_token.amount,
         mint.decimals,
+        &[staged_inbound_signer_seeds],
+    )?;
+
+    // Fees withheld by the mint must be harvested before closing the token account.
+    utils::token_2022::harvest_withheld_tokens_to_mint(
+        &token_program.to_account_info(),
+        &mint.to_account_info(),
+        &custody_token.to_account_info(),
     )?;
 
     // Finally close token account.
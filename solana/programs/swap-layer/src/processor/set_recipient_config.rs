@@ -0,0 +1,101 @@
+use crate::{
+    composite::create_recipient_token_if_needed, error::SwapLayerError, state::RecipientConfig,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token};
+
+#[derive(Accounts)]
+pub struct SetRecipientConfig<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// Recipient of inbound transfers. This account can be a PDA signing via CPI.
+    recipient: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RecipientConfig::INIT_SPACE,
+        seeds = [
+            RecipientConfig::SEED_PREFIX,
+            recipient.key().as_ref(),
+        ],
+        bump,
+    )]
+    recipient_config: Account<'info, RecipientConfig>,
+
+    /// Recipient's WSOL associated token account. This account and the accounts below are required
+    /// if the recipient receives the gas dropoff as WSOL.
+    ///
+    /// CHECK: Mutable WSOL ATA of the recipient. This account is created if it does not exist.
+    #[account(
+        mut,
+        address = associated_token::get_associated_token_address(
+            &recipient.key(),
+            &token::spl_token::native_mint::ID,
+        )
+    )]
+    recipient_wsol_token: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: WSOL mint.
+    #[account(address = token::spl_token::native_mint::ID)]
+    native_mint: Option<UncheckedAccount<'info>>,
+
+    token_program: Option<Program<'info, token::Token>>,
+    associated_token_program: Option<Program<'info, associated_token::AssociatedToken>>,
+    system_program: Program<'info, System>,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetRecipientConfigArgs {
+    pub allow_any_token_account: bool,
+    pub gas_dropoff_as_wsol: bool,
+}
+
+pub fn set_recipient_config(
+    ctx: Context<SetRecipientConfig>,
+    args: SetRecipientConfigArgs,
+) -> Result<()> {
+    let SetRecipientConfigArgs {
+        allow_any_token_account,
+        gas_dropoff_as_wsol,
+    } = args;
+
+    // Relaying fees do not account for the rent of the recipient's WSOL account, so the payer
+    // creates it here instead of the relayer.
+    if gas_dropoff_as_wsol {
+        match (
+            &ctx.accounts.recipient_wsol_token,
+            &ctx.accounts.native_mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+        ) {
+            (
+                Some(recipient_wsol_token),
+                Some(native_mint),
+                Some(token_program),
+                Some(associated_token_program),
+            ) => create_recipient_token_if_needed(
+                associated_token_program,
+                associated_token::Create {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    associated_token: recipient_wsol_token.to_account_info(),
+                    authority: ctx.accounts.recipient.to_account_info(),
+                    mint: native_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                },
+            )?,
+            _ => return err!(SwapLayerError::RecipientWsolTokenRequired),
+        }
+    }
+
+    ctx.accounts.recipient_config.set_inner(RecipientConfig {
+        bump: ctx.bumps.recipient_config,
+        recipient: ctx.accounts.recipient.key(),
+        allow_any_token_account,
+        gas_dropoff_as_wsol,
+    });
+
+    Ok(())
+}
@@ -2,11 +2,12 @@ use crate::{
     composite::*,
     error::SwapLayerError,
     state::{Peer, RedeemOption, StagedOutbound, StagedOutboundInfo, StagedRedeem},
-    utils, TRANSFER_AUTHORITY_SEED_PREFIX,
+    utils::{self, price_feed::NativeTokenPriceSource},
+    TRANSFER_AUTHORITY_SEED_PREFIX,
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{token, token_interface};
-use common::wormhole_io::{Readable, Writeable};
+use common::wormhole_io::Writeable;
 use solana_program::keccak;
 use swap_layer_messages::types::OutputToken;
 
@@ -37,29 +38,19 @@ pub struct StageOutbound<'info> {
     /// ([StageOutboundArgs::is_native] is true).
     #[account(
         mut,
-        token::mint = src_mint,
+        token::mint = source.src_mint,
         token::token_program = src_token_program,
     )]
     sender_token: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
 
-    /// Peer used to determine whether assets are sent to a valid destination. The registered peer
-    /// will also act as the authority over the staged custody token account.
-    ///
-    /// Ordinarily we could consider the authority to be the staged outbound account itself. But
-    /// because this account can be signed for outside of this program (either keypair or PDA), the
-    /// token account would then be out of this program's control.
     #[account(
         constraint = {
-            require_eq!(
-                args.target_chain,
-                target_peer.seeds.chain,
-                SwapLayerError::InvalidTargetChain,
-            );
+            target_peer.verify_destination(args.target_chain, args.recipient)?;
 
             true
         }
     )]
-    target_peer: RegisteredPeer<'info>,
+    target_peer: OutboundTarget<'info>,
 
     /// Staged outbound account, which contains all of the instructions needed to initiate a
     /// transfer on behalf of the sender.
@@ -67,12 +58,6 @@ pub struct StageOutbound<'info> {
         init,
         payer = payer,
         space = StagedOutbound::try_compute_size(&args.redeem_option, &args.encoded_output_token)?,
-        constraint = {
-            // Cannot send to zero address.
-            require!(args.recipient != [0; 32], SwapLayerError::InvalidRecipient);
-
-            true
-        }
     )]
     staged_outbound: Account<'info, StagedOutbound>,
 
@@ -81,7 +66,7 @@ pub struct StageOutbound<'info> {
     #[account(
         init,
         payer = payer,
-        token::mint = src_mint,
+        token::mint = source.src_mint,
         token::authority = target_peer,
         token::token_program = src_token_program,
         seeds = [
@@ -98,13 +83,12 @@ pub struct StageOutbound<'info> {
     )]
     usdc_refund_token: Box<Account<'info, token::TokenAccount>>,
 
-    /// Mint can either be USDC or whichever mint is used to swap into USDC.
+    /// If the sender token account is not provided, the source mint must be the native mint.
     #[account(
-        token::token_program = src_token_program,
         constraint = {
             if sender_token.is_none() {
                 require_keys_eq!(
-                    src_mint.key(),
+                    source.src_mint.key(),
                     token::spl_token::native_mint::ID,
                     SwapLayerError::InvalidSourceMint,
                 );
@@ -113,7 +97,7 @@ pub struct StageOutbound<'info> {
             true
         }
     )]
-    src_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    source: OutboundSource<'info>,
 
     src_token_program: Interface<'info, token_interface::TokenInterface>,
     token_program: Program<'info, token::Token>,
@@ -141,6 +125,10 @@ pub struct StageOutboundArgs {
     pub redeem_option: Option<RedeemOption>,
 
     pub encoded_output_token: Option<Vec<u8>>,
+
+    /// If specified, the transfer must be initiated before this timestamp. This protects the
+    /// sender from a relaying fee that has become stale.
+    pub expires_at: Option<i64>,
 }
 
 pub fn stage_outbound(ctx: Context<StageOutbound>, args: StageOutboundArgs) -> Result<()> {
@@ -157,165 +145,90 @@ pub fn stage_outbound(ctx: Context<StageOutbound>, args: StageOutboundArgs) -> R
         recipient,
         redeem_option,
         encoded_output_token,
+        expires_at,
     } = args;
 
-    // Replace None with OutputToken::USDC encoded.
-    let encoded_output_token = encoded_output_token.unwrap_or({
-        let mut buf = Vec::with_capacity(1);
-        OutputToken::Usdc.write(&mut buf).unwrap();
-        buf
-    });
-    let output_token = OutputToken::read(&mut &encoded_output_token[..]).unwrap();
-
-    let is_usdc = ctx.accounts.src_mint.key() == common::USDC_MINT;
-
-    // Swap layer does not support exact out for swaps (yet). We catch this before we reach the
-    // initiate swap instruction.
-    require!(is_usdc || is_exact_in, SwapLayerError::ExactInRequired);
-
-    // We need to determine the relayer fee. This fee will either be paid for right now if
-    // StagedInput::Usdc or will be deducted from the USDC after a resulting swap from the source
-    // mint.
-    //
-    // NOTE: The swap instruction will revert if the amount of destination tokens is less than the
-    // calculated relaying fee. The amount of source tokens should be sufficient enough to cover the
-    // relaying fee after the swap.
-    let (transfer_amount, staged_redeem) = match redeem_option {
-        Some(redeem_option) => match redeem_option {
-            RedeemOption::Relay {
-                gas_dropoff,
-                max_relayer_fee,
-            } => {
-                // Relaying fee must be less than the user-specific maximum.
-                let relaying_fee = utils::relayer_fees::calculate_relayer_fee(
-                    &ctx.accounts.target_peer.relay_params,
-                    gas_dropoff,
-                    &output_token,
-                )?;
-                require!(
-                    relaying_fee <= max_relayer_fee,
-                    SwapLayerError::ExceedsMaxRelayingFee
-                );
-
-                (
-                    if is_usdc {
-                        if is_exact_in {
-                            require!(
-                                amount_in > relaying_fee,
-                                SwapLayerError::InsufficientAmountIn
-                            );
-
-                            amount_in
-                        } else {
-                            amount_in
-                                .checked_add(relaying_fee)
-                                .ok_or_else(|| SwapLayerError::U64Overflow)?
-                        }
-                    } else {
-                        amount_in
-                    },
-                    StagedRedeem::Relay {
-                        gas_dropoff,
-                        relaying_fee,
-                    },
-                )
-            }
-            RedeemOption::Payload(buf) => (amount_in, StagedRedeem::Payload(buf)),
-        },
-        None => (amount_in, StagedRedeem::Direct),
-    };
+    let StagedOutboundParams {
+        staged_redeem,
+        encoded_output_token,
+        transfer_amount,
+        ..
+    } = prepare_staged_outbound(
+        &ctx.accounts.target_peer,
+        &ctx.accounts.source.src_mint.to_account_info(),
+        amount_in,
+        is_exact_in,
+        redeem_option,
+        encoded_output_token,
+        expires_at,
+    )?;
 
     let src_token_program = &ctx.accounts.src_token_program;
     let custody_token = &ctx.accounts.staged_custody_token;
-    let src_mint = &ctx.accounts.src_mint;
+    let src_mint = &ctx.accounts.source.src_mint;
+
     let sender_token = ctx.accounts.sender_token.as_ref();
 
-    let sender = match sender_token {
-        Some(sender_token) => match (
-            &ctx.accounts.sender,
-            &ctx.accounts.program_transfer_authority,
-        ) {
-            (Some(sender), None) => {
-                token_interface::transfer_checked(
-                    CpiContext::new(
-                        src_token_program.to_account_info(),
-                        token_interface::TransferChecked {
-                            from: sender_token.to_account_info(),
-                            to: custody_token.to_account_info(),
-                            authority: sender.to_account_info(),
-                            mint: src_mint.to_account_info(),
-                        },
-                    ),
-                    transfer_amount,
-                    src_mint.decimals,
-                )?;
-
-                sender.key()
-            }
-            (None, Some(program_transfer_authority)) => {
-                // If the program transfer authority is used, we require that the delegated amount
-                // is exactly the amount being transferred.
-                require_eq!(
-                    sender_token.delegated_amount,
-                    transfer_amount,
-                    SwapLayerError::DelegatedAmountMismatch,
-                );
+    let sender = match (
+        &ctx.accounts.sender,
+        &ctx.accounts.program_transfer_authority,
+    ) {
+        (Some(sender), None) => {
+            transfer_from_sender(
+                SenderTransferAccounts {
+                    sender,
+                    sender_token,
+                    custody_token,
+                    src_mint,
+                    target_peer: &ctx.accounts.target_peer,
+                    src_token_program,
+                    system_program: &ctx.accounts.system_program,
+                },
+                transfer_amount,
+            )?;
+
+            sender.key()
+        }
+        (None, Some(program_transfer_authority)) => {
+            // The program transfer authority requires the sender token account.
+            let sender_token = sender_token.unwrap();
 
-                let (hashed_args, authority_bump) = last_transfer_authority_signer_seeds.unwrap();
-
-                token_interface::transfer_checked(
-                    CpiContext::new_with_signer(
-                        src_token_program.to_account_info(),
-                        token_interface::TransferChecked {
-                            from: sender_token.to_account_info(),
-                            to: custody_token.to_account_info(),
-                            authority: program_transfer_authority.to_account_info(),
-                            mint: src_mint.to_account_info(),
-                        },
-                        &[&[
-                            crate::TRANSFER_AUTHORITY_SEED_PREFIX,
-                            &hashed_args,
-                            &[authority_bump],
-                        ]],
-                    ),
-                    transfer_amount,
-                    src_mint.decimals,
-                )?;
-
-                sender_token.owner
-            }
-            _ => return err!(SwapLayerError::EitherSenderOrProgramTransferAuthority),
-        },
-        None => match &ctx.accounts.sender {
-            Some(sender) => {
-                system_program::transfer(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        system_program::Transfer {
-                            from: sender.to_account_info(),
-                            to: custody_token.to_account_info(),
-                        },
-                    ),
-                    transfer_amount,
-                )?;
-
-                let peer_seeds = &ctx.accounts.target_peer.seeds;
-                token_interface::sync_native(CpiContext::new_with_signer(
+            // If the program transfer authority is used, we require that the delegated amount
+            // is exactly the amount being transferred. This is the amount before any transfer fee
+            // is withheld, which is what the sender approves.
+            require_eq!(
+                sender_token.delegated_amount,
+                transfer_amount,
+                SwapLayerError::DelegatedAmountMismatch,
+            );
+
+            let (hashed_args, authority_bump) = last_transfer_authority_signer_seeds.unwrap();
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
                     src_token_program.to_account_info(),
-                    token_interface::SyncNative {
-                        account: custody_token.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: sender_token.to_account_info(),
+                        to: custody_token.to_account_info(),
+                        authority: program_transfer_authority.to_account_info(),
+                        mint: src_mint.to_account_info(),
                     },
                     &[&[
-                        Peer::SEED_PREFIX,
-                        &peer_seeds.chain.to_be_bytes(),
-                        &[peer_seeds.bump],
+                        crate::TRANSFER_AUTHORITY_SEED_PREFIX,
+                        &hashed_args,
+                        &[authority_bump],
                     ]],
-                ))?;
+                ),
+                transfer_amount,
+                src_mint.decimals,
+            )?;
 
-                sender.key()
-            }
-            None => return err!(SwapLayerError::SenderRequired),
-        },
+            sender_token.owner
+        }
+        _ if sender_token.is_some() => {
+            return err!(SwapLayerError::EitherSenderOrProgramTransferAuthority)
+        }
+        _ => return err!(SwapLayerError::SenderRequired),
     };
 
     ctx.accounts.staged_outbound.set_inner(StagedOutbound {
@@ -327,6 +240,8 @@ pub fn stage_outbound(ctx: Context<StageOutbound>, args: StageOutboundArgs) -> R
             target_chain,
             is_exact_in,
             recipient,
+            expires_at,
+            is_initiated: false,
         },
         staged_redeem,
         encoded_output_token,
@@ -335,3 +250,230 @@ pub fn stage_outbound(ctx: Context<StageOutbound>, args: StageOutboundArgs) -> R
     // Done.
     Ok(())
 }
+
+/// Staged outbound parameters validated against the target peer.
+pub(crate) struct StagedOutboundParams {
+    pub staged_redeem: StagedRedeem,
+    pub encoded_output_token: Vec<u8>,
+
+    /// Amount of source tokens to transfer into the staged custody token account.
+    pub transfer_amount: u64,
+
+    /// Amount of source tokens received after the source mint's transfer fee (if any) is withheld.
+    pub staged_amount: u64,
+}
+
+/// Verify that a relayed payload can be redeemed on the target peer's network. Relayed payloads are
+/// only completed as USDC transfers.
+pub(crate) fn verify_relay_payload(target_peer: &Peer, output_token: &OutputToken) -> Result<()> {
+    require!(
+        target_peer.supports_relay_payload && matches!(output_token, OutputToken::Usdc),
+        SwapLayerError::RelayPayloadNotSupported
+    );
+
+    Ok(())
+}
+
+/// Validate the outbound transfer parameters and compute the relaying fee (if any) and the amount
+/// of source tokens that must be staged.
+pub(crate) fn prepare_staged_outbound(
+    target: &OutboundTarget,
+    src_mint: &AccountInfo,
+    amount_in: u64,
+    is_exact_in: bool,
+    redeem_option: Option<RedeemOption>,
+    encoded_output_token: Option<Vec<u8>>,
+    expires_at: Option<i64>,
+) -> Result<StagedOutboundParams> {
+    let target_peer: &Peer = target;
+    let native_token_price_feed = target.native_token_price_feed();
+
+    // Cannot stage an outbound transfer that is already expired.
+    if let Some(expires_at) = expires_at {
+        require!(
+            Clock::get()?.unix_timestamp < expires_at,
+            SwapLayerError::StagedOutboundExpired
+        );
+    }
+
+    // Replace None with OutputToken::USDC encoded.
+    let encoded_output_token = encoded_output_token.unwrap_or({
+        let mut buf = Vec::with_capacity(1);
+        OutputToken::Usdc.write(&mut buf).unwrap();
+        buf
+    });
+    let output_token = utils::output_token::decode_output_token(&encoded_output_token)?;
+
+    // The output token must be redeemable on the target network.
+    utils::output_token::verify_output_token(target_peer, &output_token)?;
+
+    let is_usdc = src_mint.key() == common::USDC_MINT;
+
+    // Swap layer does not support exact out for swaps (yet). We catch this before we reach the
+    // initiate swap instruction.
+    require!(is_usdc || is_exact_in, SwapLayerError::ExactInRequired);
+
+    // We need to determine the relayer fee. This fee will either be paid for right now if
+    // StagedInput::Usdc or will be deducted from the USDC after a resulting swap from the source
+    // mint.
+    //
+    // NOTE: The swap instruction will revert if the amount of destination tokens is less than the
+    // calculated relaying fee. The amount of source tokens should be sufficient enough to cover the
+    // relaying fee after the swap.
+    let relaying_fee = |gas_dropoff, max_relayer_fee| {
+        compute_relaying_fee(
+            target_peer,
+            native_token_price_feed,
+            gas_dropoff,
+            max_relayer_fee,
+            &output_token,
+        )
+    };
+    let staged_redeem = match redeem_option {
+        Some(RedeemOption::Relay {
+            gas_dropoff,
+            max_relayer_fee,
+        }) => StagedRedeem::Relay {
+            gas_dropoff,
+            relaying_fee: relaying_fee(gas_dropoff, max_relayer_fee)?,
+        },
+        Some(RedeemOption::RelayPayload {
+            gas_dropoff,
+            max_relayer_fee,
+            payload,
+        }) => {
+            verify_relay_payload(target_peer, &output_token)?;
+
+            StagedRedeem::RelayPayload {
+                gas_dropoff,
+                relaying_fee: relaying_fee(gas_dropoff, max_relayer_fee)?,
+                payload,
+            }
+        }
+        Some(RedeemOption::Payload(buf)) => StagedRedeem::Payload(buf),
+        None => StagedRedeem::Direct,
+    };
+
+    let transfer_amount = match staged_redeem.relaying_fee() {
+        Some(relaying_fee) if is_usdc && !is_exact_in => amount_in
+            .checked_add(relaying_fee)
+            .ok_or_else(|| SwapLayerError::U64Overflow)?,
+        _ => amount_in,
+    };
+
+    // Token-2022 mints with the transfer fee extension withhold part of the transfer amount, so
+    // the custody token account receives less than what is transferred. Any checks against the
+    // staged amount must use the amount received.
+    let staged_amount = utils::token_2022::amount_after_transfer_fee(src_mint, transfer_amount)?;
+    if let Some(relaying_fee) = staged_redeem.relaying_fee() {
+        if is_usdc && is_exact_in {
+            require!(
+                staged_amount > relaying_fee,
+                SwapLayerError::InsufficientAmountIn
+            );
+        }
+    }
+
+    Ok(StagedOutboundParams {
+        staged_redeem,
+        encoded_output_token,
+        transfer_amount,
+        staged_amount,
+    })
+}
+
+/// Accounts used to transfer source tokens (or lamports) from a signing sender to the staged
+/// custody token account.
+pub(crate) struct SenderTransferAccounts<'ctx, 'info> {
+    pub sender: &'ctx Signer<'info>,
+
+    /// If not provided, the sender transfers lamports, which are wrapped into the custody token
+    /// account.
+    pub sender_token: Option<&'ctx InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub custody_token: &'ctx InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub src_mint: &'ctx InterfaceAccount<'info, token_interface::Mint>,
+
+    /// Authority over the custody token account.
+    pub target_peer: &'ctx Peer,
+    pub src_token_program: &'ctx Interface<'info, token_interface::TokenInterface>,
+    pub system_program: &'ctx Program<'info, System>,
+}
+
+pub(crate) fn transfer_from_sender(
+    accounts: SenderTransferAccounts,
+    transfer_amount: u64,
+) -> Result<()> {
+    let SenderTransferAccounts {
+        sender,
+        sender_token,
+        custody_token,
+        src_mint,
+        target_peer,
+        src_token_program,
+        system_program,
+    } = accounts;
+
+    match sender_token {
+        Some(sender_token) => token_interface::transfer_checked(
+            CpiContext::new(
+                src_token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: sender_token.to_account_info(),
+                    to: custody_token.to_account_info(),
+                    authority: sender.to_account_info(),
+                    mint: src_mint.to_account_info(),
+                },
+            ),
+            transfer_amount,
+            src_mint.decimals,
+        ),
+        None => {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: sender.to_account_info(),
+                        to: custody_token.to_account_info(),
+                    },
+                ),
+                transfer_amount,
+            )?;
+
+            let peer_seeds = &target_peer.seeds;
+            token_interface::sync_native(CpiContext::new_with_signer(
+                src_token_program.to_account_info(),
+                token_interface::SyncNative {
+                    account: custody_token.to_account_info(),
+                },
+                &[&[
+                    Peer::SEED_PREFIX,
+                    &peer_seeds.chain.to_be_bytes(),
+                    &[peer_seeds.bump],
+                ]],
+            ))
+        }
+    }
+}
+
+/// Calculate the relaying fee for the target peer. The relaying fee must be less than the
+/// user-specified maximum.
+pub(crate) fn compute_relaying_fee(
+    target_peer: &Peer,
+    native_token_price_feed: Option<&AccountInfo>,
+    gas_dropoff: u32,
+    max_relayer_fee: u64,
+    output_token: &OutputToken,
+) -> Result<u64> {
+    let relaying_fee = utils::relayer_fees::calculate_relayer_fee(
+        &target_peer.relay_params,
+        NativeTokenPriceSource::try_new(&target_peer.price_feed, native_token_price_feed)?,
+        gas_dropoff,
+        output_token,
+    )?;
+    require!(
+        relaying_fee <= max_relayer_fee,
+        SwapLayerError::ExceedsMaxRelayingFee
+    );
+
+    Ok(relaying_fee)
+}
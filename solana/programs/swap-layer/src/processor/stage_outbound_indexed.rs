@@ -0,0 +1,190 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    processor::{
+        prepare_staged_outbound, transfer_from_sender, SenderTransferAccounts, StageOutboundArgs,
+        StagedOutboundParams,
+    },
+    state::{StagedOutbound, StagedOutboundCounter, StagedOutboundInfo},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{token, token_interface};
+
+#[derive(Accounts)]
+#[instruction(args: StageOutboundArgs)]
+pub struct StageOutboundIndexed<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// This signer is mutable in case the sender is sending lamports (i.e. the sender token account
+    /// is not provided).
+    #[account(mut)]
+    sender: Signer<'info>,
+
+    /// Counter used to derive the staged outbound account. This account is created if it does not
+    /// exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StagedOutboundCounter::INIT_SPACE,
+        seeds = [
+            StagedOutboundCounter::SEED_PREFIX,
+            sender.key().as_ref(),
+        ],
+        bump,
+    )]
+    staged_outbound_counter: Account<'info, StagedOutboundCounter>,
+
+    /// If provided, this token account's mint must be equal to the source mint. Otherwise the
+    /// sender sends lamports.
+    #[account(
+        mut,
+        token::mint = source.src_mint,
+        token::token_program = src_token_program,
+    )]
+    sender_token: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        constraint = {
+            target_peer.verify_destination(args.target_chain, args.recipient)?;
+
+            true
+        }
+    )]
+    target_peer: OutboundTarget<'info>,
+
+    /// Staged outbound account, which is derived from the sender and the sender's next nonce.
+    #[account(
+        init,
+        payer = payer,
+        space = StagedOutbound::try_compute_size(&args.redeem_option, &args.encoded_output_token)?,
+        seeds = [
+            StagedOutbound::SEED_PREFIX,
+            sender.key().as_ref(),
+            &staged_outbound_counter.next_nonce.to_be_bytes(),
+        ],
+        bump,
+    )]
+    staged_outbound: Box<Account<'info, StagedOutbound>>,
+
+    /// Custody token account for the staged outbound transfer. This account will be owned by the
+    /// registered peer.
+    #[account(
+        init,
+        payer = payer,
+        token::mint = source.src_mint,
+        token::authority = target_peer,
+        token::token_program = src_token_program,
+        seeds = [
+            crate::STAGED_CUSTODY_TOKEN_SEED_PREFIX,
+            staged_outbound.key().as_ref(),
+        ],
+        bump,
+    )]
+    staged_custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = common::USDC_MINT
+    )]
+    usdc_refund_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// If the sender token account is not provided, the source mint must be the native mint.
+    #[account(
+        constraint = {
+            if sender_token.is_none() {
+                require_keys_eq!(
+                    source.src_mint.key(),
+                    token::spl_token::native_mint::ID,
+                    SwapLayerError::InvalidSourceMint,
+                );
+            }
+
+            true
+        }
+    )]
+    source: OutboundSource<'info>,
+
+    src_token_program: Interface<'info, token_interface::TokenInterface>,
+    token_program: Program<'info, token::Token>,
+    system_program: Program<'info, System>,
+}
+
+pub fn stage_outbound_indexed(
+    ctx: Context<StageOutboundIndexed>,
+    args: StageOutboundArgs,
+) -> Result<()> {
+    let StageOutboundArgs {
+        amount_in,
+        is_exact_in,
+        target_chain,
+        recipient,
+        redeem_option,
+        encoded_output_token,
+        expires_at,
+    } = args;
+
+    let StagedOutboundParams {
+        staged_redeem,
+        encoded_output_token,
+        transfer_amount,
+        ..
+    } = prepare_staged_outbound(
+        &ctx.accounts.target_peer,
+        &ctx.accounts.source.src_mint.to_account_info(),
+        amount_in,
+        is_exact_in,
+        redeem_option,
+        encoded_output_token,
+        expires_at,
+    )?;
+
+    let sender = &ctx.accounts.sender;
+
+    transfer_from_sender(
+        SenderTransferAccounts {
+            sender,
+            sender_token: ctx.accounts.sender_token.as_ref(),
+            custody_token: &ctx.accounts.staged_custody_token,
+            src_mint: &ctx.accounts.source.src_mint,
+            target_peer: &ctx.accounts.target_peer,
+            src_token_program: &ctx.accounts.src_token_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        transfer_amount,
+    )?;
+
+    ctx.accounts.staged_outbound.set_inner(StagedOutbound {
+        info: StagedOutboundInfo {
+            custody_token_bump: ctx.bumps.staged_custody_token,
+            prepared_by: ctx.accounts.payer.key(),
+            usdc_refund_token: ctx.accounts.usdc_refund_token.key(),
+            sender: sender.key(),
+            target_chain,
+            is_exact_in,
+            recipient,
+            expires_at,
+            is_initiated: false,
+        },
+        staged_redeem,
+        encoded_output_token,
+    });
+
+    // Increment the counter for the sender's next staged outbound account.
+    let next_nonce = ctx
+        .accounts
+        .staged_outbound_counter
+        .next_nonce
+        .checked_add(1)
+        .ok_or_else(|| SwapLayerError::U64Overflow)?;
+    ctx.accounts
+        .staged_outbound_counter
+        .set_inner(StagedOutboundCounter {
+            bump: ctx.bumps.staged_outbound_counter,
+            sender: sender.key(),
+            next_nonce,
+        });
+
+    // Done.
+    Ok(())
+}
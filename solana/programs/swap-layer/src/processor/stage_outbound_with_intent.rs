@@ -0,0 +1,278 @@
+use crate::{
+    composite::*,
+    error::SwapLayerError,
+    processor::{prepare_staged_outbound, StagedOutboundParams},
+    state::{RedeemOption, StagedOutbound, StagedOutboundCounter, StagedOutboundInfo},
+    utils, INTENT_AUTHORITY_SEED_PREFIX,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{token, token_interface};
+use solana_program::sysvar;
+
+#[derive(Accounts)]
+#[instruction(intent: StageOutboundIntent)]
+pub struct StageOutboundWithIntent<'info> {
+    /// The payer (e.g. a relayer) submits the sender's signed intent and pays for the staged
+    /// outbound accounts.
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// Counter used to derive the staged outbound account. The intent must encode the sender's
+    /// next nonce, which protects against replaying a signed intent. This account is created if it
+    /// does not exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + StagedOutboundCounter::INIT_SPACE,
+        seeds = [
+            StagedOutboundCounter::SEED_PREFIX,
+            intent.sender.as_ref(),
+        ],
+        bump,
+        constraint = {
+            require_eq!(
+                intent.nonce,
+                staged_outbound_counter.next_nonce,
+                SwapLayerError::InvalidIntentNonce,
+            );
+
+            true
+        }
+    )]
+    staged_outbound_counter: Account<'info, StagedOutboundCounter>,
+
+    /// CHECK: Seeds must be \["intent-authority"\]. The sender must have delegated at least the
+    /// transfer amount of the sender token account to this authority.
+    #[account(
+        seeds = [INTENT_AUTHORITY_SEED_PREFIX],
+        bump,
+    )]
+    intent_authority: UncheckedAccount<'info>,
+
+    /// Sender's token account, whose mint must be equal to the source mint.
+    #[account(
+        mut,
+        token::mint = source.src_mint,
+        token::authority = intent.sender,
+        token::token_program = src_token_program,
+    )]
+    sender_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        constraint = {
+            target_peer.verify_destination(intent.target_chain, intent.recipient)?;
+
+            true
+        }
+    )]
+    target_peer: OutboundTarget<'info>,
+
+    /// Staged outbound account, which is derived from the sender and the intent's nonce.
+    #[account(
+        init,
+        payer = payer,
+        space = StagedOutbound::try_compute_size(
+            &intent.redeem_option,
+            &intent.encoded_output_token
+        )?,
+        seeds = [
+            StagedOutbound::SEED_PREFIX,
+            intent.sender.as_ref(),
+            &intent.nonce.to_be_bytes(),
+        ],
+        bump,
+    )]
+    staged_outbound: Box<Account<'info, StagedOutbound>>,
+
+    /// Custody token account for the staged outbound transfer. This account will be owned by the
+    /// registered peer.
+    #[account(
+        init,
+        payer = payer,
+        token::mint = source.src_mint,
+        token::authority = target_peer,
+        token::token_program = src_token_program,
+        seeds = [
+            crate::STAGED_CUSTODY_TOKEN_SEED_PREFIX,
+            staged_outbound.key().as_ref(),
+        ],
+        bump,
+    )]
+    staged_custody_token: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(
+        mut,
+        address = intent.usdc_refund_token,
+        token::mint = common::USDC_MINT
+    )]
+    usdc_refund_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// The source mint must be the one specified in the intent.
+    #[account(
+        constraint = {
+            require_keys_eq!(
+                source.src_mint.key(),
+                intent.src_mint,
+                SwapLayerError::InvalidSourceMint,
+            );
+
+            true
+        }
+    )]
+    source: OutboundSource<'info>,
+
+    /// CHECK: Instructions sysvar, which is used to find the Ed25519 program instruction verifying
+    /// the sender's signature.
+    #[account(address = sysvar::instructions::ID)]
+    instructions_sysvar: UncheckedAccount<'info>,
+
+    src_token_program: Interface<'info, token_interface::TokenInterface>,
+    token_program: Program<'info, token::Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Outbound transfer signed by the sender off-chain. Anyone can submit this intent on the sender's
+/// behalf with [stage_outbound_with_intent].
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct StageOutboundIntent {
+    /// Signer of this intent, who must own the sender token account.
+    pub sender: Pubkey,
+
+    pub src_mint: Pubkey,
+
+    pub amount_in: u64,
+
+    /// See [StageOutboundArgs::is_exact_in](crate::processor::StageOutboundArgs::is_exact_in).
+    pub is_exact_in: bool,
+
+    /// The Wormhole chain ID of the network to transfer tokens to.
+    pub target_chain: u16,
+
+    /// The recipient of the transfer.
+    pub recipient: [u8; 32],
+
+    pub redeem_option: Option<RedeemOption>,
+
+    pub encoded_output_token: Option<Vec<u8>>,
+
+    /// The specified token account to refund USDC.
+    pub usdc_refund_token: Pubkey,
+
+    /// Must equal the sender's next nonce (see [StagedOutboundCounter]).
+    pub nonce: u64,
+
+    /// The intent must be submitted before this timestamp.
+    pub deadline: i64,
+
+    /// See [StageOutboundArgs::expires_at](crate::processor::StageOutboundArgs::expires_at).
+    pub expires_at: Option<i64>,
+}
+
+impl StageOutboundIntent {
+    pub const MESSAGE_PREFIX: &'static [u8] = b"swap-layer:stage-outbound-intent";
+
+    /// Message signed by the sender, which is the prefix followed by this program's ID and the
+    /// serialized intent.
+    pub fn to_message(&self) -> Result<Vec<u8>> {
+        let mut message = Vec::from(Self::MESSAGE_PREFIX);
+        message.extend_from_slice(crate::ID.as_ref());
+        self.serialize(&mut message)?;
+
+        Ok(message)
+    }
+}
+
+pub fn stage_outbound_with_intent(
+    ctx: Context<StageOutboundWithIntent>,
+    intent: StageOutboundIntent,
+) -> Result<()> {
+    // The instruction preceding this one must have verified the sender's signature of this intent.
+    utils::ed25519::verify_preceding_ed25519_instruction(
+        &ctx.accounts.instructions_sysvar,
+        &intent.sender,
+        &intent.to_message()?,
+    )?;
+
+    let StageOutboundIntent {
+        sender,
+        src_mint: _,
+        amount_in,
+        is_exact_in,
+        target_chain,
+        recipient,
+        redeem_option,
+        encoded_output_token,
+        usdc_refund_token: _,
+        nonce,
+        deadline,
+        expires_at,
+    } = intent;
+
+    require!(
+        Clock::get()?.unix_timestamp < deadline,
+        SwapLayerError::IntentExpired
+    );
+
+    let StagedOutboundParams {
+        staged_redeem,
+        encoded_output_token,
+        transfer_amount,
+        ..
+    } = prepare_staged_outbound(
+        &ctx.accounts.target_peer,
+        &ctx.accounts.source.src_mint.to_account_info(),
+        amount_in,
+        is_exact_in,
+        redeem_option,
+        encoded_output_token,
+        expires_at,
+    )?;
+
+    let src_mint = &ctx.accounts.source.src_mint;
+
+    // The token program verifies that the intent authority is delegated enough tokens.
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.src_token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.sender_token.to_account_info(),
+                to: ctx.accounts.staged_custody_token.to_account_info(),
+                authority: ctx.accounts.intent_authority.to_account_info(),
+                mint: src_mint.to_account_info(),
+            },
+            &[&[INTENT_AUTHORITY_SEED_PREFIX, &[ctx.bumps.intent_authority]]],
+        ),
+        transfer_amount,
+        src_mint.decimals,
+    )?;
+
+    ctx.accounts.staged_outbound.set_inner(StagedOutbound {
+        info: StagedOutboundInfo {
+            custody_token_bump: ctx.bumps.staged_custody_token,
+            prepared_by: ctx.accounts.payer.key(),
+            usdc_refund_token: ctx.accounts.usdc_refund_token.key(),
+            sender,
+            target_chain,
+            is_exact_in,
+            recipient,
+            expires_at,
+            is_initiated: false,
+        },
+        staged_redeem,
+        encoded_output_token,
+    });
+
+    // Consume the nonce so this intent cannot be replayed.
+    ctx.accounts
+        .staged_outbound_counter
+        .set_inner(StagedOutboundCounter {
+            bump: ctx.bumps.staged_outbound_counter,
+            sender,
+            next_nonce: nonce
+                .checked_add(1)
+                .ok_or_else(|| SwapLayerError::U64Overflow)?,
+        });
+
+    // Done.
+    Ok(())
+}
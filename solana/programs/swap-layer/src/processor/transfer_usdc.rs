@@ -0,0 +1,185 @@
+use crate::{
+    composite::*,
+    processor::{prepare_staged_outbound, StagedOutboundParams},
+    state::{Custodian, RedeemOption},
+    utils,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use common::wormhole_io::TypePrefixedPayload;
+use swap_layer_messages::messages::SwapMessageV1;
+
+#[derive(Accounts)]
+#[instruction(args: TransferUsdcArgs)]
+pub struct TransferUsdc<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    custodian: CheckedCustodian<'info>,
+
+    sender: Signer<'info>,
+
+    /// Sender's USDC token account.
+    #[account(
+        mut,
+        token::mint = usdc,
+        token::authority = sender,
+    )]
+    sender_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// Custodian-owned token account, which holds the sender's USDC for the duration of this
+    /// instruction before the Token Router moves it into the prepared custody token account. This
+    /// account is created if it does not exist and is reused by every USDC transfer.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = usdc,
+        token::authority = custodian,
+        seeds = [crate::OUTBOUND_CUSTODY_TOKEN_SEED_PREFIX],
+        bump,
+    )]
+    outbound_custody_token: Box<Account<'info, token::TokenAccount>>,
+
+    /// The specified token account to refund USDC. This account is required by the Token Router
+    /// program in case a fast order is reverted at the Matching Engine after it has been placed.
+    #[account(token::mint = usdc)]
+    usdc_refund_token: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(
+        constraint = {
+            target_peer.verify_destination(args.target_chain, args.recipient)?;
+
+            true
+        }
+    )]
+    target_peer: OutboundTarget<'info>,
+
+    /// CHECK: Seeds must be \["emitter"] (Token Router Program).
+    token_router_custodian: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds must be \["endpoint"\, target_chain.to_be_bytes()] (Matching Engine Program).
+    target_router_endpoint: UncheckedAccount<'info>,
+
+    /// Prepared order account, which is created by the Token Router program. This account must
+    /// sign because there is no staged outbound account to derive it from.
+    #[account(mut)]
+    prepared_order: Signer<'info>,
+
+    /// CHECK: Mutable, seeds must be \["prepared-custody", prepared_order.key()\].
+    #[account(mut)]
+    prepared_custody_token: UncheckedAccount<'info>,
+
+    usdc: Usdc<'info>,
+
+    token_router_program: Program<'info, token_router::program::TokenRouter>,
+    token_program: Program<'info, token::Token>,
+    system_program: Program<'info, System>,
+}
+
+/// Arguments for [transfer_usdc]. These arguments are the same as
+/// [StageOutboundArgs](crate::processor::StageOutboundArgs) without an expiration.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct TransferUsdcArgs {
+    pub amount_in: u64,
+
+    /// This argument only applies to relays. If exact in is specified, the relaying fee will be
+    /// removed from the amount in. Otherwise it will be added to the amount in to guarantee the
+    /// USDC amount specified above.
+    pub is_exact_in: bool,
+
+    /// The Wormhole chain ID of the network to transfer tokens to.
+    pub target_chain: u16,
+
+    /// The recipient of the transfer.
+    pub recipient: [u8; 32],
+
+    pub redeem_option: Option<RedeemOption>,
+
+    pub encoded_output_token: Option<Vec<u8>>,
+}
+
+pub fn transfer_usdc(ctx: Context<TransferUsdc>, args: TransferUsdcArgs) -> Result<()> {
+    let TransferUsdcArgs {
+        amount_in,
+        is_exact_in,
+        target_chain,
+        recipient,
+        redeem_option,
+        encoded_output_token,
+    } = args;
+
+    let StagedOutboundParams {
+        staged_redeem,
+        encoded_output_token,
+        transfer_amount,
+        ..
+    } = prepare_staged_outbound(
+        &ctx.accounts.target_peer,
+        &ctx.accounts.usdc,
+        amount_in,
+        is_exact_in,
+        redeem_option,
+        encoded_output_token,
+        None,
+    )?;
+
+    let sender = &ctx.accounts.sender;
+
+    let redeemer_message = SwapMessageV1 {
+        recipient: recipient.into(),
+        redeem_mode: staged_redeem.try_into_redeem_mode(&sender.key())?,
+        output_token: utils::output_token::decode_output_token(&encoded_output_token)?,
+    }
+    .to_vec();
+
+    let token_program = &ctx.accounts.token_program;
+    let custodian = &ctx.accounts.custodian;
+    let outbound_custody_token = &ctx.accounts.outbound_custody_token;
+
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.sender_token.to_account_info(),
+                to: outbound_custody_token.to_account_info(),
+                authority: sender.to_account_info(),
+            },
+        ),
+        transfer_amount,
+    )?;
+
+    // Prepare market order as custodian.
+    token_router::cpi::prepare_market_order(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_router_program.to_account_info(),
+            token_router::cpi::accounts::PrepareMarketOrder {
+                payer: ctx.accounts.payer.to_account_info(),
+                custodian: token_router::cpi::accounts::CheckedCustodian {
+                    custodian: ctx.accounts.token_router_custodian.to_account_info(),
+                },
+                program_transfer_authority: Default::default(),
+                sender: custodian.to_account_info().into(),
+                prepared_order: ctx.accounts.prepared_order.to_account_info(),
+                sender_token: outbound_custody_token.to_account_info(),
+                refund_token: ctx.accounts.usdc_refund_token.to_account_info(),
+                prepared_custody_token: ctx.accounts.prepared_custody_token.to_account_info(),
+                usdc: token_router::cpi::accounts::Usdc {
+                    mint: ctx.accounts.usdc.to_account_info(),
+                },
+                target_router_endpoint: token_router::cpi::accounts::RegisteredEndpoint {
+                    endpoint: ctx.accounts.target_router_endpoint.to_account_info(),
+                },
+                token_program: token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[Custodian::SIGNER_SEEDS],
+        ),
+        token_router::PrepareMarketOrderArgs {
+            amount_in: transfer_amount,
+            min_amount_out: None,
+            target_chain,
+            redeemer: ctx.accounts.target_peer.address,
+            redeemer_message,
+        },
+    )
+}
@@ -1,6 +1,17 @@
 use anchor_lang::prelude::*;
 use common::admin;
 
+/// Token-2022 mint extensions allowed for source and destination mints. Mints with a disallowed
+/// extension cannot be swapped or staged.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, InitSpace, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct Token2022ExtensionPolicy {
+    pub allow_transfer_hook: bool,
+    pub allow_confidential_transfer: bool,
+    pub allow_permanent_delegate: bool,
+}
+
 #[account]
 #[derive(Debug, InitSpace)]
 pub struct Custodian {
@@ -14,14 +25,34 @@ pub struct Custodian {
     /// Program's fee updater. Can be used to update fee parameters and the like.
     pub fee_updater: Pubkey,
 
-    /// Program's fee recipient. Receives relayer fees in USDC.
+    /// Program's fee recipient. Default sweep destination of the USDC fee vault.
     pub fee_recipient_token: Pubkey,
+
+    /// Token-2022 extensions allowed for source and destination mints.
+    pub token_2022_extension_policy: Token2022ExtensionPolicy,
 }
 
 impl Custodian {
     pub const SEED_PREFIX: &'static [u8] = b"custodian";
     pub const BUMP: u8 = crate::CUSTODIAN_BUMP;
     pub const SIGNER_SEEDS: &'static [&'static [u8]] = &[Self::SEED_PREFIX, &[Self::BUMP]];
+
+    /// Size of the custodian account with the current layout.
+    pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Size of the custodian account with the [CustodianV0] layout.
+    pub const V0_SPACE: usize = 8 + CustodianV0::INIT_SPACE;
+}
+
+/// Custodian account data initialized by the first version of this program, which did not have a
+/// Token-2022 extension policy. The custodian must be migrated with `migrate_custodian`.
+#[derive(Debug, InitSpace, AnchorSerialize, AnchorDeserialize)]
+pub struct CustodianV0 {
+    pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
+    pub owner_assistant: Pubkey,
+    pub fee_updater: Pubkey,
+    pub fee_recipient_token: Pubkey,
 }
 
 impl admin::Ownable for Custodian {
@@ -71,4 +102,16 @@ mod test {
             "custodian mismatch",
         );
     }
+
+    #[test]
+    fn test_v0_space() {
+        // Discriminator + owner (32) + pending owner (33) + owner assistant (32) + fee updater (32)
+        // + fee recipient token (32).
+        assert_eq!(Custodian::V0_SPACE, 169, "V0 layout changed");
+        assert_ne!(
+            Custodian::SPACE,
+            Custodian::V0_SPACE,
+            "layouts must have distinct sizes"
+        );
+    }
 }
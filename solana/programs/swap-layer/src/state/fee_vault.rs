@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeVaultSeeds {
+    pub mint: Pubkey,
+    pub bump: u8,
+}
+
+/// Running totals of fees collected by a [FeeVault], denominated in the vault's mint.
+#[derive(Debug, Default, Clone, Copy, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FeeAccounting {
+    /// Relaying fees collected from relayed redemptions.
+    pub relaying_fees: u64,
+
+    /// Residual from exact in swaps (excluding self-redemptions).
+    pub dust: u64,
+
+    /// Residual from exact in swaps for relayed transfers redeemed by the recipient himself.
+    pub self_redemptions: u64,
+
+    /// Amount moved to the destination token account.
+    pub swept: u64,
+}
+
+/// Collects fees for a specific mint. Fees are held in the vault's custody token account until
+/// they are swept to the destination token account.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct FeeVault {
+    pub seeds: FeeVaultSeeds,
+    pub custody_token_bump: u8,
+
+    /// Token account that receives swept fees.
+    pub destination_token: Pubkey,
+
+    pub accounting: FeeAccounting,
+}
+
+impl FeeVault {
+    pub const SEED_PREFIX: &'static [u8] = b"fee-vault";
+
+    /// Record fees that have been transferred to the custody token account.
+    pub fn collect(&mut self, relaying_fee: u64, dust: u64, self_redemption: bool) {
+        let accounting = &mut self.accounting;
+
+        accounting.relaying_fees = accounting.relaying_fees.saturating_add(relaying_fee);
+        if self_redemption {
+            accounting.self_redemptions = accounting.self_redemptions.saturating_add(dust);
+        } else {
+            accounting.dust = accounting.dust.saturating_add(dust);
+        }
+    }
+
+    /// Record fees that have been transferred to the destination token account.
+    pub fn sweep(&mut self, amount: u64) {
+        self.accounting.swept = self.accounting.swept.saturating_add(amount);
+    }
+}
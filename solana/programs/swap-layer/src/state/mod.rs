@@ -6,3 +6,15 @@ pub use peer::*;
 
 mod staged;
 pub use staged::*;
+
+mod fee_vault;
+pub use fee_vault::*;
+
+mod registered_relayer;
+pub use registered_relayer::*;
+
+mod recipient_config;
+pub use recipient_config::*;
+
+mod program_lookup_table;
+pub use program_lookup_table::*;
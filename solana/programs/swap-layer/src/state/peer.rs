@@ -39,6 +39,51 @@ pub struct RelayParams {
     pub swap_time_limit: SwapTimeLimit,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, InitSpace, AnchorSerialize, AnchorDeserialize)]
+pub enum PriceFeedFallback {
+    // Use the stored native token price if the feed price is stale or too uncertain.
+    StoredPrice,
+    // Reject relays if the feed price is stale or too uncertain.
+    Reject,
+}
+
+#[derive(Debug, Clone, InitSpace, AnchorSerialize, AnchorDeserialize)]
+pub struct PriceFeedConfig {
+    // Price feed account, which reports the native token price in USD.
+    pub feed: Pubkey,
+    // Maximum age of the feed price in seconds.
+    pub max_age: u32,
+    // Maximum confidence interval relative to the feed price. This value is scaled 1e4
+    // (e.g. 10000 = 1.00%).
+    pub max_confidence: u32,
+    // Policy applied when the feed price cannot be used.
+    pub fallback: PriceFeedFallback,
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, InitSpace, AnchorSerialize, AnchorDeserialize,
+)]
+pub enum OutputSwapDeadlinePolicy {
+    // Accept output swaps without a deadline (deadline == 0).
+    #[default]
+    Accept,
+    // Reject output swaps without a deadline.
+    Reject,
+    // Output swaps without a deadline expire this many seconds after the fill's timestamp.
+    DefaultMaxAge(u32),
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, InitSpace, AnchorSerialize, AnchorDeserialize,
+)]
+pub struct SupportedSwapTypes {
+    // EVM swap types.
+    pub uniswap_v3: bool,
+    pub trader_joe: bool,
+    // Solana swap types.
+    pub jupiter_v6: bool,
+}
+
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub struct PeerSeeds {
     /// Peer chain. Cannot equal `1` (Solana's Chain ID).
@@ -56,8 +101,71 @@ pub struct Peer {
     pub address: [u8; 32],
     /// Relay parameters.
     pub relay_params: RelayParams,
+    /// Optional price feed for the native token price. If set, this feed takes precedence over
+    /// [RelayParams::native_token_price] when computing relayer fees.
+    pub price_feed: Option<PriceFeedConfig>,
+    /// Share of relaying fees kept by the protocol when a relayer provides a token account to be
+    /// paid. This value is scaled 1e4 (e.g. 1000000 = 100.00%).
+    pub relaying_fee_protocol_share: u32,
+    /// Number of seconds after the fill's timestamp during which only registered relayers can
+    /// execute relay completions. Zero disables the exclusivity window.
+    pub relayer_exclusivity_window: u32,
+    /// Policy applied to output swaps encoded without a deadline.
+    pub output_swap_deadline_policy: OutputSwapDeadlinePolicy,
+    /// Maximum number of seconds after the fill's timestamp that an encoded output swap deadline
+    /// can be. Zero disables this check.
+    pub max_output_swap_deadline_horizon: u32,
+    /// Swap types that can be executed on the peer's network. Output tokens encoding any other
+    /// swap type cannot be staged for this peer.
+    pub supported_swap_types: SupportedSwapTypes,
+    /// Maximum number of path elements (i.e. number of hops excluding the first leg) an encoded EVM
+    /// swap can have.
+    pub max_swap_path_length: u8,
+    /// Whether the peer's network can redeem relayed payloads. Transfers with the relay payload
+    /// redeem mode cannot be staged for this peer otherwise.
+    pub supports_relay_payload: bool,
 }
 
 impl Peer {
     pub const SEED_PREFIX: &'static [u8] = b"peer";
+
+    /// Size of a peer account registered with the current layout.
+    pub const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    /// Size of a peer account registered with the [PeerV0] layout.
+    pub const V0_SPACE: usize = 8 + PeerV0::INIT_SPACE;
+
+    /// Whether the peer's network is an EVM network, which requires addresses encoded in swap
+    /// messages to be left-padded 20-byte addresses.
+    pub fn is_evm(&self) -> bool {
+        matches!(
+            self.relay_params.execution_params,
+            ExecutionParams::Evm { .. }
+        )
+    }
+}
+
+/// Peer account data registered by the first version of this program, which only stored the peer's
+/// address and relay parameters. Peers with this layout must be migrated with `migrate_peer`.
+#[derive(Debug, InitSpace, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerV0 {
+    pub seeds: PeerSeeds,
+    pub address: [u8; 32],
+    pub relay_params: RelayParams,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_v0_space() {
+        // Discriminator + seeds (3) + address (32) + relay params (4 + 8 + 4 + 4 + 9 + 4).
+        assert_eq!(Peer::V0_SPACE, 76, "V0 layout changed");
+        assert_ne!(
+            Peer::SPACE,
+            Peer::V0_SPACE,
+            "layouts must have distinct sizes"
+        );
+    }
 }
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Address lookup table managed by this program. This account is the lookup table's authority, so
+/// only this program can extend it.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ProgramLookupTable {
+    pub bump: u8,
+
+    /// Address of the lookup table.
+    pub address: Pubkey,
+}
+
+impl ProgramLookupTable {
+    pub const SEED_PREFIX: &'static [u8] = b"lookup-table";
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Redeem preferences of an inbound transfer recipient. Recipients that are programs can set these
+/// preferences by signing with their PDA.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RecipientConfig {
+    pub bump: u8,
+    pub recipient: Pubkey,
+
+    /// If true, direct and relay completions can deliver to any token account owned by the
+    /// recipient. Otherwise only the recipient's associated token account is allowed.
+    pub allow_any_token_account: bool,
+
+    /// If true, relay completions deliver the gas dropoff as WSOL into the recipient's WSOL
+    /// associated token account instead of transferring lamports to the recipient. This account is
+    /// created when the recipient opts in. If the recipient closes it, the gas dropoff is
+    /// transferred as lamports.
+    pub gas_dropoff_as_wsol: bool,
+}
+
+impl RecipientConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"recipient-config";
+
+    pub fn allows_any_token_account(config: Option<&Self>) -> bool {
+        config
+            .map(|config| config.allow_any_token_account)
+            .unwrap_or_default()
+    }
+
+    pub fn wants_gas_dropoff_as_wsol(config: Option<&Self>) -> bool {
+        config
+            .map(|config| config.gas_dropoff_as_wsol)
+            .unwrap_or_default()
+    }
+}
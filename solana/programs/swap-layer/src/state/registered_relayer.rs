@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Relayer allowed to execute relay completions during a peer's exclusivity window.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RegisteredRelayer {
+    pub bump: u8,
+    pub relayer: Pubkey,
+}
+
+impl RegisteredRelayer {
+    pub const SEED_PREFIX: &'static [u8] = b"registered-relayer";
+}
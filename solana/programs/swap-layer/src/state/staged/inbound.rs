@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use swap_layer_messages::{messages::SwapMessageV1, types::RedeemMode};
+use swap_layer_messages::{messages::SwapMessageV1Ref, types::RedeemModeRef};
 
 use crate::error::SwapLayerError;
 
@@ -40,7 +40,7 @@ pub struct StagedInbound {
 impl StagedInbound {
     pub const SEED_PREFIX: &'static [u8] = b"staged-inbound";
 
-    pub fn try_compute_size(swap_msg: SwapMessageV1) -> Result<usize> {
+    pub fn try_compute_size(swap_msg: SwapMessageV1Ref) -> Result<usize> {
         const FIXED: usize = 8 // DISCRIMINATOR
             + StagedInboundSeeds::INIT_SPACE
             + StagedInboundInfo::INIT_SPACE
@@ -48,18 +48,19 @@ impl StagedInbound {
             + 4 // payload len
         ;
 
-        match swap_msg.redeem_mode {
-            RedeemMode::Payload { sender: _, buf } => buf
-                .len()
-                .checked_add(FIXED)
-                .ok_or_else(|| error!(SwapLayerError::PayloadTooLarge)),
+        match swap_msg.redeem_mode() {
+            RedeemModeRef::Payload { sender: _, buf } | RedeemModeRef::RelayPayload { buf, .. } => {
+                buf.len()
+                    .checked_add(FIXED)
+                    .ok_or_else(|| error!(SwapLayerError::PayloadTooLarge))
+            }
             _ => err!(SwapLayerError::InvalidRedeemMode),
         }
     }
 
     pub fn try_compute_size_if_needed(
         acc_info: &AccountInfo,
-        swap_msg: SwapMessageV1,
+        swap_msg: SwapMessageV1Ref,
     ) -> Result<usize> {
         if acc_info.data_is_empty() {
             Self::try_compute_size(swap_msg)
@@ -3,3 +3,6 @@ pub use inbound::*;
 
 mod outbound;
 pub use outbound::*;
+
+mod outbound_counter;
+pub use outbound_counter::*;
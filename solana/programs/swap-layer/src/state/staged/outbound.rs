@@ -1,10 +1,6 @@
-use crate::error::SwapLayerError;
+use crate::{error::SwapLayerError, utils::output_token::decode_output_token};
 use anchor_lang::prelude::*;
-use common::wormhole_io::Readable;
-use swap_layer_messages::{
-    messages::SwapMessageV1,
-    types::{OutputToken, RedeemMode},
-};
+use swap_layer_messages::{messages::SwapMessageV1, types::RedeemMode};
 
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
 pub enum RedeemOption {
@@ -16,6 +12,18 @@ pub enum RedeemOption {
         max_relayer_fee: u64,
     },
     Payload(Vec<u8>),
+    /// Relayed payload transfer. Only peers that support relayed payloads can be targeted with this
+    /// mode.
+    RelayPayload {
+        /// Normalized amount of gas to drop off on destination network.
+        gas_dropoff: u32,
+
+        /// Maximum fee that a relayer can charge for the transfer.
+        max_relayer_fee: u64,
+
+        /// Arbitrary payload delivered to the recipient.
+        payload: Vec<u8>,
+    },
 }
 
 #[derive(Debug, Default, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -27,6 +35,67 @@ pub enum StagedRedeem {
         relaying_fee: u64,
     },
     Payload(Vec<u8>),
+    RelayPayload {
+        gas_dropoff: u32,
+        relaying_fee: u64,
+        payload: Vec<u8>,
+    },
+}
+
+impl StagedRedeem {
+    /// Size of the staged redeem's fields (excluding its discriminant).
+    fn size(&self) -> usize {
+        match self {
+            Self::Direct => 0,
+            Self::Relay { .. } => 12, // gas_dropoff + relaying_fee
+            Self::Payload(payload) => payload.len().saturating_add(4),
+            Self::RelayPayload { payload, .. } => {
+                payload.len().saturating_add(16) // gas_dropoff + relaying_fee + len
+            }
+        }
+    }
+
+    pub fn relaying_fee(&self) -> Option<u64> {
+        match self {
+            Self::Relay { relaying_fee, .. } | Self::RelayPayload { relaying_fee, .. } => {
+                Some(*relaying_fee)
+            }
+            _ => None,
+        }
+    }
+
+    /// Encode the staged redeem as the swap message's redeem mode. Payload redeem modes encode
+    /// the sender.
+    pub fn try_into_redeem_mode(self, sender: &Pubkey) -> Result<RedeemMode> {
+        Ok(match self {
+            Self::Direct => Default::default(),
+            Self::Payload(buf) => RedeemMode::Payload {
+                sender: sender.to_bytes(),
+                buf: buf
+                    .try_into()
+                    .map_err(|_| SwapLayerError::PayloadTooLarge)?,
+            },
+            Self::Relay {
+                gas_dropoff,
+                relaying_fee,
+            } => RedeemMode::Relay {
+                gas_dropoff,
+                relaying_fee: relaying_fee.try_into().unwrap(),
+            },
+            Self::RelayPayload {
+                gas_dropoff,
+                relaying_fee,
+                payload,
+            } => RedeemMode::RelayPayload {
+                gas_dropoff,
+                relaying_fee: relaying_fee.try_into().unwrap(),
+                sender: sender.to_bytes(),
+                buf: payload
+                    .try_into()
+                    .map_err(|_| SwapLayerError::PayloadTooLarge)?,
+            },
+        })
+    }
 }
 
 #[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
@@ -51,6 +120,21 @@ pub struct StagedOutboundInfo {
     /// The specified token account to refund USDC. This account is required by the Token Router
     /// program in case a fast order is reverted at the Matching Engine after it has been placed.
     pub usdc_refund_token: Pubkey,
+
+    /// Timestamp after which the transfer can no longer be initiated. Once expired, anyone can
+    /// close the staged accounts and refund the sender.
+    pub expires_at: Option<i64>,
+
+    /// Whether the transfer has been initiated. The staged accounts are only left open after
+    /// initiation if there is residual after an exact in swap, which can then be refunded to the
+    /// sender. An initiated transfer cannot be initiated or amended again.
+    pub is_initiated: bool,
+}
+
+impl StagedOutboundInfo {
+    pub fn is_expired(&self, now: i64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if now >= expires_at)
+    }
 }
 
 #[account]
@@ -62,12 +146,31 @@ pub struct StagedOutbound {
 }
 
 impl StagedOutbound {
+    /// Seed prefix for staged outbound accounts indexed by sender. Staged outbound accounts can
+    /// also be created at arbitrary addresses.
+    pub const SEED_PREFIX: &'static [u8] = b"staged-outbound";
+
     const BASE_SIZE: usize = 8 // DISCRIMINATOR
         + StagedOutboundInfo::INIT_SPACE
         + 1 // StagedRedeem discrimant
         + 1 // encoded_output_token === None
         ;
 
+    /// Base size of a staged outbound account created with the [StagedOutboundV0] layout.
+    const V0_BASE_SIZE: usize = 8 // DISCRIMINATOR
+        + StagedOutboundInfoV0::INIT_SPACE
+        + 1 // StagedRedeem discrimant
+        + 1 // encoded_output_token === None
+        ;
+
+    /// Compute the size of a staged outbound account with the current layout, given its staged
+    /// redeem and encoded output token.
+    pub fn compute_size(staged_redeem: &StagedRedeem, encoded_output_token: &[u8]) -> usize {
+        Self::BASE_SIZE
+            .saturating_add(staged_redeem.size())
+            .saturating_add(encoded_output_token.len().saturating_add(4))
+    }
+
     pub fn try_compute_size(
         redeem_option: &Option<RedeemOption>,
         encoded_output_token: &Option<Vec<u8>>,
@@ -77,14 +180,16 @@ impl StagedOutbound {
                 Some(redeem) => match redeem {
                     RedeemOption::Relay { .. } => 12, // gas_dropoff + relaying_fee
                     RedeemOption::Payload(payload) => payload.len().saturating_add(4),
+                    RedeemOption::RelayPayload { payload, .. } => {
+                        payload.len().saturating_add(16) // gas_dropoff + relaying_fee + len
+                    }
                 },
                 None => 0,
             })
             .saturating_add(match encoded_output_token {
                 Some(encoded_output_token) => {
                     // First validate the encoded output token by attempting to deserialize it.
-                    OutputToken::read(&mut &encoded_output_token[..])
-                        .map_err(|_| error!(SwapLayerError::InvalidOutputToken))?;
+                    decode_output_token(encoded_output_token)?;
 
                     encoded_output_token.len().saturating_add(4)
                 }
@@ -102,25 +207,9 @@ impl StagedOutbound {
         let staged_redeem = std::mem::take(staged_redeem);
 
         Ok(SwapMessageV1 {
-            recipient: info.recipient,
-            redeem_mode: match staged_redeem {
-                StagedRedeem::Direct => Default::default(),
-                StagedRedeem::Payload(buf) => RedeemMode::Payload {
-                    sender: info.sender.to_bytes(),
-                    buf: buf
-                        .try_into()
-                        .map_err(|_| SwapLayerError::PayloadTooLarge)?,
-                },
-                StagedRedeem::Relay {
-                    gas_dropoff,
-                    relaying_fee,
-                } => RedeemMode::Relay {
-                    gas_dropoff,
-                    relaying_fee: relaying_fee.try_into().unwrap(),
-                },
-            },
-            output_token: Readable::read(&mut &encoded_output_token[..])
-                .map_err(|_| SwapLayerError::InvalidOutputToken)?,
+            recipient: info.recipient.into(),
+            redeem_mode: staged_redeem.try_into_redeem_mode(&info.sender)?,
+            output_token: decode_output_token(encoded_output_token)?,
         })
     }
 }
@@ -132,3 +221,85 @@ impl std::ops::Deref for StagedOutbound {
         &self.info
     }
 }
+
+/// Staged outbound account info written by the first version of this program, which did not
+/// support expirations or record whether the transfer was initiated.
+#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct StagedOutboundInfoV0 {
+    pub custody_token_bump: u8,
+    pub prepared_by: Pubkey,
+    pub sender: Pubkey,
+    pub target_chain: u16,
+    pub recipient: [u8; 32],
+    pub is_exact_in: bool,
+    pub usdc_refund_token: Pubkey,
+}
+
+/// Staged outbound account data written by the first version of this program. Staged outbound
+/// accounts with this layout must be migrated with `migrate_staged_outbound`.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct StagedOutboundV0 {
+    pub info: StagedOutboundInfoV0,
+    pub staged_redeem: StagedRedeem,
+    pub encoded_output_token: Vec<u8>,
+}
+
+impl StagedOutboundV0 {
+    /// Size of the staged outbound account created with this layout.
+    pub fn space(&self) -> usize {
+        StagedOutbound::V0_BASE_SIZE
+            .saturating_add(self.staged_redeem.size())
+            .saturating_add(self.encoded_output_token.len().saturating_add(4))
+    }
+}
+
+impl From<StagedOutboundV0> for StagedOutbound {
+    fn from(staged_outbound: StagedOutboundV0) -> Self {
+        let StagedOutboundV0 {
+            info:
+                StagedOutboundInfoV0 {
+                    custody_token_bump,
+                    prepared_by,
+                    sender,
+                    target_chain,
+                    recipient,
+                    is_exact_in,
+                    usdc_refund_token,
+                },
+            staged_redeem,
+            encoded_output_token,
+        } = staged_outbound;
+
+        Self {
+            info: StagedOutboundInfo {
+                custody_token_bump,
+                prepared_by,
+                sender,
+                target_chain,
+                recipient,
+                is_exact_in,
+                usdc_refund_token,
+                expires_at: None,
+                is_initiated: false,
+            },
+            staged_redeem,
+            encoded_output_token,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_v0_base_size() {
+        // Discriminator + info (1 + 32 + 32 + 2 + 32 + 1 + 32) + staged redeem discriminant + 1.
+        assert_eq!(StagedOutbound::V0_BASE_SIZE, 142, "V0 layout changed");
+        assert_ne!(
+            StagedOutbound::BASE_SIZE,
+            StagedOutbound::V0_BASE_SIZE,
+            "layouts must have distinct sizes"
+        );
+    }
+}
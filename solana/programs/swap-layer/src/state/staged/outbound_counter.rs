@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Counter of a sender's indexed staged outbound accounts. Each staged outbound account created
+/// with [stage_outbound_indexed](crate::swap_layer::stage_outbound_indexed) or
+/// [stage_outbound_with_intent](crate::swap_layer::stage_outbound_with_intent) is derived with the
+/// sender's next nonce, so clients can find a sender's pending outbound transfers by deriving
+/// every nonce less than [StagedOutboundCounter::next_nonce]. Because each nonce can only be used
+/// once, this counter also protects against replaying a sender's signed intent.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct StagedOutboundCounter {
+    pub bump: u8,
+    pub sender: Pubkey,
+    pub next_nonce: u64,
+}
+
+impl StagedOutboundCounter {
+    pub const SEED_PREFIX: &'static [u8] = b"staged-outbound-counter";
+}
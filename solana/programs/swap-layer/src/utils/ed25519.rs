@@ -0,0 +1,278 @@
+use crate::error::SwapLayerError;
+use anchor_lang::prelude::*;
+use solana_program::{ed25519_program, sysvar::instructions as sysvar_instructions};
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+
+/// Instruction index that refers to the Ed25519 program instruction itself.
+const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+/// Verify that the instruction preceding the current one is an Ed25519 program instruction that
+/// verified a single signature of `expected_message` by `expected_signer`.
+///
+/// The Ed25519 program fails the transaction if the signature is invalid, so we only need to make
+/// sure that it verified what we expect.
+pub fn verify_preceding_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: &Pubkey,
+    expected_message: &[u8],
+) -> Result<()> {
+    let current_index = sysvar_instructions::load_current_index_checked(instructions_sysvar)?;
+    let ed25519_index = current_index
+        .checked_sub(1)
+        .ok_or_else(|| error!(SwapLayerError::Ed25519InstructionRequired))?;
+    let ed25519_ix = sysvar_instructions::load_instruction_at_checked(
+        ed25519_index.into(),
+        instructions_sysvar,
+    )?;
+    require_keys_eq!(
+        ed25519_ix.program_id,
+        ed25519_program::ID,
+        SwapLayerError::Ed25519InstructionRequired
+    );
+
+    let data = &ed25519_ix.data;
+
+    // Only one signature can be verified by this instruction.
+    require!(
+        data.first() == Some(&1),
+        SwapLayerError::InvalidEd25519Instruction
+    );
+
+    let mut offsets = [0u16; 7];
+    let encoded_offsets = data
+        .get(
+            SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START.saturating_add(SIGNATURE_OFFSETS_SIZE),
+        )
+        .ok_or_else(|| error!(SwapLayerError::InvalidEd25519Instruction))?;
+    for (offset, bytes) in offsets.iter_mut().zip(encoded_offsets.chunks_exact(2)) {
+        *offset = u16::from_le_bytes([bytes[0], bytes[1]]);
+    }
+    let [
+        _, // signature_offset
+        signature_ix_index,
+        pubkey_offset,
+        pubkey_ix_index,
+        message_offset,
+        message_size,
+        message_ix_index,
+    ] = offsets;
+
+    // The signature, public key and message must be encoded in the Ed25519 instruction itself.
+    require!(
+        signature_ix_index == CURRENT_INSTRUCTION_INDEX
+            && pubkey_ix_index == CURRENT_INSTRUCTION_INDEX
+            && message_ix_index == CURRENT_INSTRUCTION_INDEX,
+        SwapLayerError::InvalidEd25519Instruction
+    );
+
+    let pubkey_offset = usize::from(pubkey_offset);
+    let signer = data
+        .get(pubkey_offset..pubkey_offset.saturating_add(PUBKEY_SIZE))
+        .ok_or_else(|| error!(SwapLayerError::InvalidEd25519Instruction))?;
+    require!(
+        signer == expected_signer.as_ref(),
+        SwapLayerError::InvalidIntentSigner
+    );
+
+    let message_offset = usize::from(message_offset);
+    let message = data
+        .get(message_offset..message_offset.saturating_add(message_size.into()))
+        .ok_or_else(|| error!(SwapLayerError::InvalidEd25519Instruction))?;
+    require!(
+        message == expected_message,
+        SwapLayerError::InvalidIntentMessage
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use solana_program::{
+        instruction::Instruction,
+        sysvar::{
+            self,
+            instructions::{BorrowedAccountMeta, BorrowedInstruction},
+        },
+    };
+    use solana_sdk::ed25519_instruction::new_ed25519_instruction;
+
+    use super::*;
+
+    const MESSAGE: &[u8] = b"swap-layer:test-intent";
+
+    fn keypair(seed: u8) -> ed25519_dalek::Keypair {
+        let secret = ed25519_dalek::SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        ed25519_dalek::Keypair { secret, public }
+    }
+
+    fn signer(keypair: &ed25519_dalek::Keypair) -> Pubkey {
+        Pubkey::from(keypair.public.to_bytes())
+    }
+
+    /// Verify the Ed25519 instruction preceding this program's instruction, which is at index
+    /// `current_index` in the instructions sysvar.
+    fn verify(
+        instructions: &[&Instruction],
+        current_index: u16,
+        expected_signer: &Pubkey,
+        expected_message: &[u8],
+    ) -> Result<()> {
+        let borrowed_instructions = instructions
+            .iter()
+            .map(|ix| BorrowedInstruction {
+                program_id: &ix.program_id,
+                accounts: ix
+                    .accounts
+                    .iter()
+                    .map(|meta| BorrowedAccountMeta {
+                        pubkey: &meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: &ix.data,
+            })
+            .collect::<Vec<_>>();
+        let mut data = sysvar_instructions::construct_instructions_data(&borrowed_instructions);
+        sysvar_instructions::store_current_index(&mut data, current_index);
+
+        let mut lamports = 0;
+        let instructions_sysvar = AccountInfo::new(
+            &sysvar_instructions::ID,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &sysvar::ID,
+            false,
+            0,
+        );
+
+        verify_preceding_ed25519_instruction(
+            &instructions_sysvar,
+            expected_signer,
+            expected_message,
+        )
+    }
+
+    fn verify_preceding(
+        ed25519_ix: &Instruction,
+        expected_signer: &Pubkey,
+        expected_message: &[u8],
+    ) -> Result<()> {
+        let current_ix = Instruction::new_with_bytes(crate::id(), &[], vec![]);
+        verify(
+            &[ed25519_ix, &current_ix],
+            1,
+            expected_signer,
+            expected_message,
+        )
+    }
+
+    #[test]
+    fn test_verify() {
+        let keypair = keypair(1);
+        let ed25519_ix = new_ed25519_instruction(&keypair, MESSAGE);
+
+        verify_preceding(&ed25519_ix, &signer(&keypair), MESSAGE).unwrap();
+    }
+
+    #[test]
+    fn test_no_preceding_instruction() {
+        let keypair = keypair(1);
+        let current_ix = Instruction::new_with_bytes(crate::id(), &[], vec![]);
+
+        assert_eq!(
+            verify(&[&current_ix], 0, &signer(&keypair), MESSAGE).unwrap_err(),
+            error!(SwapLayerError::Ed25519InstructionRequired)
+        );
+    }
+
+    #[test]
+    fn test_preceding_instruction_not_ed25519() {
+        let keypair = keypair(1);
+        let mut ed25519_ix = new_ed25519_instruction(&keypair, MESSAGE);
+        ed25519_ix.program_id = crate::id();
+
+        assert_eq!(
+            verify_preceding(&ed25519_ix, &signer(&keypair), MESSAGE).unwrap_err(),
+            error!(SwapLayerError::Ed25519InstructionRequired)
+        );
+    }
+
+    #[test]
+    fn test_wrong_signer() {
+        let other = keypair(2);
+        let keypair = keypair(1);
+        let ed25519_ix = new_ed25519_instruction(&keypair, MESSAGE);
+
+        assert_eq!(
+            verify_preceding(&ed25519_ix, &signer(&other), MESSAGE).unwrap_err(),
+            error!(SwapLayerError::InvalidIntentSigner)
+        );
+    }
+
+    #[test]
+    fn test_wrong_message() {
+        let keypair = keypair(1);
+        let ed25519_ix = new_ed25519_instruction(&keypair, b"swap-layer:other-intent");
+
+        assert_eq!(
+            verify_preceding(&ed25519_ix, &signer(&keypair), MESSAGE).unwrap_err(),
+            error!(SwapLayerError::InvalidIntentMessage)
+        );
+    }
+
+    #[test]
+    fn test_cross_instruction_index() {
+        let keypair = keypair(1);
+
+        // Signature, public key and message instruction indices, respectively.
+        for index_range in [4..6, 8..10, 14..16] {
+            let mut ed25519_ix = new_ed25519_instruction(&keypair, MESSAGE);
+            ed25519_ix.data[index_range].copy_from_slice(&0u16.to_le_bytes());
+
+            assert_eq!(
+                verify_preceding(&ed25519_ix, &signer(&keypair), MESSAGE).unwrap_err(),
+                error!(SwapLayerError::InvalidEd25519Instruction)
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_signatures_not_one() {
+        let keypair = keypair(1);
+
+        for num_signatures in [0, 2] {
+            let mut ed25519_ix = new_ed25519_instruction(&keypair, MESSAGE);
+            ed25519_ix.data[0] = num_signatures;
+
+            assert_eq!(
+                verify_preceding(&ed25519_ix, &signer(&keypair), MESSAGE).unwrap_err(),
+                error!(SwapLayerError::InvalidEd25519Instruction)
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncated_data() {
+        let keypair = keypair(1);
+        let ed25519_ix = new_ed25519_instruction(&keypair, MESSAGE);
+
+        // Truncate within the offsets, the public key and the message.
+        for len in [0, 8, 32, ed25519_ix.data.len().saturating_sub(1)] {
+            let mut ed25519_ix = ed25519_ix.clone();
+            ed25519_ix.data.truncate(len);
+
+            assert_eq!(
+                verify_preceding(&ed25519_ix, &signer(&keypair), MESSAGE).unwrap_err(),
+                error!(SwapLayerError::InvalidEd25519Instruction)
+            );
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::{prelude::*, system_program};
+
+/// Reallocate an account owned by this program to `new_len` bytes. The payer tops up the account's
+/// lamports so that it remains rent exempt. Added bytes are zeroed.
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let lamports_needed = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+
+    if lamports_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.to_account_info(),
+                },
+            ),
+            lamports_needed,
+        )?;
+    }
+
+    account.realloc(new_len, true).map_err(Into::into)
+}
@@ -1,7 +1,12 @@
+pub mod ed25519;
 pub mod gas_dropoff;
 pub mod jupiter_v6;
+pub mod migrate;
+pub mod output_token;
+pub mod price_feed;
 pub mod relay_parameters;
 pub mod relayer_fees;
+pub mod token_2022;
 //pub mod token_router;
 
 use std::fmt;
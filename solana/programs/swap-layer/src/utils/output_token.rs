@@ -0,0 +1,54 @@
+use crate::{error::SwapLayerError, state::Peer};
+use anchor_lang::prelude::*;
+use swap_layer_messages::types::{
+    OutputSwap, OutputToken, SwapType, TraderJoeSwapParameters, UniswapSwapParameters,
+    UniversalAddress, UniversalAddressError,
+};
+
+/// Decode an encoded output token, logging why decoding failed.
+pub fn decode_output_token(encoded_output_token: &[u8]) -> Result<OutputToken> {
+    OutputToken::decode(encoded_output_token).map_err(|err| {
+        msg!("Failed to decode output token: {}", err);
+        error!(SwapLayerError::from(err))
+    })
+}
+
+/// Verify that the output token can be redeemed on the target peer's network. Output token
+/// addresses are checked the same way the EVM Swap Layer does (see `checkAddr` in `Params.sol`).
+pub fn verify_output_token(target_peer: &Peer, output_token: &OutputToken) -> Result<()> {
+    let swap_type = match output_token {
+        OutputToken::Usdc => return Ok(()),
+        OutputToken::Gas(OutputSwap { swap_type, .. }) => swap_type,
+        OutputToken::Other {
+            address,
+            swap: OutputSwap { swap_type, .. },
+        } => {
+            UniversalAddress::from(*address)
+                .validate(target_peer.is_evm())
+                .map_err(|err| match err {
+                    UniversalAddressError::ZeroAddress => SwapLayerError::InvalidOutputToken,
+                    _ => SwapLayerError::InvalidEvmAddress,
+                })?;
+
+            swap_type
+        }
+    };
+
+    let supported = &target_peer.supported_swap_types;
+    let path_len = match swap_type {
+        SwapType::UniswapV3(UniswapSwapParameters { path, .. }) if supported.uniswap_v3 => {
+            path.len()
+        }
+        SwapType::TraderJoe(TraderJoeSwapParameters { path, .. }) if supported.trader_joe => {
+            path.len()
+        }
+        SwapType::JupiterV6(_) if supported.jupiter_v6 => return Ok(()),
+        _ => return err!(SwapLayerError::UnsupportedSwapType),
+    };
+    require!(
+        path_len <= target_peer.max_swap_path_length.into(),
+        SwapLayerError::SwapPathTooLong
+    );
+
+    Ok(())
+}
@@ -0,0 +1,321 @@
+use crate::{
+    error::SwapLayerError,
+    state::{PriceFeedConfig, PriceFeedFallback, RelayParams},
+};
+use anchor_lang::prelude::*;
+
+/// Native token prices are stored with USDC precision (i.e. 1e6 = 1 USDC/token).
+const USDC_DECIMALS: i32 = 6;
+
+/// Anchor discriminator of Pyth Solana Receiver's `PriceUpdateV2` account.
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Price read from a native token price feed. This price is expressed as `price * 10^exponent`
+/// USD/token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Abstraction over a native token price feed's account layout.
+pub trait NativeTokenPriceFeed {
+    fn read_price(&self) -> Result<FeedPrice>;
+}
+
+/// Reads a Pyth Solana Receiver `PriceUpdateV2` account. Only fully verified price updates are
+/// accepted.
+impl<'info> NativeTokenPriceFeed for AccountInfo<'info> {
+    fn read_price(&self) -> Result<FeedPrice> {
+        let data = self.try_borrow_data()?;
+        let mut buf = &data[..];
+
+        // Discriminator (8) + write authority (32).
+        require!(
+            buf.len() > 40 && buf[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+            SwapLayerError::InvalidPriceFeed
+        );
+        buf = &buf[40..];
+
+        // Verification level. Partial { num_signatures } == 0, Full == 1.
+        require_eq!(
+            u8::deserialize(&mut buf)?,
+            1,
+            SwapLayerError::InvalidPriceFeed
+        );
+
+        let _feed_id = <[u8; 32]>::deserialize(&mut buf)?;
+
+        Ok(FeedPrice {
+            price: i64::deserialize(&mut buf)?,
+            conf: u64::deserialize(&mut buf)?,
+            exponent: i32::deserialize(&mut buf)?,
+            publish_time: i64::deserialize(&mut buf)?,
+        })
+    }
+}
+
+/// Price feed paired with its configuration, which is used to compute relayer fees.
+pub struct NativeTokenPriceSource<'a> {
+    pub config: &'a PriceFeedConfig,
+    pub feed: &'a dyn NativeTokenPriceFeed,
+}
+
+impl<'a> NativeTokenPriceSource<'a> {
+    /// Pairs the peer's price feed configuration with the price feed account. If the peer has a
+    /// price feed configured, the account must be provided and must equal the configured feed.
+    pub fn try_new<'info>(
+        config: &'a Option<PriceFeedConfig>,
+        feed: Option<&'a AccountInfo<'info>>,
+    ) -> Result<Option<Self>> {
+        match config {
+            Some(config) => {
+                let feed = feed.ok_or_else(|| error!(SwapLayerError::PriceFeedRequired))?;
+                require_keys_eq!(feed.key(), config.feed, SwapLayerError::InvalidPriceFeed);
+
+                Ok(Some(Self { config, feed }))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+pub fn verify_price_feed_config(config: &PriceFeedConfig) -> Result<()> {
+    require_keys_neq!(
+        config.feed,
+        Pubkey::default(),
+        SwapLayerError::InvalidPriceFeedConfig
+    );
+    require!(config.max_age > 0, SwapLayerError::InvalidPriceFeedConfig);
+    require!(
+        config.max_confidence <= crate::MAX_BPS,
+        SwapLayerError::InvalidPriceFeedConfig
+    );
+
+    Ok(())
+}
+
+/// Determines the native token price (atomic USDC/token) to use for relayer fee calculation. If
+/// the feed price is stale or its confidence interval is too wide, the configured fallback policy
+/// determines whether the stored price is used instead.
+pub fn resolve_native_token_price(
+    relay_params: &RelayParams,
+    source: &NativeTokenPriceSource,
+    now: i64,
+) -> Result<u64> {
+    match source
+        .feed
+        .read_price()
+        .and_then(|feed_price| checked_native_token_price(source.config, &feed_price, now))
+    {
+        Ok(native_token_price) => Ok(native_token_price),
+        Err(err) => match source.config.fallback {
+            PriceFeedFallback::StoredPrice => {
+                msg!("Price feed rejected. Using stored native token price");
+                Ok(relay_params.native_token_price)
+            }
+            PriceFeedFallback::Reject => Err(err),
+        },
+    }
+}
+
+fn checked_native_token_price(
+    config: &PriceFeedConfig,
+    feed_price: &FeedPrice,
+    now: i64,
+) -> Result<u64> {
+    let FeedPrice {
+        price,
+        conf,
+        exponent,
+        publish_time,
+    } = *feed_price;
+
+    let price = u64::try_from(price)
+        .ok()
+        .filter(|&price| price > 0)
+        .ok_or_else(|| error!(SwapLayerError::InvalidPriceFeed))?;
+
+    require!(
+        now.saturating_sub(publish_time) <= i64::from(config.max_age),
+        SwapLayerError::PriceFeedStale
+    );
+
+    // Confidence interval relative to the price cannot exceed the configured maximum.
+    require!(
+        u128::from(conf).saturating_mul(crate::MAX_BPS.into())
+            <= u128::from(price).saturating_mul(config.max_confidence.into()),
+        SwapLayerError::PriceFeedConfidenceTooWide
+    );
+
+    normalize_price(price, exponent)
+        .filter(|&price| price > 0)
+        .ok_or_else(|| error!(SwapLayerError::InvalidPriceFeed))
+}
+
+/// Convert `price * 10^exponent` to USDC precision.
+fn normalize_price(price: u64, exponent: i32) -> Option<u64> {
+    let shift = exponent.checked_add(USDC_DECIMALS)?;
+    let scalar = 10_u64.checked_pow(shift.unsigned_abs())?;
+
+    if shift >= 0 {
+        price.checked_mul(scalar)
+    } else {
+        price.checked_div(scalar)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::state::{ExecutionParams, SwapTimeLimit};
+
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    struct MockPriceFeed(FeedPrice);
+
+    impl NativeTokenPriceFeed for MockPriceFeed {
+        fn read_price(&self) -> Result<FeedPrice> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn test_normalize_price() {
+        assert_eq!(normalize_price(20_000_000_000, -8), Some(200_000_000));
+        assert_eq!(normalize_price(200, 0), Some(200_000_000));
+        assert_eq!(normalize_price(u64::MAX, 0), None);
+    }
+
+    #[test]
+    fn test_resolve_fresh_price() {
+        let config = test_config(PriceFeedFallback::Reject);
+        let feed = MockPriceFeed(test_feed_price());
+
+        let price = resolve_native_token_price(
+            &test_relay_params(),
+            &NativeTokenPriceSource {
+                config: &config,
+                feed: &feed,
+            },
+            NOW,
+        );
+
+        assert_eq!(price.unwrap(), 210_000_000);
+    }
+
+    #[test]
+    fn test_resolve_stale_price_reject() {
+        let config = test_config(PriceFeedFallback::Reject);
+        let feed = MockPriceFeed(test_feed_price());
+
+        let price = resolve_native_token_price(
+            &test_relay_params(),
+            &NativeTokenPriceSource {
+                config: &config,
+                feed: &feed,
+            },
+            1_700_000_061,
+        );
+
+        assert_eq!(price.unwrap_err(), error!(SwapLayerError::PriceFeedStale));
+    }
+
+    #[test]
+    fn test_resolve_stale_price_fallback() {
+        let config = test_config(PriceFeedFallback::StoredPrice);
+        let feed = MockPriceFeed(test_feed_price());
+
+        let price = resolve_native_token_price(
+            &test_relay_params(),
+            &NativeTokenPriceSource {
+                config: &config,
+                feed: &feed,
+            },
+            1_700_000_061,
+        );
+
+        assert_eq!(price.unwrap(), 200_000_000);
+    }
+
+    #[test]
+    fn test_resolve_wide_confidence_reject() {
+        let config = test_config(PriceFeedFallback::Reject);
+        let feed = MockPriceFeed(FeedPrice {
+            conf: 500_000_000, // 2.38%
+            ..test_feed_price()
+        });
+
+        let price = resolve_native_token_price(
+            &test_relay_params(),
+            &NativeTokenPriceSource {
+                config: &config,
+                feed: &feed,
+            },
+            NOW,
+        );
+
+        assert_eq!(
+            price.unwrap_err(),
+            error!(SwapLayerError::PriceFeedConfidenceTooWide)
+        );
+    }
+
+    #[test]
+    fn test_resolve_negative_price_fallback() {
+        let config = test_config(PriceFeedFallback::StoredPrice);
+        let feed = MockPriceFeed(FeedPrice {
+            price: -1,
+            ..test_feed_price()
+        });
+
+        let price = resolve_native_token_price(
+            &test_relay_params(),
+            &NativeTokenPriceSource {
+                config: &config,
+                feed: &feed,
+            },
+            NOW,
+        );
+
+        assert_eq!(price.unwrap(), 200_000_000);
+    }
+
+    fn test_feed_price() -> FeedPrice {
+        FeedPrice {
+            price: 21_000_000_000, // 210 USD
+            conf: 21_000_000,      // 0.1%
+            exponent: -8,
+            publish_time: 1_699_999_995,
+        }
+    }
+
+    fn test_config(fallback: PriceFeedFallback) -> PriceFeedConfig {
+        PriceFeedConfig {
+            feed: Pubkey::new_unique(),
+            max_age: 60,
+            max_confidence: 10_000, // 1%
+            fallback,
+        }
+    }
+
+    fn test_relay_params() -> RelayParams {
+        RelayParams {
+            base_fee: 1_500_000,
+            native_token_price: 200_000_000,
+            max_gas_dropoff: 500_000,
+            gas_dropoff_margin: 500_000,
+            execution_params: ExecutionParams::Evm {
+                gas_price: 10_000,
+                gas_price_margin: 250_000,
+            },
+            swap_time_limit: SwapTimeLimit {
+                fast_limit: 10,
+                finalized_limit: 30,
+            },
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::utils::gas_dropoff::denormalize_gas_dropoff;
+use crate::utils::{
+    gas_dropoff::denormalize_gas_dropoff,
+    price_feed::{resolve_native_token_price, NativeTokenPriceSource},
+};
 use crate::{
     error::SwapLayerError,
     state::{ExecutionParams, RelayParams},
@@ -109,6 +112,7 @@ fn calculate_gas_dropoff_cost(
 
 pub fn calculate_relayer_fee(
     relay_params: &RelayParams,
+    price_source: Option<NativeTokenPriceSource>,
     specified_gas_dropoff: u32,
     output_token: &OutputToken,
 ) -> Result<u64> {
@@ -117,6 +121,14 @@ pub fn calculate_relayer_fee(
         SwapLayerError::RelayingDisabled
     );
 
+    // Use the price feed's native token price if one is configured for this peer.
+    let native_token_price = match price_source {
+        Some(price_source) => {
+            resolve_native_token_price(relay_params, &price_source, Clock::get()?.unix_timestamp)?
+        }
+        None => relay_params.native_token_price,
+    };
+
     // Running sum of the relayer fee (USDC).
     let mut relayer_fee = u64::from(relay_params.base_fee);
 
@@ -130,7 +142,7 @@ pub fn calculate_relayer_fee(
         let gas_dropoff_cost = calculate_gas_dropoff_cost(
             specified_gas_dropoff,
             relay_params.gas_dropoff_margin,
-            relay_params.native_token_price,
+            native_token_price,
         )
         .ok_or_else(|| SwapLayerError::GasDropoffCalculationFailed)?;
 
@@ -158,13 +170,9 @@ pub fn calculate_relayer_fee(
                     _ => 0,
                 });
 
-            let evm_gas_cost = calculate_evm_gas_cost(
-                gas_price,
-                gas_price_margin,
-                total_gas,
-                relay_params.native_token_price,
-            )
-            .ok_or_else(|| SwapLayerError::EvmGasCalculationFailed)?;
+            let evm_gas_cost =
+                calculate_evm_gas_cost(gas_price, gas_price_margin, total_gas, native_token_price)
+                    .ok_or_else(|| SwapLayerError::EvmGasCalculationFailed)?;
 
             relayer_fee = relayer_fee.saturating_add(evm_gas_cost);
 
@@ -177,6 +185,21 @@ pub fn calculate_relayer_fee(
     }
 }
 
+/// Split the relaying fee into the protocol's share and the relayer's share. The protocol share is
+/// scaled 1e4 (e.g. 1000000 = 100.00%) and is rounded down in favor of the relayer.
+pub fn split_relaying_fee(relaying_fee: u64, protocol_share: u32) -> (u64, u64) {
+    #[allow(clippy::as_conversions)]
+    const MAX: u128 = crate::MAX_BPS as u128;
+
+    let protocol_fee = u128::from(relaying_fee)
+        .saturating_mul(protocol_share.min(crate::MAX_BPS).into())
+        .saturating_div(MAX)
+        .try_into()
+        .unwrap_or(relaying_fee);
+
+    (protocol_fee, relaying_fee.saturating_sub(protocol_fee))
+}
+
 #[cfg(test)]
 mod test {
     use hex_literal::hex;
@@ -317,7 +340,7 @@ mod test {
         let gas_dropoff = 50_000;
         let output_token = &OutputToken::Usdc;
 
-        let relayer_fee = calculate_relayer_fee(&relay_params, gas_dropoff, output_token);
+        let relayer_fee = calculate_relayer_fee(&relay_params, None, gas_dropoff, output_token);
 
         assert_eq!(relayer_fee.unwrap(), 17280000);
     }
@@ -350,7 +373,7 @@ mod test {
             swap_type: swap_type.clone(),
         });
 
-        let relayer_fee = calculate_relayer_fee(&relay_params, gas_dropoff, &output_token);
+        let relayer_fee = calculate_relayer_fee(&relay_params, None, gas_dropoff, &output_token);
 
         assert_eq!(relayer_fee.unwrap(), 18505000);
     }
@@ -378,13 +401,26 @@ mod test {
             swap_type: swap_type.clone(),
         });
 
-        let relayer_fee = calculate_relayer_fee(&relay_params, gas_dropoff, &output_token);
+        let relayer_fee = calculate_relayer_fee(&relay_params, None, gas_dropoff, &output_token);
 
         assert_eq!(relayer_fee.unwrap(), 17755000);
     }
 
     // TODO: Add boundary tests.
 
+    #[test]
+    fn test_split_relaying_fee() {
+        let relaying_fee = 1_000_001;
+
+        assert_eq!(split_relaying_fee(relaying_fee, 0), (0, 1_000_001));
+        assert_eq!(
+            split_relaying_fee(relaying_fee, 250_000),
+            (250_000, 750_001)
+        );
+        assert_eq!(split_relaying_fee(relaying_fee, 1_000_000), (1_000_001, 0));
+        assert_eq!(split_relaying_fee(relaying_fee, u32::MAX), (1_000_001, 0));
+    }
+
     fn test_relay_params() -> RelayParams {
         RelayParams {
             base_fee: 1_500_000,             // 1.5 USDC
@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::{
+        extension::{
+            transfer_fee::{self, TransferFeeAmount, TransferFeeConfig},
+            BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+        },
+        state::{Account as TokenAccount, Mint},
+    },
+};
+use solana_program::program::invoke;
+
+use crate::{error::SwapLayerError, state::Token2022ExtensionPolicy};
+
+/// Returns the amount a token account receives after the mint's transfer fee is withheld. If the
+/// mint is not a Token-2022 mint or does not have the transfer fee extension, the amount is
+/// returned unchanged.
+pub fn amount_after_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != token_2022::ID {
+        return Ok(amount);
+    }
+
+    let data = mint.try_borrow_data()?;
+    epoch_amount_after_transfer_fee(&data, Clock::get()?.epoch, amount)
+}
+
+fn epoch_amount_after_transfer_fee(mint_data: &[u8], epoch: u64, amount: u64) -> Result<u64> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => {
+            let fee = transfer_fee_config
+                .calculate_epoch_fee(epoch, amount)
+                .ok_or_else(|| SwapLayerError::TransferFeeCalculationFailed)?;
+
+            Ok(amount.saturating_sub(fee))
+        }
+        Err(_) => Ok(amount),
+    }
+}
+
+/// Harvest the transfer fees withheld in the token account to its mint. Token-2022 does not allow
+/// closing a token account with withheld fees, so this must be called before closing any token
+/// account that has received a transfer fee mint. Harvesting is permissionless, but the mint must
+/// be writable if there are fees to harvest. Otherwise this is a no-op.
+pub fn harvest_withheld_tokens_to_mint<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+) -> Result<()> {
+    if *token_account.owner != token_2022::ID {
+        return Ok(());
+    }
+
+    let withheld_amount = {
+        let data = token_account.try_borrow_data()?;
+        StateWithExtensions::<TokenAccount>::unpack(&data)?
+            .get_extension::<TransferFeeAmount>()
+            .map(|transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount))
+            .unwrap_or_default()
+    };
+
+    if withheld_amount == 0 {
+        return Ok(());
+    }
+
+    invoke(
+        &transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+            &token_2022::ID,
+            mint.key,
+            &[token_account.key],
+        )?,
+        &[mint.clone(), token_account.clone(), token_program.clone()],
+    )
+    .map_err(Into::into)
+}
+
+/// Verify that the mint's Token-2022 extensions are allowed by the policy. Mints owned by the SPL
+/// Token program have no extensions.
+pub fn verify_mint_extensions(mint: &AccountInfo, policy: &Token2022ExtensionPolicy) -> Result<()> {
+    if *mint.owner != token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let extension_types = StateWithExtensions::<Mint>::unpack(&data)?.get_extension_types()?;
+
+    for extension_type in extension_types {
+        let allowed = match extension_type {
+            ExtensionType::TransferHook => policy.allow_transfer_hook,
+            ExtensionType::ConfidentialTransferMint
+            | ExtensionType::ConfidentialTransferFeeConfig => policy.allow_confidential_transfer,
+            ExtensionType::PermanentDelegate => policy.allow_permanent_delegate,
+            _ => true,
+        };
+        require!(allowed, SwapLayerError::Token2022ExtensionNotAllowed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        transfer_fee::TransferFee, StateWithExtensionsMut,
+    };
+    use solana_program::program_pack::Pack;
+
+    use super::*;
+
+    fn transfer_fee(epoch: u64, maximum_fee: u64, transfer_fee_basis_points: u16) -> TransferFee {
+        TransferFee {
+            epoch: epoch.into(),
+            maximum_fee: maximum_fee.into(),
+            transfer_fee_basis_points: transfer_fee_basis_points.into(),
+        }
+    }
+
+    fn transfer_fee_mint_data(older: TransferFee, newer: TransferFee) -> Vec<u8> {
+        let mut data = vec![
+            0;
+            ExtensionType::try_calculate_account_len::<Mint>(&[
+                ExtensionType::TransferFeeConfig
+            ])
+            .unwrap()
+        ];
+
+        let mut mint = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let transfer_fee_config = mint.init_extension::<TransferFeeConfig>(true).unwrap();
+        transfer_fee_config.older_transfer_fee = older;
+        transfer_fee_config.newer_transfer_fee = newer;
+
+        mint.base = Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+
+        data
+    }
+
+    #[test]
+    fn test_amount_after_transfer_fee() {
+        // 1% fee capped at 5,000.
+        let data = transfer_fee_mint_data(transfer_fee(0, 5_000, 100), transfer_fee(0, 5_000, 100));
+
+        assert_eq!(
+            epoch_amount_after_transfer_fee(&data, 0, 100_000).unwrap(),
+            99_000
+        );
+        assert_eq!(
+            epoch_amount_after_transfer_fee(&data, 0, 1_000_000).unwrap(),
+            995_000
+        );
+        assert_eq!(epoch_amount_after_transfer_fee(&data, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_amount_after_transfer_fee_newer_epoch() {
+        // No fee until epoch 10, then 0.5% uncapped.
+        let data = transfer_fee_mint_data(transfer_fee(0, 0, 0), transfer_fee(10, u64::MAX, 50));
+
+        assert_eq!(
+            epoch_amount_after_transfer_fee(&data, 9, 100_000).unwrap(),
+            100_000
+        );
+        assert_eq!(
+            epoch_amount_after_transfer_fee(&data, 10, 100_000).unwrap(),
+            99_500
+        );
+    }
+
+    #[test]
+    fn test_amount_without_transfer_fee_extension() {
+        let mut data = vec![0; Mint::LEN];
+        Mint::pack(
+            Mint {
+                decimals: 6,
+                is_initialized: true,
+                ..Default::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+
+        assert_eq!(
+            epoch_amount_after_transfer_fee(&data, 0, 100_000).unwrap(),
+            100_000
+        );
+    }
+}
@@ -1,4 +1,4 @@
-import { RelayParams } from "./state";
+import { PeerParams, RelayParams } from "./state";
 import { uint64ToBN } from "@wormhole-foundation/example-liquidity-layer-solana/common";
 
 export const TEST_RELAY_PARAMS: RelayParams = {
@@ -17,3 +17,18 @@ export const TEST_RELAY_PARAMS: RelayParams = {
         finalizedLimit: 2,
     },
 };
+
+export const TEST_PEER_PARAMS: PeerParams = {
+    priceFeed: null,
+    relayingFeeProtocolShare: 0,
+    relayerExclusivityWindow: 0,
+    outputSwapDeadlinePolicy: { accept: {} },
+    maxOutputSwapDeadlineHorizon: 0,
+    supportedSwapTypes: {
+        uniswapV3: true,
+        traderJoe: true,
+        jupiterV6: false,
+    },
+    maxSwapPathLength: 4,
+    supportsRelayPayload: false,
+};
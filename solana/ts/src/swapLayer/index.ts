@@ -6,7 +6,14 @@ export * from "./state";
 import * as wormholeSdk from "@certusone/wormhole-sdk";
 import { BN, Program } from "@coral-xyz/anchor";
 import * as splToken from "@solana/spl-token";
-import { Connection, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import {
+    AddressLookupTableProgram,
+    Connection,
+    PublicKey,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
+    SystemProgram,
+    TransactionInstruction,
+} from "@solana/web3.js";
 import {
     Uint64,
     uint64ToBN,
@@ -18,7 +25,21 @@ import { keccak256 } from "@wormhole-foundation/sdk-definitions";
 import IDL from "../../../target/idl/swap_layer.json";
 import { SwapLayer } from "../../../target/types/swap_layer";
 import { OutputToken, encodeOutputToken } from "./messages";
-import { Custodian, Peer, RedeemOption, RelayParams, StagedInbound, StagedOutbound } from "./state";
+import {
+    Custodian,
+    FeeVault,
+    Peer,
+    PeerParams,
+    ProgramLookupTable,
+    RecipientConfig,
+    RedeemOption,
+    RelayParams,
+    StageOutboundIntent,
+    StagedInbound,
+    StagedOutbound,
+    StagedOutboundCounter,
+    Token2022ExtensionPolicy,
+} from "./state";
 import { programDataAddress } from "./utils";
 
 export const PROGRAM_IDS = ["SwapLayer1111111111111111111111111111111111"] as const;
@@ -29,7 +50,7 @@ export type AddPeerArgs = {
     chain: wormholeSdk.ChainId;
     address: Array<number>;
     relayParams: RelayParams;
-};
+} & PeerParams;
 
 export type RelayOptions = {
     gasDropoff: number;
@@ -50,12 +71,60 @@ export type UpdateRelayParametersArgs = {
     relayParams: RelayParams;
 };
 
+export type StageOutboundRedeemOption =
+    | { relay: { gasDropoff: number; maxRelayerFee: Uint64 } }
+    | { payload: Uint8Array | Buffer }
+    | {
+          relayPayload: {
+              gasDropoff: number;
+              maxRelayerFee: Uint64;
+              payload: Uint8Array | Buffer;
+          };
+      };
+
+function toRedeemOption(input: StageOutboundRedeemOption | null): RedeemOption | null {
+    if (input === null) {
+        return null;
+    } else if ("relay" in input) {
+        const { gasDropoff, maxRelayerFee } = input.relay;
+        return {
+            relay: {
+                gasDropoff,
+                maxRelayerFee: uint64ToBN(maxRelayerFee),
+            },
+        };
+    } else if ("payload" in input) {
+        const { payload } = input;
+        return { payload: [Buffer.from(payload)] };
+    } else if ("relayPayload" in input) {
+        const { gasDropoff, maxRelayerFee, payload } = input.relayPayload;
+        return {
+            relayPayload: {
+                gasDropoff,
+                maxRelayerFee: uint64ToBN(maxRelayerFee),
+                payload: Buffer.from(payload),
+            },
+        };
+    } else {
+        throw new Error("invalid redeem option");
+    }
+}
+
 type CheckedCustodianComposite = {
     custodian: PublicKey;
 };
 
 type RegisteredPeerComposite = { peer: PublicKey };
 
+type OutboundTargetComposite = {
+    peer: RegisteredPeerComposite;
+    nativeTokenPriceFeed: PublicKey | null;
+};
+
+type OutboundSourceComposite = { custodian: CheckedCustodianComposite; srcMint: PublicKey };
+
+type UsdcFeeVaultComposite = { feeVault: PublicKey; custodyToken: PublicKey };
+
 export class SwapLayerProgram {
     private _programId: ProgramId;
     private _usdcMint: PublicKey;
@@ -153,6 +222,21 @@ export class SwapLayerProgram {
         return { peer: peer ?? this.peerAddress(chain) };
     }
 
+    outboundTargetComposite(opts: {
+        peer?: PublicKey;
+        chain?: ChainId;
+        nativeTokenPriceFeed?: PublicKey | null;
+    }): OutboundTargetComposite {
+        return {
+            peer: this.registeredPeerComposite(opts),
+            nativeTokenPriceFeed: opts.nativeTokenPriceFeed ?? null,
+        };
+    }
+
+    outboundSourceComposite(srcMint: PublicKey): OutboundSourceComposite {
+        return { custodian: this.checkedCustodianComposite(), srcMint };
+    }
+
     async consumeSwapLayerFillComposite(
         accounts: {
             preparedFill: PublicKey;
@@ -259,6 +343,35 @@ export class SwapLayerProgram {
         return this.program.account.stagedOutbound.fetch(addr);
     }
 
+    indexedStagedOutboundAddress(sender: PublicKey, nonce: Uint64): PublicKey {
+        return StagedOutbound.address(this.ID, sender, uint64ToBigInt(nonce));
+    }
+
+    stagedOutboundCounterAddress(sender: PublicKey): PublicKey {
+        return StagedOutboundCounter.address(this.ID, sender);
+    }
+
+    async fetchStagedOutboundCounter(
+        input: PublicKey | { address: PublicKey },
+    ): Promise<StagedOutboundCounter> {
+        const addr = "address" in input ? input.address : this.stagedOutboundCounterAddress(input);
+        return this.program.account.stagedOutboundCounter.fetch(addr);
+    }
+
+    /**
+     * Returns the sender's next nonce, which is zero if the sender has not staged an indexed
+     * outbound transfer yet.
+     */
+    async fetchNextStagedOutboundNonce(sender: PublicKey): Promise<bigint> {
+        return this.fetchStagedOutboundCounter(sender)
+            .then((counter) => BigInt(counter.nextNonce.toString()))
+            .catch((_) => 0n);
+    }
+
+    intentAuthorityAddress(): PublicKey {
+        return PublicKey.findProgramAddressSync([Buffer.from("intent-authority")], this.ID)[0];
+    }
+
     stagedCustodyTokenAddress(stagedAccount: PublicKey): PublicKey {
         return PublicKey.findProgramAddressSync(
             [Buffer.from("staged-custody"), stagedAccount.toBuffer()],
@@ -266,6 +379,10 @@ export class SwapLayerProgram {
         )[0];
     }
 
+    outboundCustodyTokenAddress(): PublicKey {
+        return PublicKey.findProgramAddressSync([Buffer.from("outbound-custody")], this.ID)[0];
+    }
+
     async fetchCustodian(input?: { address: PublicKey }): Promise<Custodian> {
         const addr = input === undefined ? this.custodianAddress() : input.address;
         return this.program.account.custodian.fetch(addr);
@@ -279,6 +396,72 @@ export class SwapLayerProgram {
         return this.program.account.peer.fetch(addr);
     }
 
+    feeVaultAddress(mint?: PublicKey): PublicKey {
+        return FeeVault.address(this.ID, mint ?? this._usdcMint);
+    }
+
+    feeVaultCustodyTokenAddress(feeVault: PublicKey): PublicKey {
+        return FeeVault.custodyTokenAddress(this.ID, feeVault);
+    }
+
+    usdcFeeVaultComposite(feeVault?: PublicKey): UsdcFeeVaultComposite {
+        feeVault ??= this.feeVaultAddress();
+        return { feeVault, custodyToken: this.feeVaultCustodyTokenAddress(feeVault) };
+    }
+
+    async fetchFeeVault(input?: PublicKey | { address: PublicKey }): Promise<FeeVault> {
+        const addr =
+            input !== undefined && "address" in input ? input.address : this.feeVaultAddress(input);
+        return this.program.account.feeVault.fetch(addr);
+    }
+
+    recipientConfigAddress(recipient: PublicKey): PublicKey {
+        return RecipientConfig.address(this.ID, recipient);
+    }
+
+    async fetchRecipientConfig(
+        input: PublicKey | { address: PublicKey },
+    ): Promise<RecipientConfig> {
+        const addr = "address" in input ? input.address : this.recipientConfigAddress(input);
+        return this.program.account.recipientConfig.fetch(addr);
+    }
+
+    programLookupTableAddress(): PublicKey {
+        return ProgramLookupTable.address(this.ID);
+    }
+
+    async fetchProgramLookupTable(input?: { address: PublicKey }): Promise<ProgramLookupTable> {
+        const addr = input === undefined ? this.programLookupTableAddress() : input.address;
+        return this.program.account.programLookupTable.fetch(addr);
+    }
+
+    /**
+     * Marks the mint as writable if it is a Token-2022 mint with the transfer fee extension. The
+     * program harvests withheld fees to the mint before closing token accounts.
+     */
+    async transferFeeMintWritable(
+        ix: TransactionInstruction,
+        mint: PublicKey,
+    ): Promise<TransactionInstruction> {
+        const accInfo = await this.connection().getAccountInfo(mint);
+        if (accInfo === null || !accInfo.owner.equals(splToken.TOKEN_2022_PROGRAM_ID)) {
+            return ix;
+        }
+
+        const transferFeeConfig = splToken.getTransferFeeConfig(
+            splToken.unpackMint(mint, accInfo, accInfo.owner),
+        );
+        if (transferFeeConfig !== null) {
+            for (const key of ix.keys) {
+                if (key.pubkey.equals(mint)) {
+                    key.isWritable = true;
+                }
+            }
+        }
+
+        return ix;
+    }
+
     async initializeIx(accounts: {
         owner: PublicKey;
         ownerAssistant: PublicKey;
@@ -312,25 +495,114 @@ export class SwapLayerProgram {
             payer?: PublicKey;
             custodian?: PublicKey;
             peer?: PublicKey;
+            lookupTable?: PublicKey | null;
+            targetRouterEndpoint?: PublicKey | null;
         },
         args: AddPeerArgs,
     ) {
-        let { ownerOrAssistant, payer, custodian, peer } = accounts;
+        let { ownerOrAssistant, payer, custodian, peer, lookupTable, targetRouterEndpoint } =
+            accounts;
         payer ??= ownerOrAssistant;
         peer ??= this.peerAddress(args.chain);
 
-        return (
-            this.program.methods
-                // @ts-ignore
-                .addPeer(args)
-                .accounts({
-                    payer,
-                    admin: this.adminComposite(ownerOrAssistant, custodian),
-                    peer,
-                    systemProgram: SystemProgram.programId,
-                })
-                .instruction()
-        );
+        // The program lookup table must be extended with the new peer once it exists.
+        const programLookupTable = this.programLookupTableAddress();
+        if (lookupTable === undefined) {
+            lookupTable = await this.fetchProgramLookupTable()
+                .then((acct) => acct.address)
+                .catch((_) => null);
+        }
+        if (targetRouterEndpoint === undefined) {
+            targetRouterEndpoint =
+                lookupTable === null
+                    ? null
+                    : this.tokenRouterProgram()
+                          .matchingEngineProgram()
+                          .routerEndpointAddress(args.chain);
+        }
+
+        return this.program.methods
+            .addPeer(args)
+            .accounts({
+                payer,
+                admin: this.adminComposite(ownerOrAssistant, custodian),
+                peer,
+                programLookupTable,
+                lookupTable,
+                targetRouterEndpoint,
+                addressLookupTableProgram:
+                    lookupTable === null ? null : AddressLookupTableProgram.programId,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
+    async createLookupTableIx(
+        accounts: {
+            ownerOrAssistant: PublicKey;
+            payer?: PublicKey;
+            custodian?: PublicKey;
+        },
+        recentSlot: Uint64,
+    ): Promise<[TransactionInstruction, PublicKey]> {
+        const { ownerOrAssistant, custodian } = accounts;
+
+        let { payer } = accounts;
+        payer ??= ownerOrAssistant;
+
+        const programLookupTable = this.programLookupTableAddress();
+        const [, lookupTable] = AddressLookupTableProgram.createLookupTable({
+            authority: programLookupTable,
+            payer,
+            recentSlot: uint64ToBigInt(recentSlot),
+        });
+
+        const tokenRouter = this.tokenRouterProgram();
+
+        const ix = await this.program.methods
+            .createLookupTable(uint64ToBN(recentSlot))
+            .accounts({
+                payer,
+                admin: this.adminComposite(ownerOrAssistant, custodian),
+                programLookupTable,
+                lookupTable,
+                usdc: this.usdcComposite(),
+                tokenRouterCustodian: tokenRouter.custodianAddress(),
+                tokenRouterProgram: tokenRouter.ID,
+                addressLookupTableProgram: AddressLookupTableProgram.programId,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+
+        return [ix, lookupTable];
+    }
+
+    async extendLookupTableIx(
+        accounts: {
+            ownerOrAssistant: PublicKey;
+            payer?: PublicKey;
+            custodian?: PublicKey;
+            lookupTable?: PublicKey;
+        },
+        addresses: PublicKey[],
+    ): Promise<TransactionInstruction> {
+        const { ownerOrAssistant, custodian } = accounts;
+
+        let { payer, lookupTable } = accounts;
+        payer ??= ownerOrAssistant;
+        lookupTable ??= await this.fetchProgramLookupTable().then((acct) => acct.address);
+
+        return this.program.methods
+            .extendLookupTable(addresses)
+            .accounts({
+                payer,
+                admin: this.adminComposite(ownerOrAssistant, custodian),
+                programLookupTable: this.programLookupTableAddress(),
+                lookupTable,
+                addressLookupTableProgram: AddressLookupTableProgram.programId,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
     }
 
     async updatePeerIx(
@@ -344,16 +616,60 @@ export class SwapLayerProgram {
         let { owner, custodian, peer } = accounts;
         peer ??= this.peerAddress(args.chain);
 
-        return (
-            this.program.methods
-                // @ts-ignore
-                .updatePeer(args)
-                .accounts({
-                    admin: this.ownerOnlyComposite(owner, custodian),
-                    peer,
-                })
-                .instruction()
-        );
+        return this.program.methods
+            .updatePeer(args)
+            .accounts({
+                admin: this.ownerOnlyComposite(owner, custodian),
+                peer,
+            })
+            .instruction();
+    }
+
+    async migratePeerIx(
+        accounts: {
+            owner: PublicKey;
+            payer?: PublicKey;
+            custodian?: PublicKey;
+            peer?: PublicKey;
+        },
+        args: AddPeerArgs,
+    ) {
+        let { owner, payer, custodian, peer } = accounts;
+        payer ??= owner;
+        peer ??= this.peerAddress(args.chain);
+
+        return this.program.methods
+            .migratePeer(args)
+            .accounts({
+                payer,
+                admin: this.ownerOnlyComposite(owner, custodian),
+                peer,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
+    async migrateCustodianIx(
+        accounts: {
+            owner: PublicKey;
+            payer?: PublicKey;
+            custodian?: PublicKey;
+        },
+        policy: Token2022ExtensionPolicy,
+    ) {
+        let { owner, payer, custodian } = accounts;
+        payer ??= owner;
+        custodian ??= this.custodianAddress();
+
+        return this.program.methods
+            .migrateCustodian(policy)
+            .accounts({
+                payer,
+                owner,
+                custodian,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
     }
 
     async submitOwnershipTransferIx(accounts: {
@@ -466,12 +782,160 @@ export class SwapLayerProgram {
             .instruction();
     }
 
+    async addFeeVaultIx(accounts: {
+        ownerOrAssistant: PublicKey;
+        payer?: PublicKey;
+        custodian?: PublicKey;
+        mint?: PublicKey;
+        destinationToken?: PublicKey | null;
+        tokenProgram?: PublicKey;
+    }): Promise<TransactionInstruction> {
+        const { ownerOrAssistant, custodian } = accounts;
+
+        let { payer, mint, destinationToken, tokenProgram } = accounts;
+        payer ??= ownerOrAssistant;
+        mint ??= this.usdcMint;
+        destinationToken ??= null;
+        if (tokenProgram === undefined) {
+            const accInfo = await this.connection().getAccountInfo(mint);
+            tokenProgram = accInfo.owner;
+        }
+
+        const feeVault = this.feeVaultAddress(mint);
+
+        return this.program.methods
+            .addFeeVault()
+            .accounts({
+                payer,
+                admin: this.adminComposite(ownerOrAssistant, custodian),
+                feeVault,
+                custodyToken: this.feeVaultCustodyTokenAddress(feeVault),
+                destinationToken,
+                mint,
+                tokenProgram,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
+    async updateFeeVaultDestinationIx(accounts: {
+        owner: PublicKey;
+        newDestinationToken: PublicKey;
+        custodian?: PublicKey;
+        feeVault?: PublicKey;
+    }): Promise<TransactionInstruction> {
+        const { owner, newDestinationToken, custodian } = accounts;
+
+        let { feeVault } = accounts;
+        feeVault ??= this.feeVaultAddress();
+
+        return this.program.methods
+            .updateFeeVaultDestination()
+            .accounts({
+                admin: this.ownerOnlyComposite(owner, custodian),
+                feeVault,
+                newDestinationToken,
+            })
+            .instruction();
+    }
+
+    async sweepFeesIx(accounts: {
+        ownerOrAssistant: PublicKey;
+        custodian?: PublicKey;
+        feeVault?: PublicKey;
+        destinationToken?: PublicKey;
+        mint?: PublicKey;
+        tokenProgram?: PublicKey;
+    }): Promise<TransactionInstruction> {
+        const { ownerOrAssistant, custodian } = accounts;
+
+        let { feeVault, destinationToken, mint, tokenProgram } = accounts;
+        feeVault ??= this.feeVaultAddress(mint);
+        if (destinationToken === undefined || mint === undefined) {
+            const { seeds, destinationToken: vaultDestinationToken } =
+                await this.fetchFeeVault({ address: feeVault });
+            destinationToken ??= vaultDestinationToken;
+            mint ??= seeds.mint;
+        }
+        if (tokenProgram === undefined) {
+            const accInfo = await this.connection().getAccountInfo(mint);
+            tokenProgram = accInfo.owner;
+        }
+
+        return this.program.methods
+            .sweepFees()
+            .accounts({
+                admin: this.adminComposite(ownerOrAssistant, custodian),
+                feeVault,
+                custodyToken: this.feeVaultCustodyTokenAddress(feeVault),
+                destinationToken,
+                mint,
+                tokenProgram,
+            })
+            .instruction();
+    }
+
+    async setRecipientConfigIx(
+        accounts: {
+            payer: PublicKey;
+            recipient?: PublicKey;
+        },
+        args: {
+            allowAnyTokenAccount: boolean;
+            gasDropoffAsWsol: boolean;
+        },
+    ): Promise<TransactionInstruction> {
+        const { payer } = accounts;
+        const recipient = accounts.recipient ?? payer;
+        const { gasDropoffAsWsol } = args;
+
+        return this.program.methods
+            .setRecipientConfig(args)
+            .accounts({
+                payer,
+                recipient,
+                recipientConfig: this.recipientConfigAddress(recipient),
+                recipientWsolToken: gasDropoffAsWsol
+                    ? splToken.getAssociatedTokenAddressSync(splToken.NATIVE_MINT, recipient, true)
+                    : null,
+                nativeMint: gasDropoffAsWsol ? splToken.NATIVE_MINT : null,
+                tokenProgram: gasDropoffAsWsol ? splToken.TOKEN_PROGRAM_ID : null,
+                associatedTokenProgram: gasDropoffAsWsol
+                    ? splToken.ASSOCIATED_TOKEN_PROGRAM_ID
+                    : null,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
+    async migrateStagedOutboundIx(accounts: {
+        sender: PublicKey;
+        stagedOutbound: PublicKey;
+        payer?: PublicKey;
+    }) {
+        const { sender, stagedOutbound } = accounts;
+        let { payer } = accounts;
+        payer ??= sender;
+
+        return this.program.methods
+            .migrateStagedOutbound()
+            .accounts({
+                payer,
+                sender,
+                stagedOutbound,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
     async closeStagedOutboundIx(
         accounts: {
             stagedOutbound: PublicKey;
             senderToken: PublicKey;
             preparedBy?: PublicKey;
             sender?: PublicKey;
+            srcMint?: PublicKey;
+            srcTokenProgram?: PublicKey;
         },
         targetChain: ChainId,
     ): Promise<TransactionInstruction> {
@@ -482,6 +946,15 @@ export class SwapLayerProgram {
             senderToken,
         } = accounts;
 
+        const stagedCustodyToken = this.stagedCustodyTokenAddress(stagedOutbound);
+
+        let { srcMint, srcTokenProgram } = accounts;
+        if (srcMint === undefined || srcTokenProgram === undefined) {
+            const accInfo = await this.connection().getAccountInfo(stagedCustodyToken);
+            srcTokenProgram ??= accInfo.owner;
+            srcMint ??= splToken.unpackAccount(stagedCustodyToken, accInfo, accInfo.owner).mint;
+        }
+
         const { preparedBy, sender } = await (async () => {
             if (inputPreparedBy === undefined || inputSender === undefined) {
                 const {
@@ -499,22 +972,138 @@ export class SwapLayerProgram {
             }
         })();
 
-        return this.program.methods
+        const ix = await this.program.methods
             .closeStagedOutbound()
             .accounts({
                 sender,
                 targetPeer: this.registeredPeerComposite({ chain: targetChain }),
                 preparedBy,
                 stagedOutbound,
-                stagedCustodyToken: this.stagedCustodyTokenAddress(stagedOutbound),
+                stagedCustodyToken,
                 senderToken,
-                tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                srcMint,
+                srcTokenProgram,
                 systemProgram: SystemProgram.programId,
             })
             .instruction();
+
+        return this.transferFeeMintWritable(ix, srcMint);
     }
 
-    async stageOutboundIx(
+    async refundStagedOutboundIx(
+        accounts: {
+            stagedOutbound: PublicKey;
+            srcMint: PublicKey;
+            preparedBy?: PublicKey;
+            sender?: PublicKey;
+            senderToken?: PublicKey;
+            srcTokenProgram?: PublicKey;
+        },
+        targetChain: ChainId,
+    ): Promise<TransactionInstruction> {
+        const { stagedOutbound, srcMint } = accounts;
+        let { preparedBy, sender, senderToken, srcTokenProgram } = accounts;
+
+        if (preparedBy === undefined || sender === undefined) {
+            const { info } = await this.fetchStagedOutbound(stagedOutbound);
+            preparedBy ??= info.preparedBy;
+            sender ??= info.sender;
+        }
+        if (srcTokenProgram === undefined) {
+            const accInfo = await this.connection().getAccountInfo(srcMint);
+            srcTokenProgram = accInfo.owner;
+        }
+        senderToken ??= splToken.getAssociatedTokenAddressSync(
+            srcMint,
+            sender,
+            true,
+            srcTokenProgram,
+        );
+
+        const ix = await this.program.methods
+            .refundStagedOutbound()
+            .accounts({
+                preparedBy,
+                stagedOutbound,
+                stagedCustodyToken: this.stagedCustodyTokenAddress(stagedOutbound),
+                targetPeer: this.registeredPeerComposite({ chain: targetChain }),
+                sender,
+                senderToken,
+                srcMint,
+                srcTokenProgram,
+            })
+            .instruction();
+
+        return this.transferFeeMintWritable(ix, srcMint);
+    }
+
+    async amendStagedOutboundIx(
+        accounts: {
+            stagedOutbound: PublicKey;
+            srcMint: PublicKey;
+            preparedBy?: PublicKey | null;
+            sender?: PublicKey;
+            senderToken?: PublicKey | null;
+            srcTokenProgram?: PublicKey;
+            peer?: PublicKey;
+            nativeTokenPriceFeed?: PublicKey | null;
+        },
+        args: {
+            targetChain: ChainId;
+            recipient: Array<number>;
+            redeemOption: StageOutboundRedeemOption | null;
+            outputToken: OutputToken | null;
+        },
+    ): Promise<TransactionInstruction> {
+        const { stagedOutbound, srcMint, peer, nativeTokenPriceFeed } = accounts;
+        const { targetChain, recipient, redeemOption, outputToken } = args;
+        let { preparedBy, sender, senderToken, srcTokenProgram } = accounts;
+
+        if (preparedBy === undefined || sender === undefined) {
+            const { info } = await this.fetchStagedOutbound(stagedOutbound);
+            preparedBy ??= info.preparedBy;
+            sender ??= info.sender;
+        }
+        if (srcTokenProgram === undefined) {
+            const accInfo = await this.connection().getAccountInfo(srcMint);
+            srcTokenProgram = accInfo.owner;
+        }
+        if (senderToken === undefined) {
+            senderToken = splToken.getAssociatedTokenAddressSync(
+                srcMint,
+                sender,
+                true,
+                srcTokenProgram,
+            );
+        }
+
+        return this.program.methods
+            .amendStagedOutbound({
+                recipient,
+                redeemOption: toRedeemOption(redeemOption),
+                encodedOutputToken:
+                    outputToken === null ? null : Buffer.from(encodeOutputToken(outputToken)),
+            })
+            .accounts({
+                sender,
+                preparedBy,
+                stagedOutbound,
+                stagedCustodyToken: this.stagedCustodyTokenAddress(stagedOutbound),
+                preparedOrder: this.preparedOrderAddress(stagedOutbound),
+                targetPeer: this.outboundTargetComposite({
+                    peer,
+                    chain: targetChain,
+                    nativeTokenPriceFeed,
+                }),
+                senderToken,
+                srcMint,
+                srcTokenProgram,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
+    async stageOutboundIx(
         accounts: {
             payer: PublicKey;
             stagedOutbound: PublicKey;
@@ -525,6 +1114,7 @@ export class SwapLayerProgram {
             srcMint?: PublicKey;
             srcTokenProgram?: PublicKey;
             peer?: PublicKey;
+            nativeTokenPriceFeed?: PublicKey | null;
         },
         args: {
             transferType: "native" | "programTransferAuthority" | "sender";
@@ -532,15 +1122,14 @@ export class SwapLayerProgram {
             isExactIn: boolean;
             targetChain: ChainId;
             recipient: Array<number>;
-            redeemOption:
-                | { relay: { gasDropoff: number; maxRelayerFee: Uint64 } }
-                | { payload: Uint8Array | Buffer }
-                | null;
+            redeemOption: StageOutboundRedeemOption | null;
             outputToken: OutputToken | null;
+            expiresAt?: BN | null;
         },
     ): Promise<[approveIx: TransactionInstruction | null, stageIx: TransactionInstruction]> {
-        const { payer, stagedOutbound, usdcRefundToken, peer } = accounts;
+        const { payer, stagedOutbound, usdcRefundToken, peer, nativeTokenPriceFeed } = accounts;
         const { transferType, amountIn, redeemOption: inputRedeemOption, outputToken } = args;
+        const expiresAt = args.expiresAt ?? null;
 
         let { sender, senderToken, programTransferAuthority, srcMint, srcTokenProgram } = accounts;
         srcMint ??= transferType === "native" ? splToken.NATIVE_MINT : this.usdcMint;
@@ -549,24 +1138,7 @@ export class SwapLayerProgram {
             srcTokenProgram = accInfo.owner;
         }
 
-        const redeemOption = ((): RedeemOption | null => {
-            if (inputRedeemOption === null) {
-                return null;
-            } else if ("relay" in inputRedeemOption) {
-                const { gasDropoff, maxRelayerFee } = inputRedeemOption.relay;
-                return {
-                    relay: {
-                        gasDropoff,
-                        maxRelayerFee: uint64ToBN(maxRelayerFee),
-                    },
-                };
-            } else if ("payload" in inputRedeemOption) {
-                const { payload } = inputRedeemOption;
-                return { payload: [Buffer.from(payload)] };
-            } else {
-                throw new Error("invalid redeem option");
-            }
-        })();
+        const redeemOption = toRedeemOption(inputRedeemOption);
 
         const encodedOutputToken =
             outputToken === null ? null : Buffer.from(encodeOutputToken(outputToken));
@@ -575,6 +1147,7 @@ export class SwapLayerProgram {
             amountIn: uint64ToBN(amountIn),
             redeemOption,
             encodedOutputToken,
+            expiresAt,
         });
 
         if (transferType === "native") {
@@ -612,11 +1185,15 @@ export class SwapLayerProgram {
             sender,
             programTransferAuthority: null,
             senderToken,
-            targetPeer: this.registeredPeerComposite({ peer, chain: args.targetChain }),
+            targetPeer: this.outboundTargetComposite({
+                peer,
+                chain: args.targetChain,
+                nativeTokenPriceFeed,
+            }),
             stagedOutbound,
             stagedCustodyToken: this.stagedCustodyTokenAddress(stagedOutbound),
             usdcRefundToken,
-            srcMint,
+            source: this.outboundSourceComposite(srcMint),
             srcTokenProgram,
             tokenProgram: splToken.TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -655,6 +1232,186 @@ export class SwapLayerProgram {
         return [approveIx, await ixBuilder.accounts(definedAccounts).instruction()];
     }
 
+    async stageOutboundIndexedIx(
+        accounts: {
+            payer: PublicKey;
+            usdcRefundToken: PublicKey;
+            sender?: PublicKey;
+            senderToken?: PublicKey | null;
+            srcMint?: PublicKey;
+            srcTokenProgram?: PublicKey;
+            peer?: PublicKey;
+            nativeTokenPriceFeed?: PublicKey | null;
+        },
+        args: {
+            transferType: "native" | "sender";
+            amountIn: Uint64;
+            isExactIn: boolean;
+            targetChain: ChainId;
+            recipient: Array<number>;
+            redeemOption: StageOutboundRedeemOption | null;
+            outputToken: OutputToken | null;
+            expiresAt?: BN | null;
+        },
+    ): Promise<TransactionInstruction> {
+        const { payer, usdcRefundToken, peer, nativeTokenPriceFeed } = accounts;
+        const { transferType, amountIn, redeemOption, outputToken } = args;
+
+        let { sender, senderToken, srcMint, srcTokenProgram } = accounts;
+        sender ??= payer;
+        srcMint ??= transferType === "native" ? splToken.NATIVE_MINT : this.usdcMint;
+        if (srcTokenProgram === undefined) {
+            const accInfo = await this.connection().getAccountInfo(srcMint);
+            srcTokenProgram = accInfo.owner;
+        }
+        if (senderToken === undefined) {
+            senderToken =
+                transferType === "native"
+                    ? null
+                    : splToken.getAssociatedTokenAddressSync(
+                          srcMint,
+                          sender,
+                          false,
+                          srcTokenProgram,
+                      );
+        }
+
+        const stagedOutbound = this.indexedStagedOutboundAddress(
+            sender,
+            await this.fetchNextStagedOutboundNonce(sender),
+        );
+
+        return this.program.methods
+            .stageOutboundIndexed({
+                amountIn: uint64ToBN(amountIn),
+                isExactIn: args.isExactIn,
+                targetChain: args.targetChain,
+                recipient: args.recipient,
+                redeemOption: toRedeemOption(redeemOption),
+                encodedOutputToken:
+                    outputToken === null ? null : Buffer.from(encodeOutputToken(outputToken)),
+                expiresAt: args.expiresAt ?? null,
+            })
+            .accounts({
+                payer,
+                sender,
+                stagedOutboundCounter: this.stagedOutboundCounterAddress(sender),
+                senderToken,
+                targetPeer: this.outboundTargetComposite({
+                    peer,
+                    chain: args.targetChain,
+                    nativeTokenPriceFeed,
+                }),
+                stagedOutbound,
+                stagedCustodyToken: this.stagedCustodyTokenAddress(stagedOutbound),
+                usdcRefundToken,
+                source: this.outboundSourceComposite(srcMint),
+                srcTokenProgram,
+                tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
+    stageOutboundIntent(args: {
+        sender: PublicKey;
+        srcMint?: PublicKey;
+        amountIn: Uint64;
+        isExactIn: boolean;
+        targetChain: ChainId;
+        recipient: Array<number>;
+        redeemOption: StageOutboundRedeemOption | null;
+        outputToken: OutputToken | null;
+        usdcRefundToken: PublicKey;
+        nonce: Uint64;
+        deadline: BN;
+        expiresAt?: BN | null;
+    }): StageOutboundIntent {
+        const { amountIn, redeemOption, outputToken, nonce } = args;
+
+        return {
+            sender: args.sender,
+            srcMint: args.srcMint ?? this.usdcMint,
+            amountIn: uint64ToBN(amountIn),
+            isExactIn: args.isExactIn,
+            targetChain: args.targetChain,
+            recipient: args.recipient,
+            redeemOption: toRedeemOption(redeemOption),
+            encodedOutputToken:
+                outputToken === null ? null : Buffer.from(encodeOutputToken(outputToken)),
+            usdcRefundToken: args.usdcRefundToken,
+            nonce: uint64ToBN(nonce),
+            deadline: args.deadline,
+            expiresAt: args.expiresAt ?? null,
+        };
+    }
+
+    /**
+     * Message the sender signs with an Ed25519 program instruction, which must precede the
+     * instruction staging the intent.
+     */
+    stageOutboundIntentMessage(intent: StageOutboundIntent): Buffer {
+        return Buffer.concat([
+            Buffer.from("swap-layer:stage-outbound-intent"),
+            this.ID.toBuffer(),
+            this.program.coder.types.encode("stageOutboundIntent", intent),
+        ]);
+    }
+
+    async stageOutboundWithIntentIx(
+        accounts: {
+            payer: PublicKey;
+            senderToken?: PublicKey;
+            srcTokenProgram?: PublicKey;
+            peer?: PublicKey;
+            nativeTokenPriceFeed?: PublicKey | null;
+        },
+        intent: StageOutboundIntent,
+    ): Promise<TransactionInstruction> {
+        const { payer, peer, nativeTokenPriceFeed } = accounts;
+        const { sender, srcMint, usdcRefundToken } = intent;
+
+        let { senderToken, srcTokenProgram } = accounts;
+        if (srcTokenProgram === undefined) {
+            const accInfo = await this.connection().getAccountInfo(srcMint);
+            srcTokenProgram = accInfo.owner;
+        }
+        senderToken ??= splToken.getAssociatedTokenAddressSync(
+            srcMint,
+            sender,
+            false,
+            srcTokenProgram,
+        );
+
+        const stagedOutbound = this.indexedStagedOutboundAddress(
+            sender,
+            BigInt(intent.nonce.toString()),
+        );
+
+        return this.program.methods
+            .stageOutboundWithIntent(intent)
+            .accounts({
+                payer,
+                stagedOutboundCounter: this.stagedOutboundCounterAddress(sender),
+                intentAuthority: this.intentAuthorityAddress(),
+                senderToken,
+                targetPeer: this.outboundTargetComposite({
+                    peer,
+                    chain: intent.targetChain as ChainId,
+                    nativeTokenPriceFeed,
+                }),
+                stagedOutbound,
+                stagedCustodyToken: this.stagedCustodyTokenAddress(stagedOutbound),
+                usdcRefundToken,
+                source: this.outboundSourceComposite(srcMint),
+                instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                srcTokenProgram,
+                tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
     async initiateTransferIx(
         accounts: {
             payer: PublicKey;
@@ -732,10 +1489,11 @@ export class SwapLayerProgram {
         args: {
             cpiInstruction: TransactionInstruction;
             targetChain?: ChainId;
+            collectDust?: boolean;
         },
     ): Promise<TransactionInstruction> {
         const { payer, stagedOutbound } = accounts;
-        const { cpiInstruction } = args;
+        const { cpiInstruction, collectDust } = args;
 
         let {
             stagedCustodyToken,
@@ -773,9 +1531,15 @@ export class SwapLayerProgram {
         const { srcSwapToken, dstSwapToken } = swapAccounts;
         srcTokenProgram ??= swapAccounts.srcTokenProgram;
 
+        // Only the sender (as the payer) can have the residual collected by the source mint's fee
+        // vault.
+        const srcFeeVault = collectDust
+            ? this.usdcFeeVaultComposite(this.feeVaultAddress(srcMint))
+            : null;
+
         const tokenRouter = this.tokenRouterProgram();
 
-        return this.program.methods
+        const ix = await this.program.methods
             .initiateSwapExactIn(cpiInstruction.data)
             .accounts({
                 payer,
@@ -783,6 +1547,8 @@ export class SwapLayerProgram {
                 preparedBy,
                 stagedOutbound,
                 stagedCustodyToken: this.stagedCustodyTokenAddress(stagedOutbound),
+                srcFeeVault: srcFeeVault?.feeVault ?? null,
+                srcFeeVaultCustodyToken: srcFeeVault?.custodyToken ?? null,
                 usdcRefundToken,
                 targetPeer: this.registeredPeerComposite({ chain: targetChain }),
                 preparedOrder,
@@ -804,6 +1570,164 @@ export class SwapLayerProgram {
             })
             .remainingAccounts(cpiInstruction.keys)
             .instruction();
+
+        return this.transferFeeMintWritable(ix, srcMint);
+    }
+
+    async transferUsdcIx(
+        accounts: {
+            payer: PublicKey;
+            preparedOrder: PublicKey;
+            usdcRefundToken?: PublicKey;
+            sender?: PublicKey;
+            senderToken?: PublicKey;
+            peer?: PublicKey;
+            nativeTokenPriceFeed?: PublicKey | null;
+        },
+        args: {
+            amountIn: Uint64;
+            isExactIn: boolean;
+            targetChain: ChainId;
+            recipient: Array<number>;
+            redeemOption: StageOutboundRedeemOption | null;
+            outputToken: OutputToken | null;
+        },
+    ): Promise<TransactionInstruction> {
+        const { payer, preparedOrder, peer, nativeTokenPriceFeed } = accounts;
+        const { amountIn, targetChain, redeemOption, outputToken } = args;
+
+        let { sender, senderToken, usdcRefundToken } = accounts;
+        sender ??= payer;
+        senderToken ??= splToken.getAssociatedTokenAddressSync(this.usdcMint, sender);
+        usdcRefundToken ??= senderToken;
+
+        const tokenRouter = this.tokenRouterProgram();
+
+        return this.program.methods
+            .transferUsdc({
+                amountIn: uint64ToBN(amountIn),
+                isExactIn: args.isExactIn,
+                targetChain,
+                recipient: args.recipient,
+                redeemOption: toRedeemOption(redeemOption),
+                encodedOutputToken:
+                    outputToken === null ? null : Buffer.from(encodeOutputToken(outputToken)),
+            })
+            .accounts({
+                payer,
+                custodian: this.checkedCustodianComposite(),
+                sender,
+                senderToken,
+                outboundCustodyToken: this.outboundCustodyTokenAddress(),
+                usdcRefundToken,
+                targetPeer: this.outboundTargetComposite({
+                    peer,
+                    chain: targetChain,
+                    nativeTokenPriceFeed,
+                }),
+                tokenRouterCustodian: tokenRouter.custodianAddress(),
+                targetRouterEndpoint: tokenRouter
+                    .matchingEngineProgram()
+                    .routerEndpointAddress(targetChain),
+                preparedOrder,
+                preparedCustodyToken: tokenRouter.preparedCustodyTokenAddress(preparedOrder),
+                usdc: this.usdcComposite(),
+                tokenRouterProgram: tokenRouter.ID,
+                tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
+    async swapAndInitiateIx(
+        accounts: {
+            payer: PublicKey;
+            preparedOrder: PublicKey;
+            srcMint: PublicKey;
+            usdcRefundToken: PublicKey;
+            sender?: PublicKey;
+            senderToken?: PublicKey;
+            srcTokenProgram?: PublicKey;
+            peer?: PublicKey;
+            nativeTokenPriceFeed?: PublicKey | null;
+        },
+        args: {
+            cpiInstruction: TransactionInstruction;
+            amountIn: Uint64;
+            targetChain: ChainId;
+            recipient: Array<number>;
+            redeemOption: StageOutboundRedeemOption | null;
+            outputToken: OutputToken | null;
+        },
+    ): Promise<TransactionInstruction> {
+        const { payer, preparedOrder, srcMint, usdcRefundToken, peer, nativeTokenPriceFeed } =
+            accounts;
+        const { cpiInstruction, amountIn, targetChain, redeemOption, outputToken } = args;
+
+        const swapAuthority = this.swapAuthorityAddress(preparedOrder);
+        const swapAccounts = await this.swapAccounts({
+            authority: swapAuthority,
+            sourceMint: srcMint,
+            destinationMint: this.usdcMint,
+            srcTokenProgram: accounts.srcTokenProgram,
+            dstTokenProgram: splToken.TOKEN_PROGRAM_ID,
+        });
+        const { srcSwapToken, dstSwapToken, srcTokenProgram } = swapAccounts;
+
+        let { sender, senderToken } = accounts;
+        sender ??= payer;
+        senderToken ??= splToken.getAssociatedTokenAddressSync(
+            srcMint,
+            sender,
+            false,
+            srcTokenProgram,
+        );
+
+        const tokenRouter = this.tokenRouterProgram();
+
+        const ix = await this.program.methods
+            .swapAndInitiate(
+                {
+                    amountIn: uint64ToBN(amountIn),
+                    targetChain,
+                    recipient: args.recipient,
+                    redeemOption: toRedeemOption(redeemOption),
+                    encodedOutputToken:
+                        outputToken === null ? null : Buffer.from(encodeOutputToken(outputToken)),
+                },
+                cpiInstruction.data,
+            )
+            .accounts({
+                payer,
+                sender,
+                senderToken,
+                usdcRefundToken,
+                targetPeer: this.outboundTargetComposite({
+                    peer,
+                    chain: targetChain,
+                    nativeTokenPriceFeed,
+                }),
+                preparedOrder,
+                swapAuthority,
+                srcSwapToken,
+                dstSwapToken,
+                source: this.outboundSourceComposite(srcMint),
+                usdc: this.usdcComposite(),
+                tokenRouterCustodian: tokenRouter.custodianAddress(),
+                targetRouterEndpoint: tokenRouter
+                    .matchingEngineProgram()
+                    .routerEndpointAddress(targetChain),
+                preparedCustodyToken: tokenRouter.preparedCustodyTokenAddress(preparedOrder),
+                tokenRouterProgram: tokenRouter.ID,
+                associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+                srcTokenProgram,
+                tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(cpiInstruction.keys)
+            .instruction();
+
+        return this.transferFeeMintWritable(ix, srcMint);
     }
 
     async completeTransferRelayIx(
@@ -814,7 +1738,10 @@ export class SwapLayerProgram {
             peer?: PublicKey;
             beneficiary?: PublicKey;
             recipientTokenAccount?: PublicKey;
-            feeRecipientToken?: PublicKey;
+            recipientConfig?: PublicKey | null;
+            feeVault?: PublicKey;
+            relayerFeeToken?: PublicKey | null;
+            registeredRelayer?: PublicKey | null;
         },
         sourceChain?: wormholeSdk.ChainId,
     ) {
@@ -825,17 +1752,21 @@ export class SwapLayerProgram {
             peer,
             recipient,
             recipientTokenAccount,
-            feeRecipientToken,
+            recipientConfig,
+            feeVault,
+            relayerFeeToken,
+            registeredRelayer,
         } = accounts;
 
         beneficiary ??= payer;
         recipientTokenAccount ??= splToken.getAssociatedTokenAddressSync(this.usdcMint, recipient);
-
-        // Need the undefined check to satisfy the type checker.
-        feeRecipientToken ??= await this.fetchCustodian().then((c) => c.feeRecipientToken);
-        if (feeRecipientToken === undefined) {
-            throw new Error("fee recipient token account not found");
-        }
+        recipientConfig ??= null;
+        relayerFeeToken ??= null;
+        registeredRelayer ??= null;
+        const recipientWsolToken =
+            recipientConfig === null
+                ? null
+                : splToken.getAssociatedTokenAddressSync(splToken.NATIVE_MINT, recipient, true);
 
         return this.program.methods
             .completeTransferRelay()
@@ -852,9 +1783,14 @@ export class SwapLayerProgram {
                 completeTokenAccount: this.completeTokenAccountKey(preparedFill),
                 recipient,
                 recipientTokenAccount,
+                recipientConfig,
+                recipientWsolToken,
+                feeVault: this.usdcFeeVaultComposite(feeVault),
+                relayerFeeToken,
+                registeredRelayer,
                 usdc: this.usdcComposite(),
-                feeRecipientToken,
                 tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: SystemProgram.programId,
             })
             .instruction();
@@ -868,18 +1804,29 @@ export class SwapLayerProgram {
             recipient?: PublicKey;
             beneficiary?: PublicKey;
             recipientTokenAccount?: PublicKey;
+            recipientConfig?: PublicKey | null;
         },
         sourceChain?: wormholeSdk.ChainId,
     ) {
-        let { payer, beneficiary, preparedFill, peer, recipient, recipientTokenAccount } = accounts;
+        let {
+            payer,
+            beneficiary,
+            preparedFill,
+            peer,
+            recipient,
+            recipientTokenAccount,
+            recipientConfig,
+        } = accounts;
 
         beneficiary ??= payer;
         recipient ??= payer;
         recipientTokenAccount ??= splToken.getAssociatedTokenAddressSync(this.usdcMint, recipient);
+        recipientConfig ??= null;
 
         return this.program.methods
             .completeTransferDirect()
             .accounts({
+                payer,
                 consumeSwapLayerFill: await this.consumeSwapLayerFillComposite(
                     {
                         preparedFill,
@@ -890,7 +1837,11 @@ export class SwapLayerProgram {
                 ),
                 recipient,
                 recipientTokenAccount,
+                recipientConfig,
+                usdc: this.usdcComposite(),
                 tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
             })
             .instruction();
     }
@@ -932,6 +1883,62 @@ export class SwapLayerProgram {
             .instruction();
     }
 
+    async completeTransferRelayPayloadIx(
+        accounts: {
+            payer: PublicKey;
+            preparedFill: PublicKey;
+            recipient: PublicKey;
+            peer?: PublicKey;
+            beneficiary?: PublicKey;
+            feeVault?: PublicKey;
+            relayerFeeToken?: PublicKey | null;
+            registeredRelayer?: PublicKey | null;
+        },
+        sourceChain?: wormholeSdk.ChainId,
+    ) {
+        let {
+            payer,
+            preparedFill,
+            recipient,
+            peer,
+            beneficiary,
+            feeVault,
+            relayerFeeToken,
+            registeredRelayer,
+        } = accounts;
+
+        beneficiary ??= payer;
+        relayerFeeToken ??= null;
+        registeredRelayer ??= null;
+
+        const stagedInbound = this.stagedInboundAddress(preparedFill);
+        const stagedCustodyToken = this.stagedCustodyTokenAddress(stagedInbound);
+
+        return this.program.methods
+            .completeTransferRelayPayload()
+            .accounts({
+                payer,
+                consumeSwapLayerFill: await this.consumeSwapLayerFillComposite(
+                    {
+                        preparedFill,
+                        beneficiary,
+                        sourcePeer: peer,
+                    },
+                    { sourceChain },
+                ),
+                stagedInbound,
+                stagedCustodyToken,
+                recipient,
+                feeVault: this.usdcFeeVaultComposite(feeVault),
+                relayerFeeToken,
+                registeredRelayer,
+                usdc: this.usdcComposite(),
+                tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .instruction();
+    }
+
     async releaseInboundIx(accounts: {
         stagedInbound: PublicKey;
         recipient: PublicKey;
@@ -951,7 +1958,7 @@ export class SwapLayerProgram {
             mint ??= splToken.unpackAccount(dstToken, accInfo, accInfo.owner).mint;
         }
 
-        return this.program.methods
+        const ix = await this.program.methods
             .releaseInbound()
             .accounts({
                 recipient,
@@ -963,6 +1970,8 @@ export class SwapLayerProgram {
                 tokenProgram,
             })
             .instruction();
+
+        return this.transferFeeMintWritable(ix, mint);
     }
 
     async completeSwapDirectIx(
@@ -974,7 +1983,7 @@ export class SwapLayerProgram {
             recipientToken?: PublicKey;
             beneficiary?: PublicKey;
             dstTokenProgram?: PublicKey;
-            feeRecipientToken?: PublicKey;
+            feeVault?: PublicKey;
         },
         args: {
             cpiInstruction: TransactionInstruction;
@@ -983,10 +1992,9 @@ export class SwapLayerProgram {
         const { payer, preparedFill, recipient } = accounts;
         const { cpiInstruction } = args;
 
-        let { beneficiary, dstMint, dstTokenProgram, recipientToken, feeRecipientToken } = accounts;
+        let { beneficiary, dstMint, dstTokenProgram, recipientToken, feeVault } = accounts;
         beneficiary ??= payer;
         dstMint ??= splToken.NATIVE_MINT;
-        feeRecipientToken ??= await this.fetchCustodian().then((c) => c.feeRecipientToken);
 
         const swapAuthority = this.swapAuthorityAddress(preparedFill);
         const swapAccounts = await this.swapAccounts({
@@ -1005,7 +2013,7 @@ export class SwapLayerProgram {
             dstTokenProgram,
         );
 
-        return this.program.methods
+        const ix = await this.program.methods
             .completeSwapDirect(cpiInstruction.data)
             .accounts({
                 completeSwap: {
@@ -1017,7 +2025,92 @@ export class SwapLayerProgram {
                     authority: swapAuthority,
                     srcSwapToken,
                     dstSwapToken,
-                    feeRecipientToken,
+                    feeVault: this.usdcFeeVaultComposite(feeVault),
+                    usdc: this.usdcComposite(),
+                    dstMint,
+                    associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+                    tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                    dstTokenProgram,
+                    systemProgram: SystemProgram.programId,
+                },
+                recipientToken,
+                recipient,
+                recipientConfig: null,
+            })
+            .remainingAccounts(cpiInstruction.keys)
+            .instruction();
+
+        return this.transferFeeMintWritable(ix, dstMint);
+    }
+
+    async completeSwapOrFallbackIx(
+        accounts: {
+            payer: PublicKey;
+            preparedFill: PublicKey;
+            recipient: PublicKey;
+            dstMint?: PublicKey;
+            recipientToken?: PublicKey;
+            recipientUsdcToken?: PublicKey;
+            recipientConfig?: PublicKey | null;
+            beneficiary?: PublicKey;
+            dstTokenProgram?: PublicKey;
+            feeVault?: PublicKey;
+        },
+        args: {
+            cpiInstruction: TransactionInstruction;
+        },
+    ): Promise<TransactionInstruction> {
+        const { payer, preparedFill, recipient } = accounts;
+        const { cpiInstruction } = args;
+
+        let {
+            beneficiary,
+            dstMint,
+            dstTokenProgram,
+            recipientToken,
+            recipientUsdcToken,
+            recipientConfig,
+            feeVault,
+        } = accounts;
+        beneficiary ??= payer;
+        dstMint ??= splToken.NATIVE_MINT;
+        recipientConfig ??= null;
+
+        const swapAuthority = this.swapAuthorityAddress(preparedFill);
+        const swapAccounts = await this.swapAccounts({
+            authority: swapAuthority,
+            sourceMint: this.usdcMint,
+            destinationMint: dstMint,
+            srcTokenProgram: splToken.TOKEN_PROGRAM_ID,
+            dstTokenProgram,
+        });
+        const { srcSwapToken, dstSwapToken } = swapAccounts;
+        dstTokenProgram ??= swapAccounts.dstTokenProgram;
+        recipientToken ??= splToken.getAssociatedTokenAddressSync(
+            dstMint,
+            recipient,
+            true,
+            dstTokenProgram,
+        );
+        recipientUsdcToken ??= splToken.getAssociatedTokenAddressSync(
+            this.usdcMint,
+            recipient,
+            true,
+        );
+
+        const ix = await this.program.methods
+            .completeSwapOrFallback(cpiInstruction.data)
+            .accounts({
+                completeSwap: {
+                    payer,
+                    consumeSwapLayerFill: await this.consumeSwapLayerFillComposite({
+                        preparedFill,
+                        beneficiary,
+                    }),
+                    authority: swapAuthority,
+                    srcSwapToken,
+                    dstSwapToken,
+                    feeVault: this.usdcFeeVaultComposite(feeVault),
                     usdc: this.usdcComposite(),
                     dstMint,
                     associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -1026,10 +2119,83 @@ export class SwapLayerProgram {
                     systemProgram: SystemProgram.programId,
                 },
                 recipientToken,
+                recipientUsdcToken,
                 recipient,
+                recipientConfig,
             })
             .remainingAccounts(cpiInstruction.keys)
             .instruction();
+
+        return this.transferFeeMintWritable(ix, dstMint);
+    }
+
+    async completeSwapOverrideIx(
+        accounts: {
+            recipient: PublicKey;
+            preparedFill: PublicKey;
+            dstMint?: PublicKey;
+            recipientToken?: PublicKey;
+            recipientConfig?: PublicKey | null;
+            beneficiary?: PublicKey;
+            dstTokenProgram?: PublicKey;
+            feeVault?: PublicKey;
+        },
+        args: {
+            limitAmount: Uint64;
+            cpiInstruction: TransactionInstruction;
+        },
+    ): Promise<TransactionInstruction> {
+        const { recipient, preparedFill } = accounts;
+        const { limitAmount, cpiInstruction } = args;
+
+        let { beneficiary, dstMint, dstTokenProgram, recipientToken, recipientConfig, feeVault } =
+            accounts;
+        beneficiary ??= recipient;
+        dstMint ??= splToken.NATIVE_MINT;
+        recipientConfig ??= null;
+
+        const swapAuthority = this.swapAuthorityAddress(preparedFill);
+        const swapAccounts = await this.swapAccounts({
+            authority: swapAuthority,
+            sourceMint: this.usdcMint,
+            destinationMint: dstMint,
+            srcTokenProgram: splToken.TOKEN_PROGRAM_ID,
+            dstTokenProgram,
+        });
+        const { srcSwapToken, dstSwapToken } = swapAccounts;
+        dstTokenProgram ??= swapAccounts.dstTokenProgram;
+        recipientToken ??= splToken.getAssociatedTokenAddressSync(
+            dstMint,
+            recipient,
+            false,
+            dstTokenProgram,
+        );
+
+        const ix = await this.program.methods
+            .completeSwapOverride(uint64ToBN(limitAmount), cpiInstruction.data)
+            .accounts({
+                recipient,
+                consumeSwapLayerFill: await this.consumeSwapLayerFillComposite({
+                    preparedFill,
+                    beneficiary,
+                }),
+                authority: swapAuthority,
+                srcSwapToken,
+                dstSwapToken,
+                recipientToken,
+                recipientConfig,
+                feeVault: this.usdcFeeVaultComposite(feeVault),
+                usdc: this.usdcComposite(),
+                dstMint,
+                tokenProgram: splToken.TOKEN_PROGRAM_ID,
+                dstTokenProgram,
+                associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+            })
+            .remainingAccounts(cpiInstruction.keys)
+            .instruction();
+
+        return this.transferFeeMintWritable(ix, dstMint);
     }
 
     async completeSwapRelayIx(
@@ -1040,8 +2206,11 @@ export class SwapLayerProgram {
             dstMint?: PublicKey;
             beneficiary?: PublicKey;
             dstTokenProgram?: PublicKey;
-            feeRecipientToken?: PublicKey;
+            feeVault?: PublicKey;
             recipientToken?: PublicKey;
+            recipientConfig?: PublicKey | null;
+            relayerFeeToken?: PublicKey | null;
+            registeredRelayer?: PublicKey | null;
         },
         args: {
             cpiInstruction: TransactionInstruction;
@@ -1050,10 +2219,25 @@ export class SwapLayerProgram {
         const { payer, preparedFill, recipient } = accounts;
         const { cpiInstruction } = args;
 
-        let { beneficiary, dstMint, dstTokenProgram, feeRecipientToken, recipientToken } = accounts;
+        let {
+            beneficiary,
+            dstMint,
+            dstTokenProgram,
+            feeVault,
+            recipientToken,
+            recipientConfig,
+            relayerFeeToken,
+            registeredRelayer,
+        } = accounts;
         beneficiary ??= payer;
         dstMint ??= splToken.NATIVE_MINT;
-        feeRecipientToken ??= await this.fetchCustodian().then((c) => c.feeRecipientToken);
+        recipientConfig ??= null;
+        relayerFeeToken ??= null;
+        registeredRelayer ??= null;
+        const recipientWsolToken =
+            recipientConfig === null
+                ? null
+                : splToken.getAssociatedTokenAddressSync(splToken.NATIVE_MINT, recipient, true);
 
         const swapAuthority = this.swapAuthorityAddress(preparedFill);
         const swapAccounts = await this.swapAccounts({
@@ -1072,7 +2256,7 @@ export class SwapLayerProgram {
             dstTokenProgram,
         );
 
-        return this.program.methods
+        const ix = await this.program.methods
             .completeSwapRelay(cpiInstruction.data)
             .accounts({
                 completeSwap: {
@@ -1084,7 +2268,7 @@ export class SwapLayerProgram {
                     authority: swapAuthority,
                     srcSwapToken,
                     dstSwapToken,
-                    feeRecipientToken,
+                    feeVault: this.usdcFeeVaultComposite(feeVault),
                     usdc: this.usdcComposite(),
                     dstMint,
                     associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -1094,9 +2278,15 @@ export class SwapLayerProgram {
                 },
                 recipientToken,
                 recipient,
+                recipientConfig,
+                recipientWsolToken,
+                relayerFeeToken,
+                registeredRelayer,
             })
             .remainingAccounts(cpiInstruction.keys)
             .instruction();
+
+        return this.transferFeeMintWritable(ix, dstMint);
     }
 
     async completeSwapPayloadIx(
@@ -1106,7 +2296,7 @@ export class SwapLayerProgram {
             dstMint?: PublicKey;
             beneficiary?: PublicKey;
             dstTokenProgram?: PublicKey;
-            feeRecipientToken?: PublicKey;
+            feeVault?: PublicKey;
         },
         args: {
             cpiInstruction: TransactionInstruction;
@@ -1115,7 +2305,7 @@ export class SwapLayerProgram {
         const { payer, preparedFill } = accounts;
         const { cpiInstruction } = args;
 
-        let { beneficiary, dstMint, dstTokenProgram, feeRecipientToken } = accounts;
+        let { beneficiary, dstMint, dstTokenProgram, feeVault } = accounts;
         beneficiary ??= payer;
         dstMint ??= splToken.NATIVE_MINT;
 
@@ -1129,9 +2319,8 @@ export class SwapLayerProgram {
         });
         const { srcSwapToken, dstSwapToken } = swapAccounts;
         dstTokenProgram ??= swapAccounts.dstTokenProgram;
-        feeRecipientToken ??= await this.fetchCustodian().then((c) => c.feeRecipientToken);
 
-        return this.program.methods
+        const ix = await this.program.methods
             .completeSwapPayload(cpiInstruction.data)
             .accounts({
                 payer,
@@ -1142,7 +2331,7 @@ export class SwapLayerProgram {
                 stagedInbound,
                 srcSwapToken,
                 dstSwapToken,
-                feeRecipientToken,
+                feeVault: this.usdcFeeVaultComposite(feeVault),
                 usdc: this.usdcComposite(),
                 dstMint,
                 associatedTokenProgram: splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -1152,6 +2341,8 @@ export class SwapLayerProgram {
             })
             .remainingAccounts(cpiInstruction.keys)
             .instruction();
+
+        return this.transferFeeMintWritable(ix, dstMint);
     }
 
     tokenRouterProgram(): tokenRouterSdk.TokenRouterProgram {
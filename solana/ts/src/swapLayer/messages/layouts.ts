@@ -82,6 +82,15 @@ const redeemModeItem = {
                 { name: "relayingFee", binary: "uint", size: 6, ...forceBigIntConversion },
             ],
         ],
+        [
+            [3, "RelayPayload"],
+            [
+                { name: "gasDropoff", ...gasDropoffItem },
+                { name: "relayingFee", binary: "uint", size: 6, ...forceBigIntConversion },
+                { name: "sender", ...layoutItems.universalAddressItem },
+                { name: "buf", binary: "bytes", lengthSize: 2 },
+            ],
+        ],
     ],
 } as const satisfies NamedLayoutItem;
 
@@ -1,11 +1,18 @@
 import { PublicKey } from "@solana/web3.js";
 
+export type Token2022ExtensionPolicy = {
+    allowTransferHook: boolean;
+    allowConfidentialTransfer: boolean;
+    allowPermanentDelegate: boolean;
+};
+
 export class Custodian {
     owner: PublicKey;
     pendingOwner: PublicKey | null;
     ownerAssistant: PublicKey;
     feeRecipientToken: PublicKey;
     feeUpdater: PublicKey;
+    token2022ExtensionPolicy: Token2022ExtensionPolicy;
 
     constructor(
         owner: PublicKey,
@@ -13,11 +20,13 @@ export class Custodian {
         ownerAssistant: PublicKey,
         feeRecipientToken: PublicKey,
         feeUpdater: PublicKey,
+        token2022ExtensionPolicy: Token2022ExtensionPolicy,
     ) {
         this.owner = owner;
         this.pendingOwner = pendingOwner;
         this.ownerAssistant = ownerAssistant;
         this.feeRecipientToken = feeRecipientToken;
         this.feeUpdater = feeUpdater;
+        this.token2022ExtensionPolicy = token2022ExtensionPolicy;
     }
 }
@@ -0,0 +1,47 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+
+export type FeeVaultSeeds = {
+    mint: PublicKey;
+    bump: number;
+};
+
+export type FeeAccounting = {
+    relayingFees: BN;
+    dust: BN;
+    selfRedemptions: BN;
+    swept: BN;
+};
+
+export class FeeVault {
+    seeds: FeeVaultSeeds;
+    custodyTokenBump: number;
+    destinationToken: PublicKey;
+    accounting: FeeAccounting;
+
+    constructor(
+        seeds: FeeVaultSeeds,
+        custodyTokenBump: number,
+        destinationToken: PublicKey,
+        accounting: FeeAccounting,
+    ) {
+        this.seeds = seeds;
+        this.custodyTokenBump = custodyTokenBump;
+        this.destinationToken = destinationToken;
+        this.accounting = accounting;
+    }
+
+    static address(programId: PublicKey, mint: PublicKey) {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("fee-vault"), mint.toBuffer()],
+            programId,
+        )[0];
+    }
+
+    static custodyTokenAddress(programId: PublicKey, feeVault: PublicKey) {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("fee-vault-custody"), feeVault.toBuffer()],
+            programId,
+        )[0];
+    }
+}
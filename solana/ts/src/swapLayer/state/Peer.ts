@@ -23,6 +23,37 @@ export type SwapTimeLimit = {
     finalizedLimit: number;
 };
 
+export type PriceFeedFallback = { storedPrice: {} } | { reject: {} };
+
+export type PriceFeedConfig = {
+    feed: PublicKey;
+    maxAge: number;
+    maxConfidence: number;
+    fallback: PriceFeedFallback;
+};
+
+export type OutputSwapDeadlinePolicy =
+    | { accept: {} }
+    | { reject: {} }
+    | { defaultMaxAge: { 0: number } };
+
+export type SupportedSwapTypes = {
+    uniswapV3: boolean;
+    traderJoe: boolean;
+    jupiterV6: boolean;
+};
+
+export type PeerParams = {
+    priceFeed: PriceFeedConfig | null;
+    relayingFeeProtocolShare: number;
+    relayerExclusivityWindow: number;
+    outputSwapDeadlinePolicy: OutputSwapDeadlinePolicy;
+    maxOutputSwapDeadlineHorizon: number;
+    supportedSwapTypes: SupportedSwapTypes;
+    maxSwapPathLength: number;
+    supportsRelayPayload: boolean;
+};
+
 export type PeerSeeds = {
     chain: number;
     bump: number;
@@ -32,11 +63,32 @@ export class Peer {
     seeds: PeerSeeds;
     address: Array<number>;
     relayParams: RelayParams;
+    priceFeed: PriceFeedConfig | null;
+    relayingFeeProtocolShare: number;
+    relayerExclusivityWindow: number;
+    outputSwapDeadlinePolicy: OutputSwapDeadlinePolicy;
+    maxOutputSwapDeadlineHorizon: number;
+    supportedSwapTypes: SupportedSwapTypes;
+    maxSwapPathLength: number;
+    supportsRelayPayload: boolean;
 
-    constructor(seeds: PeerSeeds, address: Array<number>, relayParams: RelayParams) {
+    constructor(
+        seeds: PeerSeeds,
+        address: Array<number>,
+        relayParams: RelayParams,
+        peerParams: PeerParams,
+    ) {
         this.seeds = seeds;
         this.address = address;
         this.relayParams = relayParams;
+        this.priceFeed = peerParams.priceFeed;
+        this.relayingFeeProtocolShare = peerParams.relayingFeeProtocolShare;
+        this.relayerExclusivityWindow = peerParams.relayerExclusivityWindow;
+        this.outputSwapDeadlinePolicy = peerParams.outputSwapDeadlinePolicy;
+        this.maxOutputSwapDeadlineHorizon = peerParams.maxOutputSwapDeadlineHorizon;
+        this.supportedSwapTypes = peerParams.supportedSwapTypes;
+        this.maxSwapPathLength = peerParams.maxSwapPathLength;
+        this.supportsRelayPayload = peerParams.supportsRelayPayload;
     }
 
     static address(programId: PublicKey, chain: number) {
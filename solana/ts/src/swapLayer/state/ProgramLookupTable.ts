@@ -0,0 +1,15 @@
+import { PublicKey } from "@solana/web3.js";
+
+export class ProgramLookupTable {
+    bump: number;
+    address: PublicKey;
+
+    constructor(bump: number, address: PublicKey) {
+        this.bump = bump;
+        this.address = address;
+    }
+
+    static address(programId: PublicKey) {
+        return PublicKey.findProgramAddressSync([Buffer.from("lookup-table")], programId)[0];
+    }
+}
@@ -0,0 +1,27 @@
+import { PublicKey } from "@solana/web3.js";
+
+export class RecipientConfig {
+    bump: number;
+    recipient: PublicKey;
+    allowAnyTokenAccount: boolean;
+    gasDropoffAsWsol: boolean;
+
+    constructor(
+        bump: number,
+        recipient: PublicKey,
+        allowAnyTokenAccount: boolean,
+        gasDropoffAsWsol: boolean,
+    ) {
+        this.bump = bump;
+        this.recipient = recipient;
+        this.allowAnyTokenAccount = allowAnyTokenAccount;
+        this.gasDropoffAsWsol = gasDropoffAsWsol;
+    }
+
+    static address(programId: PublicKey, recipient: PublicKey) {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("recipient-config"), recipient.toBuffer()],
+            programId,
+        )[0];
+    }
+}
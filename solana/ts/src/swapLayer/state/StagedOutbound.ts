@@ -10,12 +10,20 @@ export type RedeemOption =
       }
     | {
           payload: [Buffer];
+      }
+    | {
+          relayPayload: {
+              gasDropoff: number;
+              maxRelayerFee: BN;
+              payload: Buffer;
+          };
       };
 
 export type StagedRedeem =
     | { direct: {} }
     | { relay: { gasDropoff: number; relayingFee: BN } }
-    | { payload: { 0: Buffer } };
+    | { payload: { 0: Buffer } }
+    | { relayPayload: { gasDropoff: number; relayingFee: BN; payload: Buffer } };
 
 export type StagedOutboundInfo = {
     custodyTokenBump: number;
@@ -25,6 +33,23 @@ export type StagedOutboundInfo = {
     recipient: Array<number>;
     isExactIn: boolean;
     usdcRefundToken: PublicKey;
+    expiresAt: BN | null;
+    isInitiated: boolean;
+};
+
+export type StageOutboundIntent = {
+    sender: PublicKey;
+    srcMint: PublicKey;
+    amountIn: BN;
+    isExactIn: boolean;
+    targetChain: number;
+    recipient: Array<number>;
+    redeemOption: RedeemOption | null;
+    encodedOutputToken: Buffer | null;
+    usdcRefundToken: PublicKey;
+    nonce: BN;
+    deadline: BN;
+    expiresAt: BN | null;
 };
 
 export class StagedOutbound {
@@ -37,4 +62,13 @@ export class StagedOutbound {
         this.stagedRedeem = stagedRedeem;
         this.encodedOutputToken = encodedOutputToken;
     }
+
+    static address(programId: PublicKey, sender: PublicKey, nonce: bigint) {
+        const encodedNonce = Buffer.alloc(8);
+        encodedNonce.writeBigUInt64BE(nonce);
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("staged-outbound"), sender.toBuffer(), encodedNonce],
+            programId,
+        )[0];
+    }
 }
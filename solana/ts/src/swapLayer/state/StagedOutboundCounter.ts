@@ -0,0 +1,21 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+
+export class StagedOutboundCounter {
+    bump: number;
+    sender: PublicKey;
+    nextNonce: BN;
+
+    constructor(bump: number, sender: PublicKey, nextNonce: BN) {
+        this.bump = bump;
+        this.sender = sender;
+        this.nextNonce = nextNonce;
+    }
+
+    static address(programId: PublicKey, sender: PublicKey) {
+        return PublicKey.findProgramAddressSync(
+            [Buffer.from("staged-outbound-counter"), sender.toBuffer()],
+            programId,
+        )[0];
+    }
+}
@@ -1,4 +1,8 @@
 export * from "./Custodian";
+export * from "./FeeVault";
 export * from "./Peer";
+export * from "./ProgramLookupTable";
+export * from "./RecipientConfig";
 export * from "./StagedInbound";
 export * from "./StagedOutbound";
+export * from "./StagedOutboundCounter";
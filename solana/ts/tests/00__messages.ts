@@ -82,6 +82,26 @@ describe("Swap Layer Messages", () => {
         expect(encodeSwapLayerMessage(decoded)).to.eql(encoded);
     });
 
+    it("USDC Relay Payload", function () {
+        const encoded = encoding.hex.decode(
+            "010000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d03000000000000000f424000000000000000000000000000000000000000000000000000000000000ba5ed0004deadbeef00",
+        );
+
+        const decoded = decodeSwapLayerMessage(encoded);
+        expect(decoded).to.eql({
+            recipient: toUniversal("Ethereum", "0x6ca6d1e2d5347bfab1d91e883f1915560e09129d"),
+            redeemMode: {
+                mode: "RelayPayload",
+                gasDropoff: 0,
+                relayingFee: 1000000n,
+                sender: toUniversal("Ethereum", "0x00000000000000000000000000000000000ba5ed"),
+                buf: Buffer.from("deadbeef", "hex"),
+            },
+            outputToken: { type: "Usdc" },
+        } as SwapLayerMessage);
+        expect(encodeSwapLayerMessage(decoded)).to.eql(encoded);
+    });
+
     it("Jupiter V6 Swap Direct Gas (No Dex)", function () {
         const encoded = encoding.hex.decode(
             "0106a7d51718c774c928566398691d5eb68b5eb8a39b4b6d5c73555b2100000000000100bc614e0000000000000000000000003b9ac9ff1000",
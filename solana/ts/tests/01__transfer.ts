@@ -3,6 +3,7 @@ import * as splToken from "@solana/spl-token";
 import {
     ComputeBudgetProgram,
     Connection,
+    Ed25519Program,
     Keypair,
     LAMPORTS_PER_SOL,
     PublicKey,
@@ -39,12 +40,16 @@ import { assert } from "chai";
 import {
     AddPeerArgs,
     Custodian,
+    FeeVault,
     OutputToken,
     Peer,
+    RecipientConfig,
     RelayParams,
     StagedInbound,
+    StageOutboundIntent,
     StagedOutbound,
     SwapLayerProgram,
+    TEST_PEER_PARAMS,
     TEST_RELAY_PARAMS,
     U32_MAX,
     UpdateRelayParametersArgs,
@@ -54,7 +59,15 @@ import {
     encodeSwapLayerMessage,
     localnet,
 } from "../src/swapLayer";
-import { FEE_UPDATER_KEYPAIR, REGISTERED_PEERS, createLut, tryNativeToUint8Array } from "./helpers";
+import * as jupiterV6 from "../src/jupiterV6";
+import {
+    FEE_UPDATER_KEYPAIR,
+    REGISTERED_PEERS,
+    createAta,
+    createLut,
+    tryNativeToUint8Array,
+    waitUntilTimestamp,
+} from "./helpers";
 
 const SOLANA_CHAIN_ID = toChainId("Solana");
 
@@ -112,10 +125,33 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         ownerAssistant.publicKey,
                         feeRecipientToken,
                         feeUpdater.publicKey,
+                        {
+                            allowTransferHook: false,
+                            allowConfidentialTransfer: false,
+                            allowPermanentDelegate: false,
+                        },
                     ),
                 );
             });
 
+            it("Cannot Migrate Custodian (Already Migrated)", async () => {
+                const ix = await swapLayer.migrateCustodianIx(
+                    { owner: payer.publicKey },
+                    {
+                        allowTransferHook: false,
+                        allowConfidentialTransfer: false,
+                        allowPermanentDelegate: false,
+                    },
+                );
+
+                await expectIxErr(
+                    connection,
+                    [ix],
+                    [payer],
+                    "Error Code: CustodianAlreadyMigrated",
+                );
+            });
+
             before("Set up Token Accounts", async function () {
                 await splToken.getOrCreateAssociatedTokenAccount(
                     connection,
@@ -181,6 +217,176 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
             });
         });
 
+        describe("Fee Vault", () => {
+            it("Cannot Add Fee Vault (Only Owner or Assistant)", async () => {
+                const ix = await swapLayer.addFeeVaultIx({
+                    ownerOrAssistant: payer.publicKey,
+                    payer: payer.publicKey,
+                });
+
+                await expectIxErr(connection, [ix], [payer], "Error Code: OwnerOrAssistantOnly");
+            });
+
+            it("Cannot Add Fee Vault (Destination Required for Non-USDC Mint)", async () => {
+                const ix = await swapLayer.addFeeVaultIx({
+                    ownerOrAssistant: ownerAssistant.publicKey,
+                    payer: payer.publicKey,
+                    mint: splToken.NATIVE_MINT,
+                });
+
+                await expectIxErr(
+                    connection,
+                    [ix],
+                    [payer, ownerAssistant],
+                    "Error Code: InvalidFeeVaultDestination",
+                );
+            });
+
+            it("Add USDC Fee Vault as Owner Assistant", async () => {
+                const ix = await swapLayer.addFeeVaultIx({
+                    ownerOrAssistant: ownerAssistant.publicKey,
+                    payer: payer.publicKey,
+                });
+
+                await expectIxOk(connection, [ix], [payer, ownerAssistant]);
+
+                const feeVault = swapLayer.feeVaultAddress();
+                const feeVaultData = await swapLayer.fetchFeeVault({ address: feeVault });
+                const { bump } = feeVaultData.seeds;
+                const { custodyTokenBump } = feeVaultData;
+                assert.deepEqual(
+                    feeVaultData,
+                    new FeeVault(
+                        { mint: USDC_MINT_ADDRESS, bump },
+                        custodyTokenBump,
+                        feeRecipientToken,
+                        {
+                            relayingFees: uint64ToBN(0),
+                            dust: uint64ToBN(0),
+                            selfRedemptions: uint64ToBN(0),
+                            swept: uint64ToBN(0),
+                        },
+                    ),
+                );
+
+                const { amount, owner } = await splToken.getAccount(
+                    connection,
+                    swapLayer.feeVaultCustodyTokenAddress(feeVault),
+                );
+                assert.equal(amount, 0n);
+                assert.deepEqual(owner, feeVault);
+            });
+
+            it("Cannot Add Fee Vault (Already Exists)", async () => {
+                const ix = await swapLayer.addFeeVaultIx({
+                    ownerOrAssistant: ownerAssistant.publicKey,
+                    payer: payer.publicKey,
+                });
+
+                await expectIxErr(connection, [ix], [payer, ownerAssistant], "already in use");
+            });
+
+            it("Cannot Update Fee Vault Destination (Owner Only)", async () => {
+                const ix = await swapLayer.updateFeeVaultDestinationIx({
+                    owner: ownerAssistant.publicKey,
+                    newDestinationToken: feeRecipientToken,
+                });
+
+                await expectIxErr(connection, [ix], [ownerAssistant], "Error Code: OwnerOnly");
+            });
+
+            it("Update Fee Vault Destination as Owner", async () => {
+                const newDestinationToken = await createTokenAccountForTest();
+
+                const ix = await swapLayer.updateFeeVaultDestinationIx({
+                    owner: payer.publicKey,
+                    newDestinationToken,
+                });
+                await expectIxOk(connection, [ix], [payer]);
+
+                const { destinationToken } = await swapLayer.fetchFeeVault();
+                assert.deepEqual(destinationToken, newDestinationToken);
+
+                // Set it back.
+                const resetIx = await swapLayer.updateFeeVaultDestinationIx({
+                    owner: payer.publicKey,
+                    newDestinationToken: feeRecipientToken,
+                });
+                await expectIxOk(connection, [resetIx], [payer]);
+            });
+        });
+
+        describe("Lookup Table", () => {
+            it("Cannot Create Lookup Table (Only Owner or Assistant)", async () => {
+                const [ix] = await swapLayer.createLookupTableIx(
+                    { ownerOrAssistant: feeUpdater.publicKey },
+                    await connection.getSlot("finalized"),
+                );
+                await expectIxErr(connection, [ix], [feeUpdater], "OwnerOrAssistantOnly");
+            });
+
+            it("Create Lookup Table", async () => {
+                const [ix, lookupTable] = await swapLayer.createLookupTableIx(
+                    { ownerOrAssistant: ownerAssistant.publicKey, payer: payer.publicKey },
+                    await connection.getSlot("finalized"),
+                );
+                await expectIxOk(connection, [ix], [payer, ownerAssistant]);
+
+                const { address } = await swapLayer.fetchProgramLookupTable();
+                assert.isTrue(address.equals(lookupTable));
+
+                const { value: lookupTableAccount } =
+                    await connection.getAddressLookupTable(lookupTable);
+                const { authority, addresses } = lookupTableAccount!.state;
+                assert.isTrue(authority!.equals(swapLayer.programLookupTableAddress()));
+
+                const expectedAddresses = [
+                    swapLayer.custodianAddress(),
+                    USDC_MINT_ADDRESS,
+                    tokenRouter.custodianAddress(),
+                    tokenRouter.ID,
+                    jupiterV6.JUPITER_V6_PROGRAM_ID,
+                    jupiterV6.eventAuthorityAddress(jupiterV6.JUPITER_V6_PROGRAM_ID),
+                    jupiterV6.programAuthorityAddress(0),
+                ];
+                for (const expected of expectedAddresses) {
+                    assert.isTrue(addresses.some((addr) => addr.equals(expected)));
+                }
+            });
+
+            it("Cannot Extend Lookup Table (Only Owner or Assistant)", async () => {
+                const ix = await swapLayer.extendLookupTableIx(
+                    { ownerOrAssistant: feeUpdater.publicKey },
+                    [Keypair.generate().publicKey],
+                );
+                await expectIxErr(connection, [ix], [feeUpdater], "OwnerOrAssistantOnly");
+            });
+
+            it("Cannot Extend Lookup Table (Invalid Lookup Table)", async () => {
+                const ix = await swapLayer.extendLookupTableIx(
+                    { ownerOrAssistant: payer.publicKey, lookupTable: tokenRouterLkupTable },
+                    [Keypair.generate().publicKey],
+                );
+                await expectIxErr(connection, [ix], [payer], "ConstraintAddress");
+            });
+
+            it("Extend Lookup Table", async () => {
+                const newAddress = Keypair.generate().publicKey;
+                const ix = await swapLayer.extendLookupTableIx(
+                    { ownerOrAssistant: payer.publicKey },
+                    [newAddress],
+                );
+                await expectIxOk(connection, [ix], [payer]);
+
+                const { address } = await swapLayer.fetchProgramLookupTable();
+                const { value: lookupTableAccount } =
+                    await connection.getAddressLookupTable(address);
+                assert.isTrue(
+                    lookupTableAccount!.state.addresses.some((addr) => addr.equals(newAddress)),
+                );
+            });
+        });
+
         describe("Peer Registration", () => {
             const startParams: RelayParams = {
                 baseFee: 200000,
@@ -202,13 +408,18 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
             describe("Add", () => {
                 const createAddPeerIx = (opts?: {
                     ownerOrAssistant?: PublicKey;
+                    lookupTable?: PublicKey | null;
+                    targetRouterEndpoint?: PublicKey;
                     args?: AddPeerArgs;
                 }) =>
                     swapLayer.addPeerIx(
                         {
                             ownerOrAssistant: opts?.ownerOrAssistant ?? payer.publicKey,
+                            lookupTable: opts?.lookupTable,
+                            targetRouterEndpoint: opts?.targetRouterEndpoint,
                         },
                         opts?.args ?? {
+                            ...TEST_PEER_PARAMS,
                             chain: foreignChain,
                             address: foreignRecipientAddress,
                             relayParams: startParams,
@@ -230,6 +441,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: 0,
                                     address: foreignSwapLayerAddress,
                                     relayParams: startParams,
@@ -247,6 +459,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: SOLANA_CHAIN_ID,
                                     address: foreignSwapLayerAddress,
                                     relayParams: startParams,
@@ -264,6 +477,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: new Array(32).fill(0),
                                     relayParams: startParams,
@@ -281,6 +495,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: { ...startParams, baseFee: 0 },
@@ -298,6 +513,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: { ...startParams, nativeTokenPrice: new BN(0) },
@@ -315,6 +531,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: { ...startParams, gasDropoffMargin: 4294967295 },
@@ -332,6 +549,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: {
@@ -354,6 +572,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createAddPeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: {
@@ -370,6 +589,37 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                 });
 
+                it("Cannot Add Peer (Lookup Table Accounts Required)", async () => {
+                    await expectIxErr(
+                        connection,
+                        [await createAddPeerIx({ lookupTable: null })],
+                        [payer],
+                        "LookupTableAccountsRequired",
+                    );
+                });
+
+                it("Cannot Add Peer (Invalid Lookup Table)", async () => {
+                    await expectIxErr(
+                        connection,
+                        [await createAddPeerIx({ lookupTable: tokenRouterLkupTable })],
+                        [payer],
+                        "InvalidLookupTable",
+                    );
+                });
+
+                it("Cannot Add Peer (Invalid Target Router Endpoint)", async () => {
+                    await expectIxErr(
+                        connection,
+                        [
+                            await createAddPeerIx({
+                                targetRouterEndpoint: Keypair.generate().publicKey,
+                            }),
+                        ],
+                        [payer],
+                        "target_router_endpoint. Error Code: ConstraintSeeds",
+                    );
+                });
+
                 it("Add Peer As Owner", async () => {
                     await expectIxOk(connection, [await createAddPeerIx()], [payer]);
 
@@ -381,8 +631,22 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                             { chain: foreignChain, bump: seeds.bump },
                             foreignRecipientAddress,
                             startParams,
+                            TEST_PEER_PARAMS,
                         ),
                     );
+
+                    // The peer and its router endpoint are added to the program lookup table.
+                    const { address } = await swapLayer.fetchProgramLookupTable();
+                    const { value: lookupTableAccount } =
+                        await connection.getAddressLookupTable(address);
+                    const { addresses } = lookupTableAccount!.state;
+                    assert.isTrue(
+                        addresses.some((addr) => addr.equals(swapLayer.peerAddress(foreignChain))),
+                    );
+                    const routerEndpoint = tokenRouter
+                        .matchingEngineProgram()
+                        .routerEndpointAddress(foreignChain);
+                    assert.isTrue(addresses.some((addr) => addr.equals(routerEndpoint)));
                 });
             });
 
@@ -393,6 +657,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                             owner: opts?.owner ?? payer.publicKey,
                         },
                         opts?.args ?? {
+                            ...TEST_PEER_PARAMS,
                             chain: foreignChain,
                             address: foreignSwapLayerAddress,
                             relayParams: TEST_RELAY_PARAMS,
@@ -414,6 +679,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createUpdatePeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: new Array(32).fill(0),
                                     relayParams: startParams,
@@ -431,6 +697,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createUpdatePeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: { ...TEST_RELAY_PARAMS, baseFee: 0 },
@@ -448,6 +715,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createUpdatePeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: {
@@ -468,6 +736,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createUpdatePeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: {
@@ -488,6 +757,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createUpdatePeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: {
@@ -510,6 +780,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         [
                             await createUpdatePeerIx({
                                 args: {
+                                    ...TEST_PEER_PARAMS,
                                     chain: foreignChain,
                                     address: foreignSwapLayerAddress,
                                     relayParams: {
@@ -537,8 +808,76 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                             { chain: foreignChain, bump: seeds.bump },
                             foreignSwapLayerAddress,
                             TEST_RELAY_PARAMS,
+                            TEST_PEER_PARAMS,
+                        ),
+                    );
+                });
+            });
+
+            describe("Migrate", () => {
+                // This peer account is loaded with the V0 layout when the validator starts.
+                const arbitrumChain = toChainId("Arbitrum");
+                const arbitrumSwapLayerAddress = Array.from(
+                    tryNativeToUint8Array("0x00000000000000000000000000000000000a4b17", "Arbitrum"),
+                );
+
+                const createMigratePeerIx = (opts?: { owner?: PublicKey; chain?: ChainId }) =>
+                    swapLayer.migratePeerIx(
+                        {
+                            owner: opts?.owner ?? payer.publicKey,
+                        },
+                        {
+                            ...TEST_PEER_PARAMS,
+                            chain: opts?.chain ?? arbitrumChain,
+                            address: arbitrumSwapLayerAddress,
+                            relayParams: TEST_RELAY_PARAMS,
+                        },
+                    );
+
+                it("Cannot Migrate Peer (Owner Only)", async () => {
+                    await expectIxErr(
+                        connection,
+                        [await createMigratePeerIx({ owner: ownerAssistant.publicKey })],
+                        [ownerAssistant],
+                        "Error Code: OwnerOnly",
+                    );
+                });
+
+                it("Cannot Migrate Peer (Already Migrated)", async () => {
+                    await expectIxErr(
+                        connection,
+                        [await createMigratePeerIx({ chain: foreignChain })],
+                        [payer],
+                        "Error Code: PeerAlreadyMigrated",
+                    );
+                });
+
+                it("Migrate Peer As Owner", async () => {
+                    const peer = swapLayer.peerAddress(arbitrumChain);
+                    const { data: dataBefore } = await connection.getAccountInfo(peer);
+                    assert.equal(dataBefore.length, 76);
+
+                    await expectIxOk(connection, [await createMigratePeerIx()], [payer]);
+
+                    const peerData = await swapLayer.fetchPeer(arbitrumChain);
+                    const { seeds } = peerData;
+                    assert.deepEqual(
+                        peerData,
+                        new Peer(
+                            { chain: arbitrumChain, bump: seeds.bump },
+                            arbitrumSwapLayerAddress,
+                            TEST_RELAY_PARAMS,
+                            TEST_PEER_PARAMS,
                         ),
                     );
+
+                    // The peer can no longer be migrated.
+                    await expectIxErr(
+                        connection,
+                        [await createMigratePeerIx()],
+                        [payer],
+                        "Error Code: PeerAlreadyMigrated",
+                    );
                 });
             });
         });
@@ -1240,6 +1579,8 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                                 recipient: foreignRecipientAddress,
                                 isExactIn: true,
                                 usdcRefundToken,
+                                expiresAt: null,
+                                isInitiated: false,
                             },
                             { direct: {} },
                             Buffer.alloc(1),
@@ -1337,6 +1678,8 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                                 recipient: foreignRecipientAddress,
                                 isExactIn: false,
                                 usdcRefundToken: senderToken,
+                                expiresAt: null,
+                                isInitiated: false,
                             },
                             { direct: {} },
                             Buffer.alloc(1),
@@ -1400,6 +1743,8 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                                 recipient: foreignRecipientAddress,
                                 isExactIn: false,
                                 usdcRefundToken: senderToken,
+                                expiresAt: null,
+                                isInitiated: false,
                             },
                             { payload: { "0": Buffer.from("All your base are belong to us.") } },
                             Buffer.alloc(1),
@@ -1454,6 +1799,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
 
                     // Register a new target chain.
                     await addPeerForTest(owner, {
+                        ...TEST_PEER_PARAMS,
                         chain: baseChain,
                         address: foreignSwapLayerAddress,
                         relayParams: TEST_RELAY_PARAMS,
@@ -1787,6 +2133,8 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                                 recipient: foreignRecipientAddress,
                                 isExactIn: true,
                                 usdcRefundToken: senderToken,
+                                expiresAt: null,
+                                isInitiated: false,
                             },
                             {
                                 relay: {
@@ -1860,6 +2208,8 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                                 recipient: foreignRecipientAddress,
                                 isExactIn: false,
                                 usdcRefundToken: senderToken,
+                                expiresAt: null,
+                                isInitiated: false,
                             },
                             {
                                 relay: {
@@ -1925,6 +2275,8 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                                 recipient: foreignRecipientAddress,
                                 isExactIn: false,
                                 usdcRefundToken: senderToken,
+                                expiresAt: null,
+                                isInitiated: false,
                             },
                             { direct: {} },
                             Buffer.alloc(1),
@@ -2002,6 +2354,8 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                                 recipient: foreignRecipientAddress,
                                 isExactIn: false,
                                 usdcRefundToken: senderToken,
+                                expiresAt: null,
+                                isInitiated: false,
                             },
                             { direct: {} },
                             Buffer.from(encodeOutputToken(outputToken)),
@@ -2010,134 +2364,846 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                 });
             });
 
-            describe("Close", function () {
-                it("Cannot Close Staged Outbound (Invalid Sender)", async function () {
-                    const amountIn = 690000n;
-                    const senderToken = splToken.getAssociatedTokenAddressSync(
-                        swapLayer.usdcMint,
-                        payer.publicKey,
-                    );
+            describe("Indexed", function () {
+                const amountIn = 690000n;
+                const senderToken = splToken.getAssociatedTokenAddressSync(
+                    USDC_MINT_ADDRESS,
+                    payer.publicKey,
+                );
 
-                    // Stage outbound with sender.
-                    const { stagedOutbound } = await stageOutboundForTest(
+                it("Cannot Stage Outbound Indexed (Invalid Source Mint)", async function () {
+                    const ix = await swapLayer.stageOutboundIndexedIx(
                         {
                             payer: payer.publicKey,
-                            senderToken,
+                            usdcRefundToken: senderToken,
+                            senderToken: null,
+                            srcMint: USDC_MINT_ADDRESS,
+                        },
+                        {
+                            transferType: "native",
+                            amountIn,
+                            isExactIn: false,
+                            targetChain: foreignChain,
+                            recipient: foreignRecipientAddress,
+                            redeemOption: null,
+                            outputToken: null,
                         },
-                        { amountIn },
                     );
 
-                    const ix = await swapLayer.closeStagedOutboundIx(
-                        { stagedOutbound, senderToken, sender: ownerAssistant.publicKey },
-                        foreignChain,
-                    );
-                    await expectIxErr(
-                        connection,
-                        [ix],
-                        [ownerAssistant],
-                        "sender. Error Code: ConstraintAddress",
-                    );
+                    await expectIxErr(connection, [ix], [payer], "Error Code: InvalidSourceMint");
                 });
 
-                it("Cannot Close Staged Outbound (Invalid Peer)", async function () {
-                    const amountIn = 690000n;
-                    const senderToken = splToken.getAssociatedTokenAddressSync(
-                        swapLayer.usdcMint,
-                        payer.publicKey,
-                    );
+                it("Stage Outbound Indexed USDC (Direct)", async function () {
+                    const nonce = await swapLayer.fetchNextStagedOutboundNonce(payer.publicKey);
 
-                    // Stage outbound with sender.
-                    const { stagedOutbound } = await stageOutboundForTest(
-                        {
-                            payer: payer.publicKey,
+                    for (const expectedNonce of [nonce, nonce + 1n]) {
+                        const ix = await swapLayer.stageOutboundIndexedIx(
+                            {
+                                payer: payer.publicKey,
+                                usdcRefundToken: senderToken,
+                            },
+                            {
+                                transferType: "sender",
+                                amountIn,
+                                isExactIn: false,
+                                targetChain: foreignChain,
+                                recipient: foreignRecipientAddress,
+                                redeemOption: null,
+                                outputToken: null,
+                            },
+                        );
+
+                        const { amount: balanceBefore } = await splToken.getAccount(
+                            connection,
                             senderToken,
-                        },
-                        { amountIn },
-                    );
+                        );
 
-                    const sepoliaChain = toChainId("Sepolia");
-                    await addPeerForTest(owner, {
-                        chain: sepoliaChain,
-                        address: foreignSwapLayerAddress,
-                        relayParams: TEST_RELAY_PARAMS,
-                    });
+                        await expectIxOk(connection, [ix], [payer]);
 
-                    const ix = await swapLayer.closeStagedOutboundIx(
-                        { stagedOutbound, senderToken },
-                        sepoliaChain,
-                    );
-                    await expectIxErr(connection, [ix], [payer], "ConstraintTokenOwner");
+                        const { amount: balanceAfter } = await splToken.getAccount(
+                            connection,
+                            senderToken,
+                        );
+                        assert.equal(balanceBefore - balanceAfter, amountIn);
+
+                        const stagedOutbound = swapLayer.indexedStagedOutboundAddress(
+                            payer.publicKey,
+                            expectedNonce,
+                        );
+                        const stagedOutboundData =
+                            await swapLayer.fetchStagedOutbound(stagedOutbound);
+                        const { info } = stagedOutboundData;
+                        assert.deepEqual(
+                            stagedOutboundData,
+                            new StagedOutbound(
+                                {
+                                    custodyTokenBump: info.custodyTokenBump,
+                                    preparedBy: payer.publicKey,
+                                    sender: payer.publicKey,
+                                    targetChain: foreignChain,
+                                    recipient: foreignRecipientAddress,
+                                    isExactIn: false,
+                                    usdcRefundToken: senderToken,
+                                    expiresAt: null,
+                                    isInitiated: false,
+                                },
+                                { direct: {} },
+                                Buffer.alloc(1),
+                            ),
+                        );
+
+                        const { nextNonce } = await swapLayer.fetchStagedOutboundCounter(
+                            payer.publicKey,
+                        );
+                        assert.equal(nextNonce.toString(), (expectedNonce + 1n).toString());
+                    }
                 });
+            });
 
-                it("Cannot Close Staged Outbound (Invalid PreparedBy)", async function () {
-                    const amountIn = 690000n;
-                    const senderToken = splToken.getAssociatedTokenAddressSync(
-                        swapLayer.usdcMint,
-                        payer.publicKey,
-                    );
+            describe("With Intent", function () {
+                const sender = Keypair.generate();
+                const senderToken = splToken.getAssociatedTokenAddressSync(
+                    USDC_MINT_ADDRESS,
+                    sender.publicKey,
+                );
+                const amountIn = 690000n;
 
-                    // Stage outbound with sender.
-                    const { stagedOutbound } = await stageOutboundForTest(
-                        {
-                            payer: payer.publicKey,
-                            senderToken,
-                        },
-                        { amountIn },
-                    );
+                const createIntent = async (opts?: { nonce?: bigint; deadline?: number }) => {
+                    const currTime = await connection.getBlockTime(await connection.getSlot());
+                    return swapLayer.stageOutboundIntent({
+                        sender: sender.publicKey,
+                        amountIn,
+                        isExactIn: false,
+                        targetChain: foreignChain,
+                        recipient: foreignRecipientAddress,
+                        redeemOption: null,
+                        outputToken: null,
+                        usdcRefundToken: senderToken,
+                        nonce: opts?.nonce ?? 0n,
+                        deadline: new BN(opts?.deadline ?? currTime + 60),
+                        expiresAt: new BN(currTime + 3600),
+                    });
+                };
+
+                const createSignedIntentIxs = async (
+                    intent: StageOutboundIntent,
+                    opts?: { signer?: Keypair },
+                ) => {
+                    const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+                        privateKey: (opts?.signer ?? sender).secretKey,
+                        message: swapLayer.stageOutboundIntentMessage(intent),
+                    });
+                    const ix = await swapLayer.stageOutboundWithIntentIx(
+                        { payer: payer.publicKey },
+                        intent,
+                    );
+
+                    return [ed25519Ix, ix];
+                };
+
+                before("Set up Sender", async function () {
+                    await expectIxOk(
+                        connection,
+                        [
+                            SystemProgram.transfer({
+                                fromPubkey: payer.publicKey,
+                                toPubkey: sender.publicKey,
+                                lamports: LAMPORTS_PER_SOL,
+                            }),
+                            splToken.createAssociatedTokenAccountInstruction(
+                                payer.publicKey,
+                                senderToken,
+                                sender.publicKey,
+                                USDC_MINT_ADDRESS,
+                            ),
+                            splToken.createTransferInstruction(
+                                splToken.getAssociatedTokenAddressSync(
+                                    USDC_MINT_ADDRESS,
+                                    payer.publicKey,
+                                ),
+                                senderToken,
+                                payer.publicKey,
+                                amountIn,
+                            ),
+                            splToken.createApproveInstruction(
+                                senderToken,
+                                swapLayer.intentAuthorityAddress(),
+                                sender.publicKey,
+                                amountIn,
+                            ),
+                        ],
+                        [payer, sender],
+                    );
+                });
+
+                it("Cannot Stage Outbound With Intent (Ed25519 Instruction Required)", async function () {
+                    const [, ix] = await createSignedIntentIxs(await createIntent());
+
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [payer],
+                        "Error Code: Ed25519InstructionRequired",
+                    );
+                });
+
+                it("Cannot Stage Outbound With Intent (Invalid Intent Signer)", async function () {
+                    const ixs = await createSignedIntentIxs(await createIntent(), {
+                        signer: Keypair.generate(),
+                    });
+
+                    await expectIxErr(connection, ixs, [payer], "Error Code: InvalidIntentSigner");
+                });
+
+                it("Cannot Stage Outbound With Intent (Invalid Intent Nonce)", async function () {
+                    const ixs = await createSignedIntentIxs(await createIntent({ nonce: 1n }));
+
+                    await expectIxErr(connection, ixs, [payer], "Error Code: InvalidIntentNonce");
+                });
+
+                it("Cannot Stage Outbound With Intent (Intent Expired)", async function () {
+                    const currTime = await connection.getBlockTime(await connection.getSlot());
+                    const ixs = await createSignedIntentIxs(
+                        await createIntent({ deadline: currTime - 1 }),
+                    );
+
+                    await expectIxErr(connection, ixs, [payer], "Error Code: IntentExpired");
+                });
+
+                it("Stage Outbound With Intent", async function () {
+                    const intent = await createIntent();
+                    const ixs = await createSignedIntentIxs(intent);
+
+                    const { amount: balanceBefore } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                    );
+
+                    await expectIxOk(connection, ixs, [payer]);
+
+                    const { amount: balanceAfter } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                    );
+                    assert.equal(balanceBefore - balanceAfter, amountIn);
+
+                    const stagedOutbound = swapLayer.indexedStagedOutboundAddress(
+                        sender.publicKey,
+                        0n,
+                    );
+                    const stagedOutboundData = await swapLayer.fetchStagedOutbound(stagedOutbound);
+                    const { info } = stagedOutboundData;
+                    assert.deepEqual(
+                        stagedOutboundData,
+                        new StagedOutbound(
+                            {
+                                custodyTokenBump: info.custodyTokenBump,
+                                preparedBy: payer.publicKey,
+                                sender: sender.publicKey,
+                                targetChain: foreignChain,
+                                recipient: foreignRecipientAddress,
+                                isExactIn: false,
+                                usdcRefundToken: senderToken,
+                                expiresAt: intent.expiresAt,
+                                isInitiated: false,
+                            },
+                            { direct: {} },
+                            Buffer.from(encodeOutputToken({ type: "Usdc" })),
+                        ),
+                    );
+
+                    // The staged transfer expires independently of the submission deadline.
+                    assert.isTrue(info.expiresAt.gt(intent.deadline));
+
+                    const { nextNonce } = await swapLayer.fetchStagedOutboundCounter(
+                        sender.publicKey,
+                    );
+                    assert.equal(nextNonce.toString(), "1");
+
+                    // The intent cannot be replayed.
+                    await expectIxErr(connection, ixs, [payer], "Error Code: InvalidIntentNonce");
+                });
+            });
+
+            describe("Amend", function () {
+                const senderToken = splToken.getAssociatedTokenAddressSync(
+                    USDC_MINT_ADDRESS,
+                    payer.publicKey,
+                );
+
+                it("Cannot Amend Staged Outbound (Invalid Prepared By)", async function () {
+                    const { stagedOutbound } = await stageOutboundForTest({
+                        payer: payer.publicKey,
+                        senderToken,
+                    });
+
+                    const ix = await swapLayer.amendStagedOutboundIx(
+                        {
+                            stagedOutbound,
+                            srcMint: USDC_MINT_ADDRESS,
+                            preparedBy: ownerAssistant.publicKey,
+                        },
+                        {
+                            targetChain: foreignChain,
+                            recipient: foreignRecipientAddress,
+                            redeemOption: null,
+                            outputToken: null,
+                        },
+                    );
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [payer, ownerAssistant],
+                        "prepared_by. Error Code: ConstraintAddress",
+                    );
+                });
+
+                it("Cannot Amend Staged Outbound (Prepared By Required)", async function () {
+                    const { stagedOutbound } = await stageOutboundForTest({
+                        payer: payer.publicKey,
+                        senderToken,
+                    });
+
+                    // Amending to a relay requires more space.
+                    const ix = await swapLayer.amendStagedOutboundIx(
+                        { stagedOutbound, srcMint: USDC_MINT_ADDRESS, preparedBy: null },
+                        {
+                            targetChain: foreignChain,
+                            recipient: foreignRecipientAddress,
+                            redeemOption: {
+                                relay: { gasDropoff: 0, maxRelayerFee: 1000000000n },
+                            },
+                            outputToken: null,
+                        },
+                    );
+                    await expectIxErr(connection, [ix], [payer], "Error Code: PreparedByRequired");
+                });
+
+                it("Amend Staged Outbound USDC (Without Prepared By)", async function () {
+                    const { stagedOutbound } = await stageOutboundForTest({
+                        payer: payer.publicKey,
+                        senderToken,
+                    });
+                    const newRecipient = [...foreignRecipientAddress.slice(0, 31), 0x69];
+
+                    // The amended staged outbound fits in the existing account.
+                    const ix = await swapLayer.amendStagedOutboundIx(
+                        { stagedOutbound, srcMint: USDC_MINT_ADDRESS, preparedBy: null },
+                        {
+                            targetChain: foreignChain,
+                            recipient: newRecipient,
+                            redeemOption: null,
+                            outputToken: null,
+                        },
+                    );
+                    await expectIxOk(connection, [ix], [payer]);
+
+                    const { info } = await swapLayer.fetchStagedOutbound(stagedOutbound);
+                    assert.deepEqual(info.recipient, newRecipient);
+                });
+
+                it("Amend Staged Outbound USDC (Direct to Relay and Back)", async function () {
+                    const amountIn = 690000n;
+                    const gasDropoff = 42069;
+                    const { stagedOutbound, stagedCustodyToken } = await stageOutboundForTest(
+                        {
+                            payer: payer.publicKey,
+                            senderToken,
+                        },
+                        { amountIn },
+                    );
+
+                    const { relayParams } = await swapLayer.fetchPeer(foreignChain);
+                    const expectedRelayerFee = calculateRelayerFee(
+                        relayParams,
+                        denormalizeGasDropOff(gasDropoff),
+                        { type: "Usdc" },
+                    );
+
+                    // Amending to a relay transfers the relaying fee from the sender.
+                    {
+                        const ix = await swapLayer.amendStagedOutboundIx(
+                            { stagedOutbound, srcMint: USDC_MINT_ADDRESS },
+                            {
+                                targetChain: foreignChain,
+                                recipient: foreignRecipientAddress,
+                                redeemOption: {
+                                    relay: { gasDropoff, maxRelayerFee: 1000000000n },
+                                },
+                                outputToken: null,
+                            },
+                        );
+
+                        const { amount: balanceBefore } = await splToken.getAccount(
+                            connection,
+                            senderToken,
+                        );
+                        await expectIxOk(connection, [ix], [payer]);
+
+                        const { amount: balanceAfter } = await splToken.getAccount(
+                            connection,
+                            senderToken,
+                        );
+                        assert.equal(balanceBefore - balanceAfter, expectedRelayerFee);
+
+                        const { amount: custodyBalance } = await splToken.getAccount(
+                            connection,
+                            stagedCustodyToken,
+                        );
+                        assert.equal(custodyBalance, amountIn + expectedRelayerFee);
+
+                        const { stagedRedeem } = await swapLayer.fetchStagedOutbound(
+                            stagedOutbound,
+                        );
+                        assert.deepEqual(stagedRedeem, {
+                            relay: {
+                                gasDropoff,
+                                relayingFee: uint64ToBN(expectedRelayerFee),
+                            },
+                        });
+                    }
+
+                    // Amending back to a direct transfer refunds the relaying fee to the sender.
+                    {
+                        const ix = await swapLayer.amendStagedOutboundIx(
+                            { stagedOutbound, srcMint: USDC_MINT_ADDRESS },
+                            {
+                                targetChain: foreignChain,
+                                recipient: foreignRecipientAddress,
+                                redeemOption: null,
+                                outputToken: null,
+                            },
+                        );
+
+                        const { amount: balanceBefore } = await splToken.getAccount(
+                            connection,
+                            senderToken,
+                        );
+                        await expectIxOk(connection, [ix], [payer]);
+
+                        const { amount: balanceAfter } = await splToken.getAccount(
+                            connection,
+                            senderToken,
+                        );
+                        assert.equal(balanceAfter - balanceBefore, expectedRelayerFee);
+
+                        const { amount: custodyBalance } = await splToken.getAccount(
+                            connection,
+                            stagedCustodyToken,
+                        );
+                        assert.equal(custodyBalance, amountIn);
+
+                        const { stagedRedeem } = await swapLayer.fetchStagedOutbound(
+                            stagedOutbound,
+                        );
+                        assert.deepEqual(stagedRedeem, { direct: {} });
+                    }
+                });
+            });
+
+            describe("Close", function () {
+                it("Cannot Close Staged Outbound (Invalid Sender)", async function () {
+                    const amountIn = 690000n;
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    // Stage outbound with sender.
+                    const { stagedOutbound } = await stageOutboundForTest(
+                        {
+                            payer: payer.publicKey,
+                            senderToken,
+                        },
+                        { amountIn },
+                    );
+
+                    const ix = await swapLayer.closeStagedOutboundIx(
+                        { stagedOutbound, senderToken, sender: ownerAssistant.publicKey },
+                        foreignChain,
+                    );
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [ownerAssistant],
+                        "sender. Error Code: ConstraintAddress",
+                    );
+                });
+
+                it("Cannot Close Staged Outbound (Invalid Peer)", async function () {
+                    const amountIn = 690000n;
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    // Stage outbound with sender.
+                    const { stagedOutbound } = await stageOutboundForTest(
+                        {
+                            payer: payer.publicKey,
+                            senderToken,
+                        },
+                        { amountIn },
+                    );
+
+                    const sepoliaChain = toChainId("Sepolia");
+                    await addPeerForTest(owner, {
+                        ...TEST_PEER_PARAMS,
+                        chain: sepoliaChain,
+                        address: foreignSwapLayerAddress,
+                        relayParams: TEST_RELAY_PARAMS,
+                    });
+
+                    const ix = await swapLayer.closeStagedOutboundIx(
+                        { stagedOutbound, senderToken },
+                        sepoliaChain,
+                    );
+                    await expectIxErr(connection, [ix], [payer], "ConstraintTokenOwner");
+                });
+
+                it("Cannot Close Staged Outbound (Invalid PreparedBy)", async function () {
+                    const amountIn = 690000n;
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    // Stage outbound with sender.
+                    const { stagedOutbound } = await stageOutboundForTest(
+                        {
+                            payer: payer.publicKey,
+                            senderToken,
+                        },
+                        { amountIn },
+                    );
 
                     // Pass invalid preparedBy.
                     const ix = await swapLayer.closeStagedOutboundIx(
                         {
                             stagedOutbound,
                             sender: payer.publicKey,
-                            preparedBy: ownerAssistant.publicKey,
+                            preparedBy: ownerAssistant.publicKey,
+                            senderToken,
+                        },
+                        foreignChain,
+                    );
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [payer],
+                        "prepared_by. Error Code: ConstraintAddress",
+                    );
+                });
+
+                it("Close Staged Outbound (USDC)", async function () {
+                    const amountIn = 690000n;
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    // Stage outbound with sender.
+                    const { stagedOutbound, stagedCustodyToken } = await stageOutboundForTest(
+                        {
+                            payer: payer.publicKey,
+                            senderToken,
+                        },
+                        { amountIn },
+                    );
+
+                    const balanceBefore = await connection.getBalance(payer.publicKey).then(BigInt);
+                    const { amount: tokenBalanceBefore } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                    );
+
+                    const ix = await swapLayer.closeStagedOutboundIx(
+                        { stagedOutbound, senderToken },
+                        foreignChain,
+                    );
+                    await expectIxOk(connection, [ix], [payer]);
+
+                    const balanceAfter = await connection.getBalance(payer.publicKey).then(BigInt);
+                    const { amount: tokenBalanceAfter } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                    );
+
+                    assert.isTrue(balanceAfter > balanceBefore);
+                    assert.equal(tokenBalanceAfter, tokenBalanceBefore + amountIn);
+
+                    // Confirm that the staged accounts have been deleted.
+                    {
+                        const accInfo = await connection.getAccountInfo(stagedOutbound);
+                        assert.isNull(accInfo);
+                    }
+                    {
+                        const accInfo = await connection.getAccountInfo(stagedCustodyToken);
+                        assert.isNull(accInfo);
+                    }
+                });
+
+                it("Cannot Migrate Staged Outbound (Already Migrated)", async function () {
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+                    const { stagedOutbound } = await stageOutboundForTest({
+                        payer: payer.publicKey,
+                        senderToken,
+                    });
+
+                    const ix = await swapLayer.migrateStagedOutboundIx({
+                        sender: payer.publicKey,
+                        stagedOutbound,
+                    });
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [payer],
+                        "Error Code: StagedOutboundAlreadyMigrated",
+                    );
+                });
+
+                describe("V0 Layout", function () {
+                    // This staged outbound account (and its custody token account) is loaded with
+                    // the V0 layout when the validator starts.
+                    const stagedOutbound = new PublicKey(
+                        "CwZ7sKmtzpVfx1rpMZYf2HZ387yJBjRTEu5wCRVq7yGX",
+                    );
+                    const stagedCustodyToken = swapLayer.stagedCustodyTokenAddress(stagedOutbound);
+                    const amountIn = 690000n;
+
+                    it("Cannot Migrate Staged Outbound (Invalid Sender)", async function () {
+                        const ix = await swapLayer.migrateStagedOutboundIx({
+                            payer: payer.publicKey,
+                            sender: ownerAssistant.publicKey,
+                            stagedOutbound,
+                        });
+                        await expectIxErr(
+                            connection,
+                            [ix],
+                            [payer, ownerAssistant],
+                            "Error Code: ConstraintAddress",
+                        );
+                    });
+
+                    it("Migrate Staged Outbound", async function () {
+                        const { data: dataBefore } = await connection.getAccountInfo(
+                            stagedOutbound,
+                        );
+                        assert.equal(dataBefore.length, 147);
+
+                        const ix = await swapLayer.migrateStagedOutboundIx({
+                            sender: payer.publicKey,
+                            stagedOutbound,
+                        });
+                        await expectIxOk(connection, [ix], [payer]);
+
+                        const { data: dataAfter } = await connection.getAccountInfo(
+                            stagedOutbound,
+                        );
+                        assert.equal(dataAfter.length, 157);
+
+                        const stagedOutboundData = await swapLayer.fetchStagedOutbound(
+                            stagedOutbound,
+                        );
+                        const { info } = stagedOutboundData;
+                        assert.deepEqual(
+                            stagedOutboundData,
+                            new StagedOutbound(
+                                {
+                                    custodyTokenBump: info.custodyTokenBump,
+                                    preparedBy: payer.publicKey,
+                                    sender: payer.publicKey,
+                                    targetChain: foreignChain,
+                                    recipient: info.recipient,
+                                    isExactIn: false,
+                                    usdcRefundToken: splToken.getAssociatedTokenAddressSync(
+                                        swapLayer.usdcMint,
+                                        payer.publicKey,
+                                    ),
+                                    expiresAt: null,
+                                    isInitiated: false,
+                                },
+                                { direct: {} },
+                                Buffer.alloc(1),
+                            ),
+                        );
+
+                        // The staged outbound can no longer be migrated.
+                        await expectIxErr(
+                            connection,
+                            [ix],
+                            [payer],
+                            "Error Code: StagedOutboundAlreadyMigrated",
+                        );
+                    });
+
+                    it("Close Staged Outbound", async function () {
+                        const senderToken = splToken.getAssociatedTokenAddressSync(
+                            swapLayer.usdcMint,
+                            payer.publicKey,
+                        );
+                        const { amount: tokenBalanceBefore } = await splToken.getAccount(
+                            connection,
+                            senderToken,
+                        );
+
+                        const ix = await swapLayer.closeStagedOutboundIx(
+                            { stagedOutbound, senderToken },
+                            foreignChain,
+                        );
+                        await expectIxOk(connection, [ix], [payer]);
+
+                        const { amount: tokenBalanceAfter } = await splToken.getAccount(
+                            connection,
+                            senderToken,
+                        );
+                        assert.equal(tokenBalanceAfter, tokenBalanceBefore + amountIn);
+
+                        // Confirm that the staged accounts have been deleted.
+                        {
+                            const accInfo = await connection.getAccountInfo(stagedOutbound);
+                            assert.isNull(accInfo);
+                        }
+                        {
+                            const accInfo = await connection.getAccountInfo(stagedCustodyToken);
+                            assert.isNull(accInfo);
+                        }
+                    });
+                });
+
+                it("Close Staged Outbound (Transfer Fee Mint)", async function () {
+                    const amountIn = 10_000_000n;
+                    const { mint: srcMint, senderToken } =
+                        await createTransferFeeMintForTest(amountIn);
+
+                    const { stagedOutbound, stagedCustodyToken, custodyBalance } =
+                        await stageOutboundForTest(
+                            {
+                                payer: payer.publicKey,
+                                senderToken,
+                                srcMint,
+                            },
+                            { amountIn, isExactIn: true },
+                        );
+
+                    // The transfer fee was withheld in the staged custody token account.
+                    assert.equal(custodyBalance, amountIn - amountIn / 100n);
+
+                    const ix = await swapLayer.closeStagedOutboundIx(
+                        { stagedOutbound, senderToken },
+                        foreignChain,
+                    );
+                    await expectIxOk(connection, [ix], [payer]);
+
+                    const { amount: senderBalance } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                        undefined,
+                        splToken.TOKEN_2022_PROGRAM_ID,
+                    );
+                    assert.equal(senderBalance, custodyBalance - custodyBalance / 100n);
+
+                    // The fee withheld in the staged custody token account was harvested to the
+                    // mint before the account was closed.
+                    assert.equal(await getWithheldAmountForTest(srcMint), amountIn / 100n);
+                    assert.isNull(await connection.getAccountInfo(stagedOutbound));
+                    assert.isNull(await connection.getAccountInfo(stagedCustodyToken));
+                });
+            });
+
+            describe("Refund", function () {
+                it("Cannot Refund Staged Outbound (Not Expired)", async function () {
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    const currTime = await connection.getBlockTime(await connection.getSlot());
+                    const { stagedOutbound } = await stageOutboundForTest(
+                        {
+                            payer: payer.publicKey,
                             senderToken,
                         },
+                        { expiresAt: new BN(currTime + 60) },
+                    );
+
+                    const ix = await swapLayer.refundStagedOutboundIx(
+                        { stagedOutbound, srcMint: swapLayer.usdcMint },
                         foreignChain,
                     );
                     await expectIxErr(
                         connection,
                         [ix],
                         [payer],
-                        "prepared_by. Error Code: ConstraintAddress",
+                        "Error Code: StagedOutboundNotExpired",
                     );
                 });
 
-                it("Close Staged Outbound (USDC)", async function () {
+                it("Cannot Refund Staged Outbound (No Expiration)", async function () {
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    const { stagedOutbound } = await stageOutboundForTest({
+                        payer: payer.publicKey,
+                        senderToken,
+                    });
+
+                    const ix = await swapLayer.refundStagedOutboundIx(
+                        { stagedOutbound, srcMint: swapLayer.usdcMint },
+                        foreignChain,
+                    );
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [payer],
+                        "Error Code: StagedOutboundNotExpired",
+                    );
+                });
+
+                it("Refund Staged Outbound (Expired)", async function () {
                     const amountIn = 690000n;
                     const senderToken = splToken.getAssociatedTokenAddressSync(
                         swapLayer.usdcMint,
                         payer.publicKey,
                     );
 
-                    // Stage outbound with sender.
+                    const currTime = await connection.getBlockTime(await connection.getSlot());
+                    const expiresAt = currTime + 2;
                     const { stagedOutbound, stagedCustodyToken } = await stageOutboundForTest(
                         {
                             payer: payer.publicKey,
                             senderToken,
                         },
-                        { amountIn },
+                        { amountIn, expiresAt: new BN(expiresAt) },
                     );
 
-                    const balanceBefore = await connection.getBalance(payer.publicKey).then(BigInt);
+                    const { info } = await swapLayer.fetchStagedOutbound(stagedOutbound);
+                    assert.isFalse(info.isInitiated);
+                    assert.equal(info.expiresAt.toNumber(), expiresAt);
+
+                    await waitUntilTimestamp(connection, expiresAt);
+
                     const { amount: tokenBalanceBefore } = await splToken.getAccount(
                         connection,
                         senderToken,
                     );
 
-                    const ix = await swapLayer.closeStagedOutboundIx(
-                        { stagedOutbound, senderToken },
+                    // Anyone can refund an expired staged outbound.
+                    const ix = await swapLayer.refundStagedOutboundIx(
+                        { stagedOutbound, srcMint: swapLayer.usdcMint },
                         foreignChain,
                     );
-                    await expectIxOk(connection, [ix], [payer]);
+                    await expectIxOk(connection, [ix], [ownerAssistant]);
 
-                    const balanceAfter = await connection.getBalance(payer.publicKey).then(BigInt);
                     const { amount: tokenBalanceAfter } = await splToken.getAccount(
                         connection,
                         senderToken,
                     );
-
-                    assert.isTrue(balanceAfter > balanceBefore);
                     assert.equal(tokenBalanceAfter, tokenBalanceBefore + amountIn);
 
                     // Confirm that the staged accounts have been deleted.
@@ -2150,6 +3216,46 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         assert.isNull(accInfo);
                     }
                 });
+
+                it("Refund Staged Outbound (Transfer Fee Mint)", async function () {
+                    const amountIn = 10_000_000n;
+                    const { mint: srcMint, senderToken } =
+                        await createTransferFeeMintForTest(amountIn);
+
+                    const currTime = await connection.getBlockTime(await connection.getSlot());
+                    const expiresAt = currTime + 2;
+                    const { stagedOutbound, stagedCustodyToken, custodyBalance } =
+                        await stageOutboundForTest(
+                            {
+                                payer: payer.publicKey,
+                                senderToken,
+                                srcMint,
+                            },
+                            { amountIn, isExactIn: true, expiresAt: new BN(expiresAt) },
+                        );
+
+                    await waitUntilTimestamp(connection, expiresAt);
+
+                    const ix = await swapLayer.refundStagedOutboundIx(
+                        { stagedOutbound, srcMint },
+                        foreignChain,
+                    );
+                    await expectIxOk(connection, [ix], [ownerAssistant]);
+
+                    const { amount: senderBalance } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                        undefined,
+                        splToken.TOKEN_2022_PROGRAM_ID,
+                    );
+                    assert.equal(senderBalance, custodyBalance - custodyBalance / 100n);
+
+                    // The fee withheld in the staged custody token account was harvested to the
+                    // mint before the account was closed.
+                    assert.equal(await getWithheldAmountForTest(srcMint), amountIn / 100n);
+                    assert.isNull(await connection.getAccountInfo(stagedOutbound));
+                    assert.isNull(await connection.getAccountInfo(stagedCustodyToken));
+                });
             });
         });
 
@@ -2252,6 +3358,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
 
                     // Need to register a peer for holesky to trigger the invalid peer error.
                     await addPeerForTest(owner, {
+                        ...TEST_PEER_PARAMS,
                         chain: holeskyChain,
                         address: foreignSwapLayerAddress,
                         relayParams: TEST_RELAY_PARAMS,
@@ -2399,7 +3506,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
             });
 
             describe("Inbound", function () {
-                it("Cannot Complete Transfer (Invalid Fee Recipient)", async function () {
+                it("Cannot Complete Transfer (Invalid Fee Vault)", async function () {
                     const result = await createAndRedeemCctpFillForTest(
                         testCctpNonce++,
                         foreignChain,
@@ -2419,20 +3526,13 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     const { vaa } = result!;
                     const preparedFill = tokenRouter.preparedFillAddress(vaa);
 
-                    // Pass the payer token to as the fee recipient token.
-                    const payerToken = await splToken.getOrCreateAssociatedTokenAccount(
-                        connection,
-                        payer,
-                        USDC_MINT_ADDRESS,
-                        payer.publicKey,
-                    );
-
+                    // Pass the fee vault of another mint instead.
                     const transferIx = await swapLayer.completeTransferRelayIx(
                         {
                             payer: payer.publicKey,
                             preparedFill,
                             recipient: payer.publicKey,
-                            feeRecipientToken: payerToken.address,
+                            feeVault: swapLayer.feeVaultAddress(splToken.NATIVE_MINT),
                         },
                         foreignChain,
                     );
@@ -2441,7 +3541,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                         connection,
                         [transferIx],
                         [payer],
-                        "fee_recipient_token. Error Code: ConstraintAddress",
+                        "fee_vault. Error Code: AccountNotInitialized",
                     );
                 });
 
@@ -2679,7 +3779,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     // Balance check.
                     const recipientBefore = await getUsdcAtaBalance(connection, payer.publicKey);
                     const payerLamportBefore = await connection.getBalance(payer.publicKey);
-                    const feeRecipientBefore = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultBefore = await getFeeVaultBalanceForTest();
 
                     const transferIx = await swapLayer.completeTransferRelayIx(
                         {
@@ -2696,11 +3796,11 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     // Balance check.
                     const recipientAfter = await getUsdcAtaBalance(connection, payer.publicKey);
                     const payerLamportAfter = await connection.getBalance(payer.publicKey);
-                    const feeRecipientAfter = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultAfter = await getFeeVaultBalanceForTest();
 
                     assert.equal(recipientAfter, recipientBefore + message.deposit!.message.amount);
                     assert.isBelow(payerLamportAfter, payerLamportBefore);
-                    assert.equal(feeRecipientAfter, feeRecipientBefore);
+                    assert.equal(feeVaultAfter, feeVaultBefore);
                 });
 
                 it("Complete Transfer With Gas Dropoff", async function () {
@@ -2738,7 +3838,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                     const recipientLamportBefore = await connection.getBalance(recipient.publicKey);
                     const payerLamportBefore = await connection.getBalance(payer.publicKey);
-                    const feeRecipientBefore = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultBefore = await getFeeVaultBalanceForTest();
 
                     const transferIx = await swapLayer.completeTransferRelayIx(
                         {
@@ -2756,7 +3856,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     const recipientAfter = await getUsdcAtaBalance(connection, recipient.publicKey);
                     const recipientLamportAfter = await connection.getBalance(recipient.publicKey);
                     const payerLamportAfter = await connection.getBalance(payer.publicKey);
-                    const feeRecipientAfter = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultAfter = await getFeeVaultBalanceForTest();
 
                     assert.equal(
                         recipientAfter - recipientBefore,
@@ -2764,7 +3864,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                     assert.equal(recipientLamportAfter - recipientLamportBefore, gasAmountDenorm);
                     assert.isBelow(payerLamportAfter, payerLamportBefore - gasAmountDenorm);
-                    assert.equal(feeRecipientAfter, feeRecipientBefore + relayerFee);
+                    assert.equal(feeVaultAfter, feeVaultBefore + relayerFee);
                 });
 
                 it("Complete Transfer Without Gas Dropoff", async function () {
@@ -2802,7 +3902,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                     const recipientLamportBefore = await connection.getBalance(recipient.publicKey);
                     const payerLamportBefore = await connection.getBalance(payer.publicKey);
-                    const feeRecipientBefore = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultBefore = await getFeeVaultBalanceForTest();
 
                     const transferIx = await swapLayer.completeTransferRelayIx(
                         {
@@ -2820,15 +3920,132 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     const recipientAfter = await getUsdcAtaBalance(connection, recipient.publicKey);
                     const recipientLamportAfter = await connection.getBalance(recipient.publicKey);
                     const payerLamportAfter = await connection.getBalance(payer.publicKey);
-                    const feeRecipientAfter = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultAfter = await getFeeVaultBalanceForTest();
 
                     assert.equal(
                         recipientAfter - recipientBefore,
                         message.deposit!.message.amount - relayerFee,
                     );
-                    assert.equal(recipientLamportAfter - recipientLamportBefore, gasAmount);
-                    assert.isBelow(payerLamportAfter, payerLamportBefore - gasAmount);
-                    assert.equal(feeRecipientAfter, feeRecipientBefore + relayerFee);
+                    assert.equal(recipientLamportAfter - recipientLamportBefore, gasAmount);
+                    assert.isBelow(payerLamportAfter, payerLamportBefore - gasAmount);
+                    assert.equal(feeVaultAfter, feeVaultBefore + relayerFee);
+                });
+
+                it("Complete Transfer (Recipient ATA Does Not Exist)", async function () {
+                    const relayerFee = 1000000n;
+                    const newRecipient = Keypair.generate().publicKey;
+
+                    const result = await createAndRedeemCctpFillForTest(
+                        testCctpNonce++,
+                        foreignChain,
+                        foreignTokenRouterAddress,
+                        foreignSwapLayerAddress,
+                        wormholeSequence,
+                        encodeSwapLayerMessage({
+                            recipient: new UniversalAddress(newRecipient.toString(), "base58"),
+                            redeemMode: {
+                                mode: "Relay",
+                                gasDropoff: 0,
+                                relayingFee: relayerFee,
+                            },
+                            outputToken: { type: "Usdc" },
+                        }),
+                    );
+                    const { vaa, message } = result!;
+
+                    const preparedFill = tokenRouter.preparedFillAddress(vaa);
+
+                    const recipientToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        newRecipient,
+                    );
+                    assert.isNull(await connection.getAccountInfo(recipientToken));
+
+                    const ataRent = await connection
+                        .getMinimumBalanceForRentExemption(splToken.ACCOUNT_SIZE)
+                        .then(BigInt);
+                    const payerLamportBefore = await connection
+                        .getBalance(payer.publicKey)
+                        .then(BigInt);
+
+                    const transferIx = await swapLayer.completeTransferRelayIx(
+                        {
+                            payer: payer.publicKey,
+                            beneficiary: Keypair.generate().publicKey,
+                            preparedFill,
+                            recipient: newRecipient,
+                        },
+                        foreignChain,
+                    );
+
+                    await expectIxOk(connection, [transferIx], [payer]);
+
+                    // The payer created the recipient's ATA.
+                    const { lamports: recipientTokenLamports } =
+                        await connection.getAccountInfo(recipientToken);
+                    assert.equal(BigInt(recipientTokenLamports), ataRent);
+
+                    const payerLamportAfter = await connection
+                        .getBalance(payer.publicKey)
+                        .then(BigInt);
+                    assert.isTrue(payerLamportBefore - payerLamportAfter >= ataRent);
+
+                    const recipientAfter = await getUsdcAtaBalance(connection, newRecipient);
+                    assert.equal(recipientAfter, message.deposit!.message.amount - relayerFee);
+                });
+
+                it("Complete Transfer (Recipient ATA Exists)", async function () {
+                    const relayerFee = 1000000n;
+                    const newRecipient = Keypair.generate().publicKey;
+                    await createAta(connection, payer, swapLayer.usdcMint, newRecipient);
+
+                    const result = await createAndRedeemCctpFillForTest(
+                        testCctpNonce++,
+                        foreignChain,
+                        foreignTokenRouterAddress,
+                        foreignSwapLayerAddress,
+                        wormholeSequence,
+                        encodeSwapLayerMessage({
+                            recipient: new UniversalAddress(newRecipient.toString(), "base58"),
+                            redeemMode: {
+                                mode: "Relay",
+                                gasDropoff: 0,
+                                relayingFee: relayerFee,
+                            },
+                            outputToken: { type: "Usdc" },
+                        }),
+                    );
+                    const { vaa, message } = result!;
+
+                    const preparedFill = tokenRouter.preparedFillAddress(vaa);
+
+                    const ataRent = await connection
+                        .getMinimumBalanceForRentExemption(splToken.ACCOUNT_SIZE)
+                        .then(BigInt);
+                    const payerLamportBefore = await connection
+                        .getBalance(payer.publicKey)
+                        .then(BigInt);
+
+                    const transferIx = await swapLayer.completeTransferRelayIx(
+                        {
+                            payer: payer.publicKey,
+                            beneficiary: Keypair.generate().publicKey,
+                            preparedFill,
+                            recipient: newRecipient,
+                        },
+                        foreignChain,
+                    );
+
+                    await expectIxOk(connection, [transferIx], [payer]);
+
+                    // The payer did not pay for the recipient's ATA.
+                    const payerLamportAfter = await connection
+                        .getBalance(payer.publicKey)
+                        .then(BigInt);
+                    assert.isTrue(payerLamportBefore - payerLamportAfter < ataRent);
+
+                    const recipientAfter = await getUsdcAtaBalance(connection, newRecipient);
+                    assert.equal(recipientAfter, message.deposit!.message.amount - relayerFee);
                 });
 
                 it("Complete Transfer With Gas Dropoff (Failed Encoded Swap)", async function () {
@@ -2881,7 +4098,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                     const recipientLamportBefore = await connection.getBalance(recipient.publicKey);
                     const payerLamportBefore = await connection.getBalance(payer.publicKey);
-                    const feeRecipientBefore = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultBefore = await getFeeVaultBalanceForTest();
 
                     const transferIx = await swapLayer.completeTransferRelayIx(
                         {
@@ -2899,7 +4116,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     const recipientAfter = await getUsdcAtaBalance(connection, recipient.publicKey);
                     const recipientLamportAfter = await connection.getBalance(recipient.publicKey);
                     const payerLamportAfter = await connection.getBalance(payer.publicKey);
-                    const feeRecipientAfter = await getUsdcAtaBalance(connection, feeRecipient);
+                    const feeVaultAfter = await getFeeVaultBalanceForTest();
 
                     assert.equal(
                         recipientAfter - recipientBefore,
@@ -2907,7 +4124,124 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                     assert.equal(recipientLamportAfter - recipientLamportBefore, gasAmountDenorm);
                     assert.isBelow(payerLamportAfter, payerLamportBefore - gasAmountDenorm);
-                    assert.equal(feeRecipientAfter, feeRecipientBefore + relayerFee);
+                    assert.equal(feeVaultAfter, feeVaultBefore + relayerFee);
+                });
+
+                it("Complete Transfer With Gas Dropoff (As WSOL)", async function () {
+                    const relayerFee = 1000000n;
+                    const gasAmountDenorm = 690000000;
+                    const newRecipient = Keypair.generate();
+
+                    // The WSOL account is created when the recipient opts in.
+                    const configIx = await swapLayer.setRecipientConfigIx(
+                        { payer: payer.publicKey, recipient: newRecipient.publicKey },
+                        { allowAnyTokenAccount: false, gasDropoffAsWsol: true },
+                    );
+                    await expectIxOk(connection, [configIx], [payer, newRecipient]);
+
+                    const recipientWsolToken = splToken.getAssociatedTokenAddressSync(
+                        splToken.NATIVE_MINT,
+                        newRecipient.publicKey,
+                    );
+                    const { amount: wsolBefore } = await splToken.getAccount(
+                        connection,
+                        recipientWsolToken,
+                    );
+                    assert.equal(wsolBefore, 0n);
+
+                    const result = await createAndRedeemCctpFillForTest(
+                        testCctpNonce++,
+                        foreignChain,
+                        foreignTokenRouterAddress,
+                        foreignSwapLayerAddress,
+                        wormholeSequence,
+                        encodeSwapLayerMessage({
+                            recipient: new UniversalAddress(
+                                newRecipient.publicKey.toString(),
+                                "base58",
+                            ),
+                            redeemMode: {
+                                mode: "Relay",
+                                gasDropoff: gasAmountDenorm / 1000,
+                                relayingFee: relayerFee,
+                            },
+                            outputToken: { type: "Usdc" },
+                        }),
+                    );
+                    const { vaa } = result!;
+
+                    const preparedFill = tokenRouter.preparedFillAddress(vaa);
+
+                    const transferIx = await swapLayer.completeTransferRelayIx(
+                        {
+                            payer: payer.publicKey,
+                            beneficiary: Keypair.generate().publicKey,
+                            preparedFill,
+                            recipient: newRecipient.publicKey,
+                            recipientConfig: swapLayer.recipientConfigAddress(
+                                newRecipient.publicKey,
+                            ),
+                        },
+                        foreignChain,
+                    );
+
+                    await expectIxOk(connection, [transferIx], [payer]);
+
+                    // The full gas dropoff is wrapped.
+                    const { amount: wsolAfter } = await splToken.getAccount(
+                        connection,
+                        recipientWsolToken,
+                    );
+                    assert.equal(wsolAfter, BigInt(gasAmountDenorm));
+                });
+            });
+
+            describe("Sweep Fees", function () {
+                it("Cannot Sweep Fees (Only Owner or Assistant)", async function () {
+                    const ix = await swapLayer.sweepFeesIx({ ownerOrAssistant: payer.publicKey });
+
+                    await expectIxErr(connection, [ix], [payer], "Error Code: OwnerOrAssistantOnly");
+                });
+
+                it("Cannot Sweep Fees (Invalid Destination)", async function () {
+                    const ix = await swapLayer.sweepFeesIx({
+                        ownerOrAssistant: ownerAssistant.publicKey,
+                        destinationToken: await createTokenAccountForTest(),
+                    });
+
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [ownerAssistant],
+                        "destination_token. Error Code: ConstraintAddress",
+                    );
+                });
+
+                it("Sweep Fees as Owner Assistant", async function () {
+                    const { accounting: accountingBefore } = await swapLayer.fetchFeeVault();
+                    const feeVaultBefore = await getFeeVaultBalanceForTest();
+                    assert.isAbove(Number(feeVaultBefore), 0);
+
+                    const feeRecipientBefore = await getUsdcAtaBalance(connection, feeRecipient);
+
+                    const ix = await swapLayer.sweepFeesIx({
+                        ownerOrAssistant: ownerAssistant.publicKey,
+                    });
+                    await expectIxOk(connection, [ix], [ownerAssistant]);
+
+                    const feeRecipientAfter = await getUsdcAtaBalance(connection, feeRecipient);
+                    assert.equal(feeRecipientAfter, feeRecipientBefore + feeVaultBefore);
+                    assert.equal(await getFeeVaultBalanceForTest(), 0n);
+
+                    const { accounting: accountingAfter } = await swapLayer.fetchFeeVault();
+                    assert.equal(
+                        accountingAfter.swept.sub(accountingBefore.swept).toString(),
+                        feeVaultBefore.toString(),
+                    );
+                    assert.equal(
+                        accountingAfter.relayingFees.toString(),
+                        accountingBefore.relayingFees.toString(),
+                    );
                 });
             });
         });
@@ -2989,6 +4323,115 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                     assert.equal(preparedCustodyTokenBalance, amountIn);
                 });
+
+                it("Cannot Transfer USDC (Invalid Recipient)", async function () {
+                    const preparedOrderSigner = Keypair.generate();
+
+                    const ix = await swapLayer.transferUsdcIx(
+                        {
+                            payer: payer.publicKey,
+                            preparedOrder: preparedOrderSigner.publicKey,
+                        },
+                        {
+                            amountIn: 690000n,
+                            isExactIn: false,
+                            targetChain: foreignChain,
+                            recipient: new Array(32).fill(0),
+                            redeemOption: null,
+                            outputToken: null,
+                        },
+                    );
+
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [payer, preparedOrderSigner],
+                        "Error Code: InvalidRecipient",
+                    );
+                });
+
+                it("Transfer USDC", async function () {
+                    const amountIn = 6900000000n;
+                    const outputToken: OutputToken = { type: "Usdc" };
+                    const preparedOrderSigner = Keypair.generate();
+                    const preparedOrder = preparedOrderSigner.publicKey;
+
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    const ix = await swapLayer.transferUsdcIx(
+                        {
+                            payer: payer.publicKey,
+                            preparedOrder,
+                        },
+                        {
+                            amountIn,
+                            isExactIn: false,
+                            targetChain: foreignChain,
+                            recipient: foreignRecipientAddress,
+                            redeemOption: null,
+                            outputToken,
+                        },
+                    );
+
+                    const { amount: balanceBefore } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                    );
+
+                    await expectIxOk(connection, [ix], [payer, preparedOrderSigner]);
+
+                    const { amount: balanceAfter } = await splToken.getAccount(
+                        connection,
+                        senderToken,
+                    );
+                    assert.equal(balanceBefore - balanceAfter, amountIn);
+
+                    // Verify the relevant information in the prepared order.
+                    const preparedOrderData = await tokenRouter.fetchPreparedOrder(preparedOrder);
+
+                    const {
+                        info: { preparedCustodyTokenBump },
+                    } = preparedOrderData;
+
+                    assert.deepEqual(
+                        preparedOrderData,
+                        new PreparedOrder(
+                            {
+                                orderSender: swapLayer.custodianAddress(),
+                                preparedBy: payer.publicKey,
+                                orderType: {
+                                    market: {
+                                        minAmountOut: null,
+                                    },
+                                },
+                                srcToken: swapLayer.outboundCustodyTokenAddress(),
+                                refundToken: senderToken,
+                                targetChain: foreignChain,
+                                redeemer: foreignSwapLayerAddress,
+                                preparedCustodyTokenBump,
+                            },
+                            Buffer.from(
+                                encodeSwapLayerMessage({
+                                    recipient: new UniversalAddress(
+                                        Uint8Array.from(foreignRecipientAddress),
+                                    ),
+                                    redeemMode: { mode: "Direct" },
+                                    outputToken,
+                                }),
+                            ),
+                        ),
+                    );
+
+                    // Verify the prepared custody token balance.
+                    const { amount: preparedCustodyTokenBalance } = await splToken.getAccount(
+                        connection,
+                        tokenRouter.preparedCustodyTokenAddress(preparedOrder),
+                    );
+                    assert.equal(preparedCustodyTokenBalance, amountIn);
+                });
             });
 
             describe("Inbound", function () {
@@ -3159,6 +4602,60 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     await expectIxErr(connection, [transferIx], [payer], "InvalidOutputToken");
                 });
 
+                it("Cannot Complete Transfer (Recipient ATA Owner Changed)", async function () {
+                    const newRecipient = Keypair.generate();
+                    const recipientToken = await createAta(
+                        connection,
+                        payer,
+                        swapLayer.usdcMint,
+                        newRecipient.publicKey,
+                    );
+
+                    // Reassign the recipient's ATA to another owner.
+                    await expectIxOk(
+                        connection,
+                        [
+                            splToken.createSetAuthorityInstruction(
+                                recipientToken,
+                                newRecipient.publicKey,
+                                splToken.AuthorityType.AccountOwner,
+                                payer.publicKey,
+                            ),
+                        ],
+                        [payer, newRecipient],
+                    );
+
+                    const result = await createAndRedeemCctpFillForTest(
+                        testCctpNonce++,
+                        foreignChain,
+                        foreignTokenRouterAddress,
+                        foreignSwapLayerAddress,
+                        wormholeSequence,
+                        encodeSwapLayerMessage({
+                            recipient: new UniversalAddress(
+                                newRecipient.publicKey.toString(),
+                                "base58",
+                            ),
+                            redeemMode: { mode: "Direct" },
+                            outputToken: { type: "Usdc" },
+                        }),
+                    );
+                    const { vaa } = result!;
+
+                    const preparedFill = tokenRouter.preparedFillAddress(vaa);
+
+                    const transferIx = await swapLayer.completeTransferDirectIx(
+                        {
+                            payer: payer.publicKey,
+                            preparedFill,
+                            recipient: newRecipient.publicKey,
+                        },
+                        foreignChain,
+                    );
+
+                    await expectIxErr(connection, [transferIx], [payer], "ConstraintTokenOwner");
+                });
+
                 it("Complete Transfer (Recipient Not Payer)", async function () {
                     const result = await createAndRedeemCctpFillForTest(
                         testCctpNonce++,
@@ -3222,30 +4719,138 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                     );
                     const { vaa, message } = result!;
 
-                    const preparedFill = tokenRouter.preparedFillAddress(vaa);
-                    const beneficiary = Keypair.generate();
+                    const preparedFill = tokenRouter.preparedFillAddress(vaa);
+                    const beneficiary = Keypair.generate();
+
+                    // Balance check.
+                    const recipientBefore = await getUsdcAtaBalance(connection, payer.publicKey);
+                    const beneficiaryBefore = await connection.getBalance(beneficiary.publicKey);
+
+                    const transferIx = await swapLayer.completeTransferDirectIx(
+                        {
+                            payer: payer.publicKey,
+                            beneficiary: beneficiary.publicKey,
+                            preparedFill,
+                        },
+                        foreignChain,
+                    );
+
+                    await expectIxOk(connection, [transferIx], [payer]);
+
+                    // Balance check.
+                    const recipientAfter = await getUsdcAtaBalance(connection, payer.publicKey);
+                    const beneficiaryAfter = await connection.getBalance(beneficiary.publicKey);
+
+                    assert.equal(recipientAfter, recipientBefore + message.deposit!.message.amount);
+                    assert.isAbove(beneficiaryAfter, beneficiaryBefore);
+                });
+
+                describe("Recipient Config", function () {
+                    const configRecipient = Keypair.generate();
+                    let recipientToken: PublicKey;
+
+                    before("Create Non-ATA Token Account", async function () {
+                        recipientToken = await splToken.createAccount(
+                            connection,
+                            payer,
+                            swapLayer.usdcMint,
+                            configRecipient.publicKey,
+                            Keypair.generate(),
+                        );
+                    });
+
+                    it("Cannot Complete Transfer (Recipient Token Not Allowed)", async function () {
+                        const preparedFill = await redeemDirectFillForTest(
+                            configRecipient.publicKey,
+                        );
+
+                        const transferIx = await swapLayer.completeTransferDirectIx(
+                            {
+                                payer: payer.publicKey,
+                                preparedFill,
+                                recipient: configRecipient.publicKey,
+                                recipientTokenAccount: recipientToken,
+                            },
+                            foreignChain,
+                        );
+                        await expectIxErr(
+                            connection,
+                            [transferIx],
+                            [payer],
+                            "Error Code: RecipientTokenNotAllowed",
+                        );
+                    });
+
+                    it("Set Recipient Config", async function () {
+                        const ix = await swapLayer.setRecipientConfigIx(
+                            {
+                                payer: payer.publicKey,
+                                recipient: configRecipient.publicKey,
+                            },
+                            { allowAnyTokenAccount: true, gasDropoffAsWsol: false },
+                        );
+                        await expectIxOk(connection, [ix], [payer, configRecipient]);
 
-                    // Balance check.
-                    const recipientBefore = await getUsdcAtaBalance(connection, payer.publicKey);
-                    const beneficiaryBefore = await connection.getBalance(beneficiary.publicKey);
+                        const recipientConfig = await swapLayer.fetchRecipientConfig(
+                            configRecipient.publicKey,
+                        );
+                        assert.deepEqual(
+                            recipientConfig,
+                            new RecipientConfig(
+                                recipientConfig.bump,
+                                configRecipient.publicKey,
+                                true,
+                                false,
+                            ),
+                        );
+                    });
 
-                    const transferIx = await swapLayer.completeTransferDirectIx(
-                        {
-                            payer: payer.publicKey,
-                            beneficiary: beneficiary.publicKey,
-                            preparedFill,
-                        },
-                        foreignChain,
-                    );
+                    it("Complete Transfer (Any Token Account)", async function () {
+                        const preparedFill = await redeemDirectFillForTest(
+                            configRecipient.publicKey,
+                        );
 
-                    await expectIxOk(connection, [transferIx], [payer]);
+                        const { amount: balanceBefore } = await splToken.getAccount(
+                            connection,
+                            recipientToken,
+                        );
 
-                    // Balance check.
-                    const recipientAfter = await getUsdcAtaBalance(connection, payer.publicKey);
-                    const beneficiaryAfter = await connection.getBalance(beneficiary.publicKey);
+                        const transferIx = await swapLayer.completeTransferDirectIx(
+                            {
+                                payer: payer.publicKey,
+                                preparedFill,
+                                recipient: configRecipient.publicKey,
+                                recipientTokenAccount: recipientToken,
+                                recipientConfig: swapLayer.recipientConfigAddress(
+                                    configRecipient.publicKey,
+                                ),
+                            },
+                            foreignChain,
+                        );
+                        await expectIxOk(connection, [transferIx], [payer]);
 
-                    assert.equal(recipientAfter, recipientBefore + message.deposit!.message.amount);
-                    assert.isAbove(beneficiaryAfter, beneficiaryBefore);
+                        const { amount: balanceAfter } = await splToken.getAccount(
+                            connection,
+                            recipientToken,
+                        );
+                        assert.isAbove(Number(balanceAfter), Number(balanceBefore));
+                    });
+
+                    async function redeemDirectFillForTest(recipient: PublicKey) {
+                        const result = await createAndRedeemCctpFillForTest(
+                            testCctpNonce++,
+                            foreignChain,
+                            foreignTokenRouterAddress,
+                            foreignSwapLayerAddress,
+                            wormholeSequence,
+                            encodeSwapLayerMessage({
+                                recipient: toUniversal("Solana", recipient.toBytes()),
+                                redeemMode: { mode: "Direct" },
+                                outputToken: { type: "Usdc" },
+                            }),
+                        );
+                        return tokenRouter.preparedFillAddress(result!.vaa);
+                    }
                 });
             });
         });
@@ -3692,6 +5297,233 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                 });
             });
         });
+
+        describe("USDC Transfer (Relay Payload)", function () {
+            describe("Outbound", function () {
+                it("Cannot Stage Outbound (Relay Payload Not Supported)", async function () {
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+
+                    const [, ix] = await swapLayer.stageOutboundIx(
+                        {
+                            payer: payer.publicKey,
+                            senderToken,
+                            stagedOutbound: Keypair.generate().publicKey,
+                            usdcRefundToken: senderToken,
+                        },
+                        {
+                            transferType: "sender",
+                            amountIn: 6900000000n,
+                            isExactIn: false,
+                            targetChain: foreignChain,
+                            recipient: foreignRecipientAddress,
+                            redeemOption: {
+                                relayPayload: {
+                                    gasDropoff: 0,
+                                    maxRelayerFee: 1000000000n,
+                                    payload: Buffer.from("Insert payload here"),
+                                },
+                            },
+                            outputToken: null,
+                        },
+                    );
+                    await expectIxErr(
+                        connection,
+                        [ix],
+                        [payer],
+                        "Error Code: RelayPayloadNotSupported",
+                    );
+                });
+
+                it("Stage Outbound (Relay Payload)", async function () {
+                    const stagedOutboundSigner = Keypair.generate();
+                    const stagedOutbound = stagedOutboundSigner.publicKey;
+                    const polygonChain = toChainId("Polygon");
+
+                    // Register a new target chain whose network can redeem relayed payloads.
+                    await addPeerForTest(owner, {
+                        ...TEST_PEER_PARAMS,
+                        chain: polygonChain,
+                        address: foreignSwapLayerAddress,
+                        relayParams: TEST_RELAY_PARAMS,
+                        supportsRelayPayload: true,
+                    });
+
+                    const amountIn = 690000n;
+                    const payload = Buffer.from("Insert payload here");
+                    const senderToken = splToken.getAssociatedTokenAddressSync(
+                        swapLayer.usdcMint,
+                        payer.publicKey,
+                    );
+                    const [, ix] = await swapLayer.stageOutboundIx(
+                        {
+                            payer: payer.publicKey,
+                            senderToken,
+                            stagedOutbound,
+                            usdcRefundToken: senderToken,
+                            peer: swapLayer.peerAddress(polygonChain),
+                        },
+                        {
+                            transferType: "sender",
+                            amountIn,
+                            isExactIn: true,
+                            targetChain: polygonChain,
+                            recipient: foreignRecipientAddress,
+                            redeemOption: {
+                                relayPayload: {
+                                    gasDropoff: 0,
+                                    maxRelayerFee: 1000000000n,
+                                    payload,
+                                },
+                            },
+                            outputToken: null,
+                        },
+                    );
+                    await expectIxOk(connection, [ix], [payer, stagedOutboundSigner]);
+
+                    const expectedRelayerFee = calculateRelayerFee(TEST_RELAY_PARAMS, 0, {
+                        type: "Usdc",
+                    });
+
+                    const { info, stagedRedeem } = await swapLayer.fetchStagedOutbound(
+                        stagedOutbound,
+                    );
+                    assert.equal(info.targetChain, polygonChain);
+                    assert.deepEqual(stagedRedeem, {
+                        relayPayload: {
+                            gasDropoff: 0,
+                            relayingFee: uint64ToBN(expectedRelayerFee),
+                            payload,
+                        },
+                    });
+                });
+            });
+
+            describe("Inbound", function () {
+                const payload = Buffer.from("Insert payload here");
+                const relayingFee = 420000n;
+                const sender = toUniversal("Ethereum", "0x000000000000000000000000000000000000d00d");
+                const validSwapMessage = encodeSwapLayerMessage({
+                    recipient: toUniversal("Solana", recipient.publicKey.toBytes()),
+                    redeemMode: {
+                        mode: "RelayPayload",
+                        gasDropoff: 0,
+                        relayingFee,
+                        sender,
+                        buf: payload,
+                    },
+                    outputToken: { type: "Usdc" },
+                });
+
+                it("Cannot Complete Transfer (Invalid Redeem Mode)", async function () {
+                    const result = await createAndRedeemCctpFillForTest(
+                        testCctpNonce++,
+                        foreignChain,
+                        foreignTokenRouterAddress,
+                        foreignSwapLayerAddress,
+                        wormholeSequence,
+                        encodeSwapLayerMessage({
+                            recipient: toUniversal("Solana", recipient.publicKey.toBytes()),
+                            redeemMode: { mode: "Payload", sender, buf: payload },
+                            outputToken: { type: "Usdc" },
+                        }),
+                    );
+                    const { vaa } = result!;
+
+                    const ix = await swapLayer.completeTransferRelayPayloadIx(
+                        {
+                            payer: payer.publicKey,
+                            preparedFill: tokenRouter.preparedFillAddress(vaa),
+                            recipient: recipient.publicKey,
+                        },
+                        foreignChain,
+                    );
+                    await expectIxErr(connection, [ix], [payer], "Error Code: InvalidRedeemMode");
+                });
+
+                it("Cannot Complete Transfer (Invalid Recipient)", async function () {
+                    const result = await createAndRedeemCctpFillForTest(
+                        testCctpNonce++,
+                        foreignChain,
+                        foreignTokenRouterAddress,
+                        foreignSwapLayerAddress,
+                        wormholeSequence,
+                        validSwapMessage,
+                    );
+                    const { vaa } = result!;
+
+                    const ix = await swapLayer.completeTransferRelayPayloadIx(
+                        {
+                            payer: payer.publicKey,
+                            preparedFill: tokenRouter.preparedFillAddress(vaa),
+                            recipient: payer.publicKey,
+                        },
+                        foreignChain,
+                    );
+                    await expectIxErr(connection, [ix], [payer], "Error Code: InvalidRecipient");
+                });
+
+                it("Complete Transfer Relay Payload", async function () {
+                    const result = await createAndRedeemCctpFillForTest(
+                        testCctpNonce++,
+                        foreignChain,
+                        foreignTokenRouterAddress,
+                        foreignSwapLayerAddress,
+                        wormholeSequence,
+                        validSwapMessage,
+                    );
+                    const { vaa, message } = result!;
+
+                    const preparedFill = tokenRouter.preparedFillAddress(vaa);
+                    const feeVaultBalanceBefore = await getFeeVaultBalanceForTest();
+
+                    const ix = await swapLayer.completeTransferRelayPayloadIx(
+                        {
+                            payer: payer.publicKey,
+                            preparedFill,
+                            recipient: recipient.publicKey,
+                        },
+                        foreignChain,
+                    );
+                    await expectIxOk(connection, [ix], [payer]);
+
+                    // The relayer did not provide a token account, so the fee vault collects the
+                    // entire relaying fee.
+                    const feeVaultBalanceAfter = await getFeeVaultBalanceForTest();
+                    assert.equal(feeVaultBalanceAfter, feeVaultBalanceBefore + relayingFee);
+
+                    const stagedInbound = swapLayer.stagedInboundAddress(preparedFill);
+                    const stagedCustodyToken = swapLayer.stagedCustodyTokenAddress(stagedInbound);
+                    const { amount: stagedBalance } = await splToken.getAccount(
+                        connection,
+                        stagedCustodyToken,
+                    );
+                    assert.equal(stagedBalance, message.deposit!.message.amount - relayingFee);
+
+                    const stagedInboundData = await swapLayer.fetchStagedInbound(stagedInbound);
+                    assert.deepEqual(
+                        stagedInboundData,
+                        new StagedInbound(
+                            {
+                                preparedFill,
+                                bump: stagedInboundData.seeds.bump,
+                            },
+                            {
+                                custodyToken: stagedCustodyToken,
+                                stagedBy: payer.publicKey,
+                                sourceChain: foreignChain,
+                                sender: Array.from(sender.toUint8Array()),
+                                recipient: recipient.publicKey,
+                                isNative: false,
+                            },
+                            payload,
+                        ),
+                    );
+                });
+            });
+        });
     });
 
     async function createAndRedeemCctpFillForTest(
@@ -3849,6 +5681,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
             payer: PublicKey;
             senderToken: PublicKey;
             sender?: PublicKey;
+            srcMint?: PublicKey;
         },
         opts: {
             amountIn?: bigint;
@@ -3858,12 +5691,13 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                 | { payload: Uint8Array | Buffer }
                 | null;
             outputToken?: OutputToken | null;
+            expiresAt?: BN | null;
         } = {},
     ) {
         const stagedOutboundSigner = Keypair.generate();
         const stagedOutbound = stagedOutboundSigner.publicKey;
 
-        let { amountIn, isExactIn, redeemOption, outputToken } = opts;
+        let { amountIn, isExactIn, redeemOption, outputToken, expiresAt } = opts;
         amountIn ??= 690000n;
         isExactIn ??= false;
         redeemOption ??= null;
@@ -3873,7 +5707,13 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
             {
                 ...accounts,
                 stagedOutbound,
-                usdcRefundToken: accounts.senderToken,
+                usdcRefundToken:
+                    accounts.srcMint === undefined
+                        ? accounts.senderToken
+                        : splToken.getAssociatedTokenAddressSync(
+                              swapLayer.usdcMint,
+                              accounts.sender ?? accounts.payer,
+                          ),
             },
             {
                 transferType: "sender",
@@ -3883,6 +5723,7 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
                 recipient: foreignRecipientAddress,
                 redeemOption,
                 outputToken,
+                expiresAt,
             },
         );
         assert.isNull(approveIx);
@@ -3890,14 +5731,91 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
         await expectIxOk(connection, [ix], [payer, stagedOutboundSigner]);
 
         const stagedCustodyToken = swapLayer.stagedCustodyTokenAddress(stagedOutbound);
+        const { owner: tokenProgram } = await connection.getAccountInfo(stagedCustodyToken);
         const { amount: custodyBalance } = await splToken.getAccount(
             connection,
             stagedCustodyToken,
+            undefined,
+            tokenProgram,
         );
 
         return { stagedOutbound, stagedCustodyToken, custodyBalance };
     }
 
+    async function createTransferFeeMintForTest(amount: bigint) {
+        const mintSigner = Keypair.generate();
+        const mint = mintSigner.publicKey;
+        const tokenProgram = splToken.TOKEN_2022_PROGRAM_ID;
+
+        // 1% transfer fee capped at 1 token.
+        const mintLen = splToken.getMintLen([splToken.ExtensionType.TransferFeeConfig]);
+        await expectIxOk(
+            connection,
+            [
+                SystemProgram.createAccount({
+                    fromPubkey: payer.publicKey,
+                    newAccountPubkey: mint,
+                    space: mintLen,
+                    lamports: await connection.getMinimumBalanceForRentExemption(mintLen),
+                    programId: tokenProgram,
+                }),
+                splToken.createInitializeTransferFeeConfigInstruction(
+                    mint,
+                    payer.publicKey,
+                    payer.publicKey,
+                    100,
+                    1_000_000n,
+                    tokenProgram,
+                ),
+                splToken.createInitializeMintInstruction(
+                    mint,
+                    6,
+                    payer.publicKey,
+                    null,
+                    tokenProgram,
+                ),
+            ],
+            [payer, mintSigner],
+        );
+
+        const senderToken = splToken.getAssociatedTokenAddressSync(
+            mint,
+            payer.publicKey,
+            false,
+            tokenProgram,
+        );
+        await expectIxOk(
+            connection,
+            [
+                splToken.createAssociatedTokenAccountInstruction(
+                    payer.publicKey,
+                    senderToken,
+                    payer.publicKey,
+                    mint,
+                    tokenProgram,
+                ),
+                splToken.createMintToInstruction(
+                    mint,
+                    senderToken,
+                    payer.publicKey,
+                    amount,
+                    [],
+                    tokenProgram,
+                ),
+            ],
+            [payer],
+        );
+
+        return { mint, senderToken };
+    }
+
+    async function getWithheldAmountForTest(mint: PublicKey) {
+        const { withheldAmount } = splToken.getTransferFeeConfig(
+            await splToken.getMint(connection, mint, undefined, splToken.TOKEN_2022_PROGRAM_ID),
+        )!;
+        return withheldAmount;
+    }
+
     async function stageInboundForTest(
         validSwapMessage: Buffer | Uint8Array,
         accounts: {
@@ -3934,6 +5852,14 @@ describe("Swap Layer -- Admin and USDC Transfer", () => {
         return { stagedInbound, stagedInboundCustody };
     }
 
+    async function getFeeVaultBalanceForTest() {
+        const { amount } = await splToken.getAccount(
+            connection,
+            swapLayer.feeVaultCustodyTokenAddress(swapLayer.feeVaultAddress()),
+        );
+        return amount;
+    }
+
     async function createTokenAccountForTest() {
         const tokenOwner = Keypair.generate();
         const token = splToken.getAssociatedTokenAddressSync(
@@ -26,6 +26,7 @@ import {
     ETHEREUM_USDC_ADDRESS,
     LOCALHOST,
     MOCK_GUARDIANS,
+    OWNER_ASSISTANT_KEYPAIR,
     PAYER_KEYPAIR,
     REGISTERED_TOKEN_ROUTERS,
     USDC_MINT_ADDRESS,
@@ -90,6 +91,11 @@ describe("Swap Layer -- Jupiter V6", () => {
     const testRecipient = Keypair.generate();
     const feeUpdater = FEE_UPDATER_KEYPAIR;
 
+    // Outbound recipients must be valid addresses on the (EVM) target network.
+    const foreignRecipientAddress = Array.from(
+        Buffer.alloc(32, "0000000000000000000000beefdeadCf7178C407aA7369b67CB7edeadbeef", "hex"),
+    );
+
     // Program SDKs
     const swapLayer = new SwapLayerProgram(connection, localnet(), USDC_MINT_ADDRESS);
     const tokenRouter = swapLayer.tokenRouterProgram();
@@ -156,10 +162,12 @@ describe("Swap Layer -- Jupiter V6", () => {
                 .commonAccounts()
                 .then((accounts) => Object.values(accounts).filter((key) => key !== undefined));
 
-            const { feeRecipientToken } = await swapLayer.fetchCustodian();
+            const { feeVault, custodyToken: feeVaultCustodyToken } =
+                swapLayer.usdcFeeVaultComposite();
             const addresses = [
                 swapLayer.custodianAddress(),
-                feeRecipientToken,
+                feeVault,
+                feeVaultCustodyToken,
                 swapLayer.peerAddress(toChainId("Ethereum")),
                 splToken.TOKEN_2022_PROGRAM_ID,
                 splToken.ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -401,6 +409,46 @@ describe("Swap Layer -- Jupiter V6", () => {
                 );
             });
 
+            it("Cannot Swap (Invalid Swap In Amount)", async function () {
+                const srcMint = USDT_MINT_ADDRESS;
+
+                const { stagedOutbound, custodyBalance: inAmount } = await stageOutboundForTest(
+                    {
+                        payer: payer.publicKey,
+                        senderToken: splToken.getAssociatedTokenAddressSync(
+                            srcMint,
+                            payer.publicKey,
+                            false,
+                            await whichTokenProgram(connection, srcMint),
+                        ),
+                        srcMint,
+                    },
+                    {
+                        redeemOption: {
+                            relay: { gasDropoff: 500000, maxRelayerFee: 9999999999999n },
+                        },
+                    },
+                );
+
+                const preparedOrder = swapLayer.preparedOrderAddress(stagedOutbound);
+                const swapAuthority = swapLayer.swapAuthorityAddress(preparedOrder);
+                const { instruction: cpiInstruction } = await modifyUsdtToUsdcSwapResponseForTest(
+                    swapAuthority,
+                    {
+                        inAmount: inAmount - 1n,
+                        quotedOutAmount: inAmount - 1n, // stable swap
+                        slippageBps: 10000,
+                        cpi: true,
+                    },
+                );
+
+                await swapExactInForTest(
+                    { payer: payer.publicKey, stagedOutbound, srcMint },
+                    { cpiInstruction },
+                    { errorMsg: "Error Code: InvalidSwapInAmount" },
+                );
+            });
+
             it("Cannot Swap (Invalid USDC Refund Token)", async function () {
                 const srcMint = USDT_MINT_ADDRESS;
 
@@ -1173,12 +1221,12 @@ describe("Swap Layer -- Jupiter V6", () => {
                         relayingFee: 0n,
                         denormGasDropoff: 0n,
                         swapResponseModifier: modifyUsdcToUsdtSwapResponseForTest,
-                        errorMsg: "recipient_token. Error Code: ConstraintAddress",
+                        errorMsg: "Error Code: RecipientTokenNotAllowed",
                     },
                 );
             });
 
-            it("Cannot Swap (Invalid Fee Recipient)", async function () {
+            it("Cannot Swap (Invalid Fee Vault)", async function () {
                 const dstMint = USDT_MINT_ADDRESS;
                 const { limitAmount, outputToken } = newQuotedSwapOutputToken({
                     quotedAmountOut: 198_800_000n,
@@ -1202,20 +1250,13 @@ describe("Swap Layer -- Jupiter V6", () => {
                     },
                 );
 
-                // Pass in payer token account instead.
-                const recipientToken = await createAta(
-                    connection,
-                    payer,
-                    swapLayer.usdcMint,
-                    recipient,
-                );
-
+                // Pass in the fee vault of another mint instead.
                 await completeSwapRelayForTest(
                     {
                         payer: payer.publicKey,
                         preparedFill,
                         recipient: recipient,
-                        feeRecipientToken: recipientToken,
+                        feeVault: swapLayer.feeVaultAddress(USDT_MINT_ADDRESS),
                         dstMint,
                     },
                     {
@@ -1223,7 +1264,7 @@ describe("Swap Layer -- Jupiter V6", () => {
                         relayingFee: 0n,
                         denormGasDropoff: 0n,
                         swapResponseModifier: modifyUsdcToUsdtSwapResponseForTest,
-                        errorMsg: "fee_recipient_token. Error Code: ConstraintAddress",
+                        errorMsg: "fee_vault. Error Code: AccountNotInitialized",
                     },
                 );
             });
@@ -1914,7 +1955,7 @@ describe("Swap Layer -- Jupiter V6", () => {
                         amountIn,
                         isExactIn: true,
                         targetChain: toChainId("Ethereum"),
-                        recipient: Array.from(Buffer.alloc(32, "deadbeef")),
+                        recipient: foreignRecipientAddress,
                         redeemOption: null,
                         outputToken: null,
                     },
@@ -1957,6 +1998,67 @@ describe("Swap Layer -- Jupiter V6", () => {
         });
 
         describe("Outbound", function () {
+            before("Add USDT Fee Vault", async function () {
+                const ownerAssistant = OWNER_ASSISTANT_KEYPAIR;
+                const ix = await swapLayer.addFeeVaultIx({
+                    ownerOrAssistant: ownerAssistant.publicKey,
+                    payer: payer.publicKey,
+                    mint: USDT_MINT_ADDRESS,
+                    destinationToken: splToken.getAssociatedTokenAddressSync(
+                        USDT_MINT_ADDRESS,
+                        payer.publicKey,
+                        false,
+                        await whichTokenProgram(connection, USDT_MINT_ADDRESS),
+                    ),
+                });
+
+                await expectIxOk(connection, [ix], [payer, ownerAssistant]);
+            });
+
+            it("Cannot Initiate Swap (Collect Dust Without Sender)", async function () {
+                const srcMint = USDT_MINT_ADDRESS;
+
+                const { stagedOutbound, custodyBalance: inAmount } = await stageOutboundForTest({
+                    payer: payer.publicKey,
+                    senderToken: splToken.getAssociatedTokenAddressSync(
+                        srcMint,
+                        payer.publicKey,
+                        false,
+                        await whichTokenProgram(connection, srcMint),
+                    ),
+                    srcMint,
+                });
+
+                const preparedOrder = swapLayer.preparedOrderAddress(stagedOutbound);
+                const swapAuthority = swapLayer.swapAuthorityAddress(preparedOrder);
+                const { instruction: cpiInstruction } = await modifyUsdtToUsdcSwapResponseForTest(
+                    swapAuthority,
+                    {
+                        inAmount,
+                        quotedOutAmount: inAmount, // stable swap
+                        slippageBps: 50,
+                        cpi: true,
+                    },
+                );
+
+                // Someone other than the sender cannot give up the sender's residual.
+                await swapExactInForTest(
+                    {
+                        payer: testRecipient.publicKey,
+                        stagedOutbound,
+                        srcMint,
+                    },
+                    {
+                        cpiInstruction,
+                        collectDust: true,
+                    },
+                    {
+                        signers: [testRecipient],
+                        errorMsg: "Error Code: SenderRequired",
+                    },
+                );
+            });
+
             it("USDT via Whirlpool", async function () {
                 const srcMint = USDT_MINT_ADDRESS;
 
@@ -2004,6 +2106,7 @@ describe("Swap Layer -- Jupiter V6", () => {
                     assert.isTrue(accInfos.every((info) => info === null));
                 }
 
+                // The sender opts into collecting any residual in the USDT fee vault.
                 const ix = await swapLayer.initiateSwapExactInIx(
                     {
                         payer: payer.publicKey,
@@ -2012,9 +2115,21 @@ describe("Swap Layer -- Jupiter V6", () => {
                     },
                     {
                         cpiInstruction,
+                        collectDust: true,
                     },
                 );
 
+                const srcFeeVault = swapLayer.feeVaultAddress(srcMint);
+                const srcFeeVaultCustodyToken = swapLayer.feeVaultCustodyTokenAddress(srcFeeVault);
+                const srcTokenProgram = await whichTokenProgram(connection, srcMint);
+                const { accounting: accountingBefore } = await swapLayer.fetchFeeVault(srcMint);
+                const { amount: srcFeeVaultBalanceBefore } = await splToken.getAccount(
+                    connection,
+                    srcFeeVaultCustodyToken,
+                    undefined,
+                    srcTokenProgram,
+                );
+
                 const computeIx = ComputeBudgetProgram.setComputeUnitLimit({
                     units: 360_000,
                 });
@@ -2040,6 +2155,19 @@ describe("Swap Layer -- Jupiter V6", () => {
                     assert.isTrue(accInfos.every((info) => info === null));
                 }
 
+                // Any residual is collected by the source mint's fee vault.
+                const { accounting: accountingAfter } = await swapLayer.fetchFeeVault(srcMint);
+                const { amount: srcFeeVaultBalanceAfter } = await splToken.getAccount(
+                    connection,
+                    srcFeeVaultCustodyToken,
+                    undefined,
+                    srcTokenProgram,
+                );
+                assert.equal(
+                    (srcFeeVaultBalanceAfter - srcFeeVaultBalanceBefore).toString(),
+                    accountingAfter.dust.sub(accountingBefore.dust).toString(),
+                );
+
                 const { targetChain, usdcRefundToken, recipient } = stagedOutboundInfo;
                 const { address: redeemer } = await swapLayer.fetchPeer(targetChain as ChainId);
 
@@ -2081,6 +2209,199 @@ describe("Swap Layer -- Jupiter V6", () => {
                 );
                 assert.isTrue(preparedCustodyTokenBalance >= minAmountOut);
             });
+
+            it("Cannot Swap And Initiate (Invalid Swap In Amount)", async function () {
+                const srcMint = USDT_MINT_ADDRESS;
+                const amountIn = 690000n;
+                const preparedOrderSigner = Keypair.generate();
+                const preparedOrder = preparedOrderSigner.publicKey;
+
+                const swapAuthority = swapLayer.swapAuthorityAddress(preparedOrder);
+                const { instruction: cpiInstruction } = await modifyUsdtToUsdcSwapResponseForTest(
+                    swapAuthority,
+                    {
+                        inAmount: amountIn - 1n,
+                        quotedOutAmount: amountIn - 1n, // stable swap
+                        slippageBps: 50,
+                        cpi: true,
+                    },
+                );
+
+                const ix = await swapLayer.swapAndInitiateIx(
+                    {
+                        payer: payer.publicKey,
+                        preparedOrder,
+                        srcMint,
+                        usdcRefundToken: splToken.getAssociatedTokenAddressSync(
+                            swapLayer.usdcMint,
+                            payer.publicKey,
+                        ),
+                    },
+                    {
+                        cpiInstruction,
+                        amountIn,
+                        targetChain: toChainId("Ethereum"),
+                        recipient: foreignRecipientAddress,
+                        redeemOption: null,
+                        outputToken: null,
+                    },
+                );
+
+                const computeIx = ComputeBudgetProgram.setComputeUnitLimit({
+                    units: 420_000,
+                });
+
+                const addressLookupTableAccounts = await Promise.all(
+                    luts.map(async (lookupTableAddress) => {
+                        const resp = await connection.getAddressLookupTable(lookupTableAddress);
+                        return resp.value;
+                    }),
+                );
+
+                await expectIxErr(
+                    connection,
+                    [computeIx, ix],
+                    [payer, preparedOrderSigner],
+                    "Error Code: InvalidSwapInAmount",
+                    { addressLookupTableAccounts },
+                );
+            });
+
+            it("USDT via Whirlpool (Swap And Initiate)", async function () {
+                const srcMint = USDT_MINT_ADDRESS;
+                const srcTokenProgram = await whichTokenProgram(connection, srcMint);
+                const amountIn = 690000n;
+                const targetChain = toChainId("Ethereum");
+                const preparedOrderSigner = Keypair.generate();
+                const preparedOrder = preparedOrderSigner.publicKey;
+
+                const senderToken = splToken.getAssociatedTokenAddressSync(
+                    srcMint,
+                    payer.publicKey,
+                    false,
+                    srcTokenProgram,
+                );
+                const usdcRefundToken = splToken.getAssociatedTokenAddressSync(
+                    swapLayer.usdcMint,
+                    payer.publicKey,
+                );
+
+                const swapAuthority = swapLayer.swapAuthorityAddress(preparedOrder);
+                const {
+                    instruction: cpiInstruction,
+                    sourceToken,
+                    destinationToken,
+                    sourceMint,
+                    destinationMint,
+                    minAmountOut,
+                } = await modifyUsdtToUsdcSwapResponseForTest(swapAuthority, {
+                    inAmount: amountIn,
+                    quotedOutAmount: amountIn, // stable swap
+                    slippageBps: 50,
+                    cpi: true,
+                });
+                assert.deepEqual(sourceMint, srcMint);
+                assert.deepEqual(destinationMint, swapLayer.usdcMint);
+
+                const ix = await swapLayer.swapAndInitiateIx(
+                    {
+                        payer: payer.publicKey,
+                        preparedOrder,
+                        srcMint,
+                        usdcRefundToken,
+                    },
+                    {
+                        cpiInstruction,
+                        amountIn,
+                        targetChain,
+                        recipient: foreignRecipientAddress,
+                        redeemOption: null,
+                        outputToken: null,
+                    },
+                );
+
+                const computeIx = ComputeBudgetProgram.setComputeUnitLimit({
+                    units: 420_000,
+                });
+
+                const addressLookupTableAccounts = await Promise.all(
+                    luts.map(async (lookupTableAddress) => {
+                        const resp = await connection.getAddressLookupTable(lookupTableAddress);
+                        return resp.value;
+                    }),
+                );
+
+                const { amount: balanceBefore } = await splToken.getAccount(
+                    connection,
+                    senderToken,
+                    undefined,
+                    srcTokenProgram,
+                );
+
+                await expectIxOk(connection, [computeIx, ix], [payer, preparedOrderSigner], {
+                    addressLookupTableAccounts,
+                });
+
+                // Any residual is returned to the sender.
+                const { amount: balanceAfter } = await splToken.getAccount(
+                    connection,
+                    senderToken,
+                    undefined,
+                    srcTokenProgram,
+                );
+                assert.isTrue(balanceBefore - balanceAfter <= amountIn);
+
+                // The swap token accounts are closed.
+                {
+                    const accInfos = await connection.getMultipleAccountsInfo([
+                        sourceToken,
+                        destinationToken,
+                    ]);
+                    assert.isTrue(accInfos.every((info) => info === null));
+                }
+
+                const { address: redeemer } = await swapLayer.fetchPeer(targetChain);
+
+                // Verify the relevant information in the prepared order.
+                const preparedOrderData = await tokenRouter.fetchPreparedOrder(preparedOrder);
+
+                const { info } = preparedOrderData;
+                assert.deepEqual(
+                    preparedOrderData,
+                    new tokenRouterSdk.PreparedOrder(
+                        {
+                            orderSender: swapLayer.custodianAddress(),
+                            preparedBy: payer.publicKey,
+                            orderType: {
+                                market: {
+                                    minAmountOut: null,
+                                },
+                            },
+                            srcToken: destinationToken,
+                            refundToken: usdcRefundToken,
+                            targetChain,
+                            redeemer,
+                            preparedCustodyTokenBump: info.preparedCustodyTokenBump,
+                        },
+                        Buffer.from(
+                            encodeSwapLayerMessage({
+                                recipient: new UniversalAddress(
+                                    Uint8Array.from(foreignRecipientAddress),
+                                ),
+                                redeemMode: { mode: "Direct" },
+                                outputToken: { type: "Usdc" },
+                            }),
+                        ),
+                    ),
+                );
+
+                // Verify the prepared custody token balance.
+                const { amount: preparedCustodyTokenBalance } = await splToken.getAccount(
+                    connection,
+                    tokenRouter.preparedCustodyTokenAddress(preparedOrder),
+                );
+                assert.isTrue(preparedCustodyTokenBalance >= minAmountOut);
+            });
         });
 
         describe("Inbound", function () {
@@ -2135,7 +2456,7 @@ describe("Swap Layer -- Jupiter V6", () => {
                     {
                         limitAmount,
                         swapResponseModifier: modifyUsdcToUsdtSwapResponseForTest,
-                        errorMsg: "recipient_token. Error Code: ConstraintAddress",
+                        errorMsg: "Error Code: RecipientTokenNotAllowed",
                     },
                 );
             });
@@ -2393,6 +2714,381 @@ describe("Swap Layer -- Jupiter V6", () => {
                 );
             });
 
+            it("Complete Swap Or Fallback (Swap Time Limit Not Exceeded)", async function () {
+                // Give the relayer enough time to execute the swap.
+                await updateSwapTimeLimitForTest(600);
+
+                const dstMint = USDT_MINT_ADDRESS;
+                const { limitAmount, outputToken } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 198_800_000n,
+                    dstMint,
+                    slippageBps: 15,
+                });
+
+                const { preparedFill, recipient } = await redeemSwapLayerFastFillForTest(
+                    { payer: payer.publicKey },
+                    emittedEvents,
+                    {
+                        dstMint,
+                        outputToken,
+                        amountIn: 200_000_000n,
+                    },
+                );
+
+                const dstToken = splToken.getAssociatedTokenAddressSync(dstMint, recipient);
+                const { amount: dstBalanceBefore } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+
+                await completeSwapOrFallbackForTest({
+                    payer: payer.publicKey,
+                    preparedFill,
+                    recipient,
+                    dstMint,
+                });
+
+                const { amount: dstBalanceAfter } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+                assert.isTrue(dstBalanceAfter - dstBalanceBefore >= limitAmount);
+
+                // The recipient did not receive USDC.
+                const recipientUsdcToken = splToken.getAssociatedTokenAddressSync(
+                    swapLayer.usdcMint,
+                    recipient,
+                );
+                assert.isNull(await connection.getAccountInfo(recipientUsdcToken));
+
+                await updateSwapTimeLimitForTest(0);
+            });
+
+            it("Complete Swap Or Fallback (Swap Time Limit Exceeded)", async function () {
+                const dstMint = USDT_MINT_ADDRESS;
+                const { outputToken } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 198_800_000n,
+                    dstMint,
+                    slippageBps: 15,
+                });
+
+                const { preparedFill, recipient } = await redeemSwapLayerFastFillForTest(
+                    { payer: payer.publicKey },
+                    emittedEvents,
+                    {
+                        dstMint,
+                        outputToken,
+                        amountIn: 200_000_000n,
+                    },
+                );
+
+                const { amount: fillAmount } = await splToken.getAccount(
+                    connection,
+                    tokenRouter.preparedCustodyTokenAddress(preparedFill),
+                );
+
+                const dstToken = splToken.getAssociatedTokenAddressSync(dstMint, recipient);
+                const { amount: dstBalanceBefore } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+
+                // The swap time limit is zero, so the bridged USDC is delivered to the recipient's
+                // USDC associated token account (created by this instruction) instead.
+                await completeSwapOrFallbackForTest({
+                    payer: payer.publicKey,
+                    preparedFill,
+                    recipient,
+                    dstMint,
+                });
+
+                const recipientUsdcBalance = await getUsdcAtaBalance(connection, recipient);
+                assert.equal(recipientUsdcBalance, fillAmount);
+
+                const { amount: dstBalanceAfter } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+                assert.equal(dstBalanceAfter, dstBalanceBefore);
+            });
+
+            it("Complete Swap Or Fallback (Quote Below Limit)", async function () {
+                // The swap time limit has not elapsed.
+                await updateSwapTimeLimitForTest(600);
+
+                const dstMint = USDT_MINT_ADDRESS;
+                const { limitAmount, outputToken } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 198_800_000n,
+                    dstMint,
+                    slippageBps: 15,
+                });
+
+                // The recipient executes the instruction itself.
+                const recipient = payer.publicKey;
+                const { preparedFill } = await redeemSwapLayerFastFillForTest(
+                    { payer: payer.publicKey },
+                    emittedEvents,
+                    {
+                        dstMint,
+                        outputToken,
+                        amountIn: 200_000_000n,
+                        recipient,
+                    },
+                    false, // createRecipientAta
+                );
+
+                const { amount: fillAmount } = await splToken.getAccount(
+                    connection,
+                    tokenRouter.preparedCustodyTokenAddress(preparedFill),
+                );
+
+                const dstToken = splToken.getAssociatedTokenAddressSync(dstMint, recipient);
+                const { amount: dstBalanceBefore } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+                const usdcBalanceBefore = await getUsdcAtaBalance(connection, recipient);
+
+                // The recipient's quote cannot meet the limit amount, so the bridged USDC is
+                // delivered immediately instead of performing the swap.
+                const quotedOutAmount = limitAmount / 2n;
+                await completeSwapOrFallbackForTest(
+                    {
+                        payer: payer.publicKey,
+                        preparedFill,
+                        recipient,
+                        dstMint,
+                    },
+                    { quotedOutAmount },
+                );
+
+                const usdcBalanceAfter = await getUsdcAtaBalance(connection, recipient);
+                assert.equal(usdcBalanceAfter - usdcBalanceBefore, fillAmount);
+
+                const { amount: dstBalanceAfter } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+                assert.equal(dstBalanceAfter, dstBalanceBefore);
+
+                await updateSwapTimeLimitForTest(0);
+            });
+
+            it("Cannot Complete Swap Override (Invalid Recipient)", async function () {
+                const { limitAmount, outputToken } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 2_000_000_000n,
+                    slippageBps: 150,
+                    deadline: 1,
+                });
+
+                // The encoded recipient is not the payer.
+                const { preparedFill } = await redeemSwapLayerFastFillForTest(
+                    { payer: payer.publicKey },
+                    emittedEvents,
+                    {
+                        outputToken,
+                        amountIn: 200_000_000n,
+                    },
+                );
+
+                await completeSwapOverrideForTest(
+                    { preparedFill, dstMint: USDT_MINT_ADDRESS },
+                    { limitAmount, errorMsg: "Error Code: InvalidRecipient" },
+                );
+            });
+
+            it("Cannot Complete Swap Override (Swap Deadline Not Exceeded)", async function () {
+                const currTime = await connection.getBlockTime(await connection.getSlot());
+                const { limitAmount, outputToken } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 2_000_000_000n,
+                    slippageBps: 150,
+                    deadline: currTime! + 3600,
+                });
+
+                const { preparedFill } = await redeemSwapLayerFastFillForTest(
+                    { payer: payer.publicKey },
+                    emittedEvents,
+                    {
+                        outputToken,
+                        amountIn: 200_000_000n,
+                        recipient: payer.publicKey,
+                    },
+                    false,
+                );
+
+                await completeSwapOverrideForTest(
+                    { preparedFill, dstMint: USDT_MINT_ADDRESS },
+                    { limitAmount, errorMsg: "Error Code: SwapDeadlineNotExceeded" },
+                );
+            });
+
+            it("Cannot Complete Swap Override (Recipient Token Not Allowed)", async function () {
+                const { outputToken } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 2_000_000_000n,
+                    slippageBps: 150,
+                    deadline: 1,
+                });
+
+                const { preparedFill } = await redeemSwapLayerFastFillForTest(
+                    { payer: payer.publicKey },
+                    emittedEvents,
+                    {
+                        outputToken,
+                        amountIn: 200_000_000n,
+                        recipient: payer.publicKey,
+                    },
+                    false,
+                );
+
+                // Without a recipient config, only the recipient's ATA is allowed.
+                const dstMint = USDT_MINT_ADDRESS;
+                const recipientToken = await splToken.createAccount(
+                    connection,
+                    payer,
+                    dstMint,
+                    payer.publicKey,
+                    Keypair.generate(),
+                );
+
+                await completeSwapOverrideForTest(
+                    { preparedFill, dstMint, recipientToken },
+                    { limitAmount: 1n, errorMsg: "Error Code: RecipientTokenNotAllowed" },
+                );
+            });
+
+            it("Complete Swap Override (Gas to Other (USDT) via Whirlpool)", async function () {
+                // The encoded swap expired long ago, so the recipient can choose another mint.
+                const { outputToken } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 2_000_000_000n,
+                    slippageBps: 150,
+                    deadline: 1,
+                });
+
+                const { preparedFill } = await redeemSwapLayerFastFillForTest(
+                    { payer: payer.publicKey },
+                    emittedEvents,
+                    {
+                        outputToken,
+                        amountIn: 200_000_000n,
+                        recipient: payer.publicKey,
+                    },
+                    false,
+                );
+
+                const dstMint = USDT_MINT_ADDRESS;
+                const { limitAmount } = newQuotedSwapOutputToken({
+                    quotedAmountOut: 198_800_000n,
+                    dstMint,
+                    slippageBps: 15,
+                });
+
+                const dstToken = splToken.getAssociatedTokenAddressSync(dstMint, payer.publicKey);
+                const { amount: dstBalanceBefore } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+
+                await completeSwapOverrideForTest({ preparedFill, dstMint }, { limitAmount });
+
+                const { amount: dstBalanceAfter } = await splToken.getAccount(
+                    connection,
+                    dstToken,
+                );
+                assert.isTrue(dstBalanceAfter - dstBalanceBefore >= limitAmount);
+            });
+
+            async function completeSwapOverrideForTest(
+                accounts: {
+                    preparedFill: PublicKey;
+                    dstMint: PublicKey;
+                    recipientToken?: PublicKey;
+                },
+                opts: ForTestOpts & { limitAmount: bigint },
+            ) {
+                const [{ signers, errorMsg }, { limitAmount }] = setDefaultForTestOpts(opts);
+                const { preparedFill } = accounts;
+
+                // Fetch amount from prepared fill.
+                const { amount: inAmount } = await splToken.getAccount(
+                    connection,
+                    tokenRouter.preparedCustodyTokenAddress(preparedFill),
+                );
+
+                const { instruction: cpiInstruction } = await modifyUsdcToUsdtSwapResponseForTest(
+                    swapLayer.swapAuthorityAddress(preparedFill),
+                    { cpi: true, inAmount, quotedOutAmount: 198_800_000n },
+                );
+
+                const ix = await swapLayer.completeSwapOverrideIx(
+                    { ...accounts, recipient: payer.publicKey },
+                    { limitAmount, cpiInstruction },
+                );
+
+                const addressLookupTableAccounts = await Promise.all(
+                    luts.map(async (lookupTableAddress) => {
+                        const resp = await connection.getAddressLookupTable(lookupTableAddress);
+                        return resp.value;
+                    }),
+                );
+
+                const ixs = [ComputeBudgetProgram.setComputeUnitLimit({ units: 700_000 }), ix];
+                if (errorMsg !== null) {
+                    await expectIxErr(connection, ixs, signers, errorMsg, {
+                        addressLookupTableAccounts,
+                    });
+                } else {
+                    await expectIxOk(connection, ixs, signers, { addressLookupTableAccounts });
+                }
+            }
+
+            async function updateSwapTimeLimitForTest(limit: number) {
+                const ix = await swapLayer.updateRelayParamsIx(
+                    {
+                        feeUpdater: feeUpdater.publicKey,
+                    },
+                    {
+                        chain: toChainId("Ethereum"),
+                        relayParams: {
+                            ...TEST_RELAY_PARAMS,
+                            swapTimeLimit: { fastLimit: limit, finalizedLimit: limit },
+                        },
+                    },
+                );
+
+                await expectIxOk(connection, [ix], [feeUpdater]);
+            }
+
+            async function completeSwapOrFallbackForTest(
+                accounts: {
+                    payer: PublicKey;
+                    preparedFill: PublicKey;
+                    recipient: PublicKey;
+                    dstMint: PublicKey;
+                },
+                opts: { quotedOutAmount?: bigint } = {},
+            ) {
+                const { instruction: cpiInstruction } = await modifyUsdcToUsdtSwapResponseForTest(
+                    swapLayer.swapAuthorityAddress(accounts.preparedFill),
+                    { cpi: true, quotedOutAmount: opts.quotedOutAmount },
+                );
+
+                const ix = await swapLayer.completeSwapOrFallbackIx(accounts, { cpiInstruction });
+
+                const addressLookupTableAccounts = await Promise.all(
+                    luts.map(async (lookupTableAddress) => {
+                        const resp = await connection.getAddressLookupTable(lookupTableAddress);
+                        return resp.value;
+                    }),
+                );
+
+                await expectIxOk(
+                    connection,
+                    [ComputeBudgetProgram.setComputeUnitLimit({ units: 700_000 }), ix],
+                    [payer],
+                    { addressLookupTableAccounts },
+                );
+            }
+
             it("Other (WSOL) via Phoenix V1", async function () {
                 const dstMint = splToken.NATIVE_MINT;
                 const { limitAmount, outputToken } = newQuotedSwapOutputToken({
@@ -2951,7 +3647,7 @@ describe("Swap Layer -- Jupiter V6", () => {
             payer: PublicKey;
             preparedFill: PublicKey;
             recipient: PublicKey;
-            feeRecipientToken?: PublicKey;
+            feeVault?: PublicKey;
             recipientToken?: PublicKey;
             dstMint?: PublicKey;
         },
@@ -3008,11 +3704,14 @@ describe("Swap Layer -- Jupiter V6", () => {
         const swapMsg = decodeSwapLayerMessage(redeemerMessage);
         const selfRedeem = accounts.payer == accounts.recipient;
 
-        // Fetch the balance of the fee recipient before the swap.
-        const { feeRecipientToken } = await swapLayer.fetchCustodian();
-        const { amount: feeRecipientBefore } = await splToken.getAccount(
+        // Fetch the fee vault's balance and accounting before the swap.
+        const { feeVault, custodyToken: feeVaultCustodyToken } = swapLayer.usdcFeeVaultComposite();
+        const { accounting: accountingBefore } = await swapLayer.fetchFeeVault({
+            address: feeVault,
+        });
+        const { amount: feeVaultBalanceBefore } = await splToken.getAccount(
             connection,
-            feeRecipientToken,
+            feeVaultCustodyToken,
         );
 
         if (swapMsg.outputToken.type === "Gas") {
@@ -3068,11 +3767,28 @@ describe("Swap Layer -- Jupiter V6", () => {
             assert.fail("Invalid output token type");
         }
 
-        const { amount: feeRecipientAfter } = await splToken.getAccount(
+        // The fee vault collects the relaying fee (unless self-redeemed) and any USDC dust.
+        const { accounting: accountingAfter } = await swapLayer.fetchFeeVault({
+            address: feeVault,
+        });
+        const { amount: feeVaultBalanceAfter } = await splToken.getAccount(
             connection,
-            feeRecipientToken,
+            feeVaultCustodyToken,
+        );
+        const relayingFeesCollected = BigInt(
+            accountingAfter.relayingFees.sub(accountingBefore.relayingFees).toString(),
+        );
+        const dustCollected = BigInt(
+            accountingAfter.dust
+                .add(accountingAfter.selfRedemptions)
+                .sub(accountingBefore.dust.add(accountingBefore.selfRedemptions))
+                .toString(),
+        );
+        assert.equal(relayingFeesCollected, selfRedeem ? 0n : relayingFee);
+        assert.equal(
+            feeVaultBalanceAfter - feeVaultBalanceBefore,
+            relayingFeesCollected + dustCollected,
         );
-        assert.equal(feeRecipientAfter - feeRecipientBefore, selfRedeem ? 0 : relayingFee);
     }
 
     async function completeSwapPayloadForTest(
@@ -3865,7 +4581,7 @@ describe("Swap Layer -- Jupiter V6", () => {
                 amountIn,
                 isExactIn: true,
                 targetChain,
-                recipient: Array.from(Buffer.alloc(32, "deadbeef")),
+                recipient: foreignRecipientAddress,
                 redeemOption,
                 outputToken,
             },
@@ -3942,6 +4658,7 @@ describe("Swap Layer -- Jupiter V6", () => {
         args: {
             cpiInstruction: TransactionInstruction;
             targetChain?: ChainId;
+            collectDust?: boolean;
         },
         opts: {
             additionalLuts?: PublicKey[];
@@ -67,3 +67,13 @@ export async function createAta(
 
     return recipientToken;
 }
+
+export async function waitUntilTimestamp(connection: Connection, timestamp: number) {
+    while (true) {
+        const blockTime = await connection.getBlockTime(await connection.getSlot());
+        if (blockTime > timestamp) {
+            return;
+        }
+        await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+}
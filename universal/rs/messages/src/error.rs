@@ -0,0 +1,267 @@
+use std::{fmt, io};
+
+use crate::wormhole_io::Readable;
+
+/// Field of a swap message that failed to decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldPath {
+    PayloadType,
+    Recipient,
+    RedeemMode,
+    RedeemModeSender,
+    RedeemModePayload,
+    RedeemModeGasDropoff,
+    RedeemModeRelayingFee,
+    OutputToken,
+    OutputTokenAddress,
+    OutputSwapDeadline,
+    OutputSwapLimitAmount,
+    SwapType,
+    SwapParameters,
+}
+
+impl FieldPath {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PayloadType => "payload_type",
+            Self::Recipient => "recipient",
+            Self::RedeemMode => "redeem_mode",
+            Self::RedeemModeSender => "redeem_mode.sender",
+            Self::RedeemModePayload => "redeem_mode.payload",
+            Self::RedeemModeGasDropoff => "redeem_mode.gas_dropoff",
+            Self::RedeemModeRelayingFee => "redeem_mode.relaying_fee",
+            Self::OutputToken => "output_token",
+            Self::OutputTokenAddress => "output_token.address",
+            Self::OutputSwapDeadline => "output_token.swap.deadline",
+            Self::OutputSwapLimitAmount => "output_token.swap.limit_amount",
+            Self::SwapType => "output_token.swap.swap_type",
+            Self::SwapParameters => "output_token.swap.parameters",
+        }
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error decoding a swap message or one of its types.
+///
+/// Offsets are absolute byte positions in the encoded value. For swap messages, this includes the
+/// payload type prefix, whether decoded with
+/// [SwapMessageV1Ref::parse](crate::messages::SwapMessageV1Ref::parse) or read as a
+/// [TypePrefixedPayload](crate::wormhole_io::TypePrefixedPayload).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The encoded value ended at `offset` before the field could be read.
+    Truncated { field: FieldPath, offset: usize },
+    /// The field's discriminant does not match any known variant.
+    UnknownDiscriminant {
+        field: FieldPath,
+        offset: usize,
+        value: u8,
+    },
+    /// Bytes remain after the last field.
+    TrailingBytes { offset: usize },
+    /// The underlying reader failed for a reason other than running out of bytes.
+    Io {
+        field: FieldPath,
+        offset: usize,
+        kind: io::ErrorKind,
+    },
+}
+
+impl DecodeError {
+    pub fn field(&self) -> Option<FieldPath> {
+        match self {
+            Self::Truncated { field, .. }
+            | Self::UnknownDiscriminant { field, .. }
+            | Self::Io { field, .. } => Some(*field),
+            Self::TrailingBytes { .. } => None,
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::Truncated { offset, .. }
+            | Self::UnknownDiscriminant { offset, .. }
+            | Self::TrailingBytes { offset }
+            | Self::Io { offset, .. } => *offset,
+        }
+    }
+
+    /// Recovers the decode error from an [io::Error] returned by a [Readable] impl in this crate.
+    pub fn from_io(err: &io::Error) -> Option<Self> {
+        err.get_ref()
+            .and_then(|inner| inner.downcast_ref::<Self>())
+            .copied()
+    }
+
+    fn shifted(self, by: usize) -> Self {
+        match self {
+            Self::Truncated { field, offset } => Self::Truncated {
+                field,
+                offset: offset.saturating_add(by),
+            },
+            Self::UnknownDiscriminant {
+                field,
+                offset,
+                value,
+            } => Self::UnknownDiscriminant {
+                field,
+                offset: offset.saturating_add(by),
+                value,
+            },
+            Self::TrailingBytes { offset } => Self::TrailingBytes {
+                offset: offset.saturating_add(by),
+            },
+            Self::Io {
+                field,
+                offset,
+                kind,
+            } => Self::Io {
+                field,
+                offset: offset.saturating_add(by),
+                kind,
+            },
+        }
+    }
+
+    /// Attributes an error reading `field`, which starts at `start`. `end` is the offset reached
+    /// when the error occurred, which is where the encoded value was truncated. Errors from nested
+    /// types already name their own field, so only their offset is adjusted.
+    fn at(field: FieldPath, start: usize, end: usize, err: io::Error) -> Self {
+        match Self::from_io(&err) {
+            Some(inner) => inner.shifted(start),
+            None if err.kind() == io::ErrorKind::UnexpectedEof => {
+                Self::Truncated { field, offset: end }
+            }
+            None => Self::Io {
+                field,
+                offset: start,
+                kind: err.kind(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Truncated { field, offset } => {
+                write!(f, "{field} truncated at offset {offset}")
+            }
+            Self::UnknownDiscriminant {
+                field,
+                offset,
+                value,
+            } => write!(f, "unknown {field} discriminant {value} at offset {offset}"),
+            Self::TrailingBytes { offset } => write!(f, "trailing bytes at offset {offset}"),
+            Self::Io {
+                field,
+                offset,
+                kind,
+            } => write!(f, "{field} at offset {offset}: {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<DecodeError> for io::Error {
+    fn from(err: DecodeError) -> Self {
+        let kind = match err {
+            DecodeError::Truncated { .. } => io::ErrorKind::UnexpectedEof,
+            DecodeError::Io { kind, .. } => kind,
+            _ => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+/// Reader that tracks how many bytes have been read so that errors can be attributed to a field
+/// and offset.
+pub(crate) struct FieldReader<R> {
+    inner: R,
+    offset: usize,
+}
+
+impl<R: io::Read> FieldReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self::with_offset(inner, 0)
+    }
+
+    /// Starts counting at `offset`, which accounts for bytes consumed before this reader (e.g. a
+    /// payload type prefix).
+    pub(crate) fn with_offset(inner: R, offset: usize) -> Self {
+        Self { inner, offset }
+    }
+
+    pub(crate) fn read<T: Readable>(&mut self, field: FieldPath) -> Result<T, DecodeError> {
+        let start = self.offset;
+        let result = T::read(self);
+        result.map_err(|err| DecodeError::at(field, start, self.offset, err))
+    }
+
+    /// Reads a discriminant, returning its value and offset.
+    pub(crate) fn read_discriminant(
+        &mut self,
+        field: FieldPath,
+    ) -> Result<(u8, usize), DecodeError> {
+        let offset = self.offset;
+        self.read(field).map(|value| (value, offset))
+    }
+}
+
+impl<R: io::Read> io::Read for FieldReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.offset = self.offset.saturating_add(len);
+        Ok(len)
+    }
+}
+
+impl<'a> FieldReader<&'a [u8]> {
+    /// Reads `len` bytes without copying them.
+    pub(crate) fn read_bytes(
+        &mut self,
+        field: FieldPath,
+        len: usize,
+    ) -> Result<&'a [u8], DecodeError> {
+        if self.inner.len() < len {
+            return Err(DecodeError::Truncated {
+                field,
+                offset: self.offset.saturating_add(self.inner.len()),
+            });
+        }
+
+        let (bytes, remaining) = self.inner.split_at(len);
+        self.inner = remaining;
+        self.offset = self.offset.saturating_add(len);
+        Ok(bytes)
+    }
+
+    /// Returns the bytes consumed by `read_fields` without copying them.
+    pub(crate) fn read_span(
+        &mut self,
+        read_fields: impl FnOnce(&mut Self) -> Result<(), DecodeError>,
+    ) -> Result<&'a [u8], DecodeError> {
+        let encoded = self.inner;
+        let start = self.offset;
+        read_fields(self)?;
+
+        Ok(&encoded[..self.offset.saturating_sub(start)])
+    }
+
+    /// Requires that every byte has been read.
+    pub(crate) fn finish(self) -> Result<(), DecodeError> {
+        if self.inner.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::TrailingBytes {
+                offset: self.offset,
+            })
+        }
+    }
+}
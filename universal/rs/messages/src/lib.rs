@@ -1,3 +1,5 @@
+pub mod error;
+
 pub mod messages;
 
 pub mod types;
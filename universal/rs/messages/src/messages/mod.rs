@@ -1,27 +1,35 @@
 use std::io;
 
 use crate::{
-    types::{OutputToken, RedeemMode},
+    error::{DecodeError, FieldPath, FieldReader},
+    types::{OutputToken, OutputTokenRef, RedeemMode, RedeemModeRef, UniversalAddress},
     wormhole_io::{Readable, TypePrefixedPayload, Writeable},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SwapMessageV1 {
-    pub recipient: [u8; 32],
+    pub recipient: UniversalAddress,
     pub redeem_mode: RedeemMode,
     pub output_token: OutputToken,
 }
 
+impl SwapMessageV1 {
+    const TYPE_PREFIX_LEN: usize = 1;
+}
+
 impl Readable for SwapMessageV1 {
     fn read<R>(reader: &mut R) -> io::Result<Self>
     where
         Self: Sized,
         R: io::Read,
     {
+        // This message is always read after its type prefix, so offsets account for it.
+        let mut reader = FieldReader::with_offset(reader, Self::TYPE_PREFIX_LEN);
+
         Ok(Self {
-            recipient: Readable::read(reader)?,
-            redeem_mode: Readable::read(reader)?,
-            output_token: Readable::read(reader)?,
+            recipient: reader.read(FieldPath::Recipient)?,
+            redeem_mode: reader.read(FieldPath::RedeemMode)?,
+            output_token: reader.read(FieldPath::OutputToken)?,
         })
     }
 }
@@ -48,13 +56,75 @@ impl TypePrefixedPayload<1> for SwapMessageV1 {
     }
 }
 
+/// Borrowed view of an encoded [SwapMessageV1] (including its type prefix). The encoded message is
+/// validated once when parsed, and nothing is allocated. Variable-length fields (payloads and EVM
+/// swap paths) are exposed as slices of the encoded message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapMessageV1Ref<'a> {
+    span: &'a [u8],
+    recipient: UniversalAddress,
+    redeem_mode: RedeemModeRef<'a>,
+    output_token: OutputTokenRef<'a>,
+}
+
+impl<'a> SwapMessageV1Ref<'a> {
+    pub fn parse(span: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut reader = FieldReader::new(span);
+
+        let (payload_type, offset) = reader.read_discriminant(FieldPath::PayloadType)?;
+        if Some([payload_type]) != SwapMessageV1::TYPE {
+            return Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::PayloadType,
+                offset,
+                value: payload_type,
+            });
+        }
+
+        let recipient = reader.read(FieldPath::Recipient)?;
+        let redeem_mode = RedeemModeRef::parse(&mut reader)?;
+        let output_token = OutputTokenRef::parse(&mut reader)?;
+        reader.finish()?;
+
+        Ok(Self {
+            span,
+            recipient,
+            redeem_mode,
+            output_token,
+        })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.span
+    }
+
+    pub fn recipient(&self) -> UniversalAddress {
+        self.recipient
+    }
+
+    pub fn redeem_mode(&self) -> RedeemModeRef<'a> {
+        self.redeem_mode
+    }
+
+    pub fn output_token(&self) -> OutputTokenRef<'a> {
+        self.output_token
+    }
+}
+
+impl<'a> TryFrom<SwapMessageV1Ref<'a>> for SwapMessageV1 {
+    type Error = io::Error;
+
+    fn try_from(value: SwapMessageV1Ref<'a>) -> Result<Self, Self::Error> {
+        Self::read_slice(value.span)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use hex_literal::hex;
 
     use crate::types::{
-        OutputSwap, OutputToken, RedeemMode, SwapType, Uint24, Uint48, UniswapSwapParameters,
-        UniswapSwapPath,
+        OutputSwap, OutputSwapRef, OutputToken, RedeemMode, SwapType, SwapTypeRef, Uint24, Uint48,
+        UniswapSwapParameters, UniswapSwapPath,
     };
 
     use super::*;
@@ -83,7 +153,8 @@ mod test {
         assert_eq!(
             swap_message,
             SwapMessageV1 {
-                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d"),
+                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d")
+                    .into(),
                 redeem_mode: RedeemMode::Direct,
                 output_token: OutputToken::Usdc,
             }
@@ -114,7 +185,8 @@ mod test {
         assert_eq!(
             swap_message,
             SwapMessageV1 {
-                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d"),
+                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d")
+                    .into(),
                 redeem_mode: RedeemMode::Direct,
                 output_token: OutputToken::Gas(OutputSwap {
                     deadline: 0,
@@ -155,7 +227,8 @@ mod test {
         assert_eq!(
             swap_message,
             SwapMessageV1 {
-                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d"),
+                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d")
+                    .into(),
                 redeem_mode: RedeemMode::Relay {
                     gas_dropoff: 0,
                     relaying_fee: Uint48::from(1000000u32),
@@ -189,7 +262,8 @@ mod test {
         assert_eq!(
             swap_message,
             SwapMessageV1 {
-                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d"),
+                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d")
+                    .into(),
                 redeem_mode: RedeemMode::Payload {
                     sender: hex!(
                         "00000000000000000000000000000000000000000000000000000000000ba5ed"
@@ -200,4 +274,151 @@ mod test {
             }
         );
     }
+
+    #[test]
+    pub fn test_swap_message_v1_usdc_relay_payload() {
+        let redeemer_message = hex!("010000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d03000001f40000000f424000000000000000000000000000000000000000000000000000000000000ba5ed0004deadbeef00");
+
+        let swap_message = SwapMessageV1::read_slice(&redeemer_message).unwrap();
+        assert_eq!(
+            swap_message,
+            SwapMessageV1 {
+                recipient: hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d")
+                    .into(),
+                redeem_mode: RedeemMode::RelayPayload {
+                    gas_dropoff: 500,
+                    relaying_fee: Uint48::from(1000000u32),
+                    sender: hex!(
+                        "00000000000000000000000000000000000000000000000000000000000ba5ed"
+                    ),
+                    buf: hex!("deadbeef").to_vec().try_into().unwrap()
+                },
+                output_token: OutputToken::Usdc,
+            }
+        );
+        assert_eq!(swap_message.to_vec(), redeemer_message);
+    }
+
+    #[test]
+    pub fn test_swap_message_v1_ref() {
+        let redeemer_message = hex!("010000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d03000001f40000000f424000000000000000000000000000000000000000000000000000000000000ba5ed0004deadbeef00");
+
+        let swap_message = SwapMessageV1Ref::parse(&redeemer_message).unwrap();
+        assert_eq!(
+            swap_message.recipient(),
+            hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d").into()
+        );
+        assert_eq!(
+            swap_message.redeem_mode(),
+            RedeemModeRef::RelayPayload {
+                gas_dropoff: 500,
+                relaying_fee: Uint48::from(1000000u32),
+                sender: hex!("00000000000000000000000000000000000000000000000000000000000ba5ed"),
+                buf: &hex!("deadbeef"),
+            }
+        );
+        assert_eq!(swap_message.output_token(), OutputTokenRef::Usdc);
+        assert_eq!(
+            SwapMessageV1::try_from(swap_message).unwrap(),
+            SwapMessageV1::read_slice(&redeemer_message).unwrap()
+        );
+
+        let redeemer_message = hex!("010000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d00010000000000000000000000000000000000000000010001f4015991a2df15a8f6a256d3ec51e99254cd3fb576a90001f4");
+
+        let swap_message = SwapMessageV1Ref::parse(&redeemer_message).unwrap();
+        assert_eq!(swap_message.redeem_mode(), RedeemModeRef::Direct);
+        assert_eq!(
+            swap_message.output_token(),
+            OutputTokenRef::Gas(OutputSwapRef {
+                deadline: 0,
+                limit_amount: 0,
+                swap_type: SwapTypeRef::UniswapV3(&hex!(
+                    "0001f4015991a2df15a8f6a256d3ec51e99254cd3fb576a90001f4"
+                )),
+            })
+        );
+
+        // Truncated and trailing bytes are invalid.
+        assert_eq!(
+            SwapMessageV1Ref::parse(&redeemer_message[..82]),
+            Err(DecodeError::Truncated {
+                field: FieldPath::SwapParameters,
+                offset: 82,
+            })
+        );
+        assert_eq!(
+            DecodeError::from_io(&SwapMessageV1::read_slice(&redeemer_message[..82]).unwrap_err()),
+            Some(DecodeError::Truncated {
+                field: FieldPath::SwapParameters,
+                offset: 82,
+            })
+        );
+        assert_eq!(
+            SwapMessageV1Ref::parse(&[&redeemer_message[..], &[0]].concat()),
+            Err(DecodeError::TrailingBytes { offset: 83 })
+        );
+    }
+
+    #[test]
+    pub fn test_swap_message_v1_decode_error() {
+        let mut redeemer_message = hex!("010000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d00010000000000000000000000000000000000000000010001f4015991a2df15a8f6a256d3ec51e99254cd3fb576a90001f4");
+
+        // Unknown swap type.
+        redeemer_message[55] = 7;
+        assert_eq!(
+            SwapMessageV1Ref::parse(&redeemer_message),
+            Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::SwapType,
+                offset: 55,
+                value: 7,
+            })
+        );
+
+        // Reading the owned message reports the same absolute offset.
+        let err = SwapMessageV1::read_slice(&redeemer_message).unwrap_err();
+        assert_eq!(
+            DecodeError::from_io(&err),
+            Some(DecodeError::UnknownDiscriminant {
+                field: FieldPath::SwapType,
+                offset: 55,
+                value: 7,
+            })
+        );
+
+        // Unknown redeem mode.
+        redeemer_message[33] = 4;
+        assert_eq!(
+            SwapMessageV1Ref::parse(&redeemer_message),
+            Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::RedeemMode,
+                offset: 33,
+                value: 4,
+            })
+        );
+
+        // Unknown payload type.
+        redeemer_message[0] = 2;
+        assert_eq!(
+            SwapMessageV1Ref::parse(&redeemer_message),
+            Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::PayloadType,
+                offset: 0,
+                value: 2,
+            })
+        );
+
+        // Truncated output swap.
+        let err = OutputToken::decode(&hex!("01000000000000")).unwrap_err();
+        assert_eq!(
+            err,
+            DecodeError::Truncated {
+                field: FieldPath::OutputSwapLimitAmount,
+                offset: 7,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "output_token.swap.limit_amount truncated at offset 7"
+        );
+    }
 }
@@ -9,3 +9,6 @@ pub use swap_type::*;
 
 mod uint;
 pub use uint::*;
+
+mod universal_address;
+pub use universal_address::*;
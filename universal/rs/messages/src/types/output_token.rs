@@ -1,8 +1,11 @@
 use std::io;
 
-use crate::wormhole_io::{Readable, Writeable};
+use crate::{
+    error::{DecodeError, FieldPath, FieldReader},
+    wormhole_io::{Readable, Writeable},
+};
 
-use super::SwapType;
+use super::{SwapType, SwapTypeRef};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OutputToken {
@@ -26,6 +29,15 @@ impl OutputToken {
             ),
         }
     }
+
+    /// Decodes an output token that spans all of `buf`.
+    pub fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = FieldReader::new(buf);
+        let output_token = reader.read(FieldPath::OutputToken)?;
+        reader.finish()?;
+
+        Ok(output_token)
+    }
 }
 
 impl Readable for OutputToken {
@@ -34,17 +46,21 @@ impl Readable for OutputToken {
         Self: Sized,
         R: io::Read,
     {
-        match u8::read(reader)? {
-            Self::USDC => Ok(Self::Usdc),
-            Self::GAS => Ok(Self::Gas(Readable::read(reader)?)),
-            Self::OTHER => Ok(Self::Other {
-                address: Readable::read(reader)?,
-                swap: Readable::read(reader)?,
+        let mut reader = FieldReader::new(reader);
+
+        match reader.read_discriminant(FieldPath::OutputToken)? {
+            (Self::USDC, _) => Ok(Self::Usdc),
+            (Self::GAS, _) => Ok(Self::Gas(reader.read(FieldPath::OutputToken)?)),
+            (Self::OTHER, _) => Ok(Self::Other {
+                address: reader.read(FieldPath::OutputTokenAddress)?,
+                swap: reader.read(FieldPath::OutputToken)?,
             }),
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Invalid OutputToken",
-            )),
+            (value, offset) => Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::OutputToken,
+                offset,
+                value,
+            }
+            .into()),
         }
     }
 }
@@ -91,10 +107,12 @@ impl Readable for OutputSwap {
         Self: Sized,
         R: io::Read,
     {
+        let mut reader = FieldReader::new(reader);
+
         Ok(Self {
-            deadline: Readable::read(reader)?,
-            limit_amount: Readable::read(reader)?,
-            swap_type: Readable::read(reader)?,
+            deadline: reader.read(FieldPath::OutputSwapDeadline)?,
+            limit_amount: reader.read(FieldPath::OutputSwapLimitAmount)?,
+            swap_type: reader.read(FieldPath::SwapType)?,
         })
     }
 }
@@ -109,3 +127,50 @@ impl Writeable for OutputSwap {
         self.swap_type.write(writer)
     }
 }
+
+/// Borrowed view of [OutputToken].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputTokenRef<'a> {
+    Usdc,
+    Gas(OutputSwapRef<'a>),
+    Other {
+        address: [u8; 32],
+        swap: OutputSwapRef<'a>,
+    },
+}
+
+impl<'a> OutputTokenRef<'a> {
+    pub(crate) fn parse(reader: &mut FieldReader<&'a [u8]>) -> Result<Self, DecodeError> {
+        match reader.read_discriminant(FieldPath::OutputToken)? {
+            (OutputToken::USDC, _) => Ok(Self::Usdc),
+            (OutputToken::GAS, _) => Ok(Self::Gas(OutputSwapRef::parse(reader)?)),
+            (OutputToken::OTHER, _) => Ok(Self::Other {
+                address: reader.read(FieldPath::OutputTokenAddress)?,
+                swap: OutputSwapRef::parse(reader)?,
+            }),
+            (value, offset) => Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::OutputToken,
+                offset,
+                value,
+            }),
+        }
+    }
+}
+
+/// Borrowed view of [OutputSwap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputSwapRef<'a> {
+    pub deadline: u32,
+    pub limit_amount: u128,
+    pub swap_type: SwapTypeRef<'a>,
+}
+
+impl<'a> OutputSwapRef<'a> {
+    pub(crate) fn parse(reader: &mut FieldReader<&'a [u8]>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            deadline: reader.read(FieldPath::OutputSwapDeadline)?,
+            limit_amount: reader.read(FieldPath::OutputSwapLimitAmount)?,
+            swap_type: SwapTypeRef::parse(reader)?,
+        })
+    }
+}
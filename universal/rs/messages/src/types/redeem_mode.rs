@@ -1,6 +1,7 @@
 use std::io;
 
 use crate::{
+    error::{DecodeError, FieldPath, FieldReader},
     types::Uint48,
     wormhole_io::{Readable, Writeable, WriteableBytes},
 };
@@ -17,12 +18,19 @@ pub enum RedeemMode {
         gas_dropoff: u32,
         relaying_fee: crate::types::Uint48,
     },
+    RelayPayload {
+        gas_dropoff: u32,
+        relaying_fee: crate::types::Uint48,
+        sender: [u8; 32],
+        buf: WriteableBytes<u16>,
+    },
 }
 
 impl RedeemMode {
     const DIRECT: u8 = 0;
     const PAYLOAD: u8 = 1;
     const RELAY: u8 = 2;
+    const RELAY_PAYLOAD: u8 = 3;
 
     pub fn written_size(&self) -> usize {
         match self {
@@ -39,6 +47,15 @@ impl RedeemMode {
 
                 FIXED
             }
+            Self::RelayPayload { buf, .. } => {
+                const FIXED: usize = 1 // discriminant
+                    + 4 // gas_dropoff
+                    + Uint48::BYTES // relaying_fee
+                    + 32 // sender
+                    + 2; // payload len
+
+                buf.len().saturating_add(FIXED)
+            }
         }
     }
 }
@@ -49,20 +66,30 @@ impl Readable for RedeemMode {
         Self: Sized,
         R: io::Read,
     {
-        match u8::read(reader)? {
-            Self::DIRECT => Ok(Self::Direct),
-            Self::PAYLOAD => Ok(Self::Payload {
-                sender: Readable::read(reader)?,
-                buf: Readable::read(reader)?,
+        let mut reader = FieldReader::new(reader);
+
+        match reader.read_discriminant(FieldPath::RedeemMode)? {
+            (Self::DIRECT, _) => Ok(Self::Direct),
+            (Self::PAYLOAD, _) => Ok(Self::Payload {
+                sender: reader.read(FieldPath::RedeemModeSender)?,
+                buf: reader.read(FieldPath::RedeemModePayload)?,
+            }),
+            (Self::RELAY, _) => Ok(Self::Relay {
+                gas_dropoff: reader.read(FieldPath::RedeemModeGasDropoff)?,
+                relaying_fee: reader.read(FieldPath::RedeemModeRelayingFee)?,
             }),
-            Self::RELAY => Ok(Self::Relay {
-                gas_dropoff: Readable::read(reader)?,
-                relaying_fee: Readable::read(reader)?,
+            (Self::RELAY_PAYLOAD, _) => Ok(Self::RelayPayload {
+                gas_dropoff: reader.read(FieldPath::RedeemModeGasDropoff)?,
+                relaying_fee: reader.read(FieldPath::RedeemModeRelayingFee)?,
+                sender: reader.read(FieldPath::RedeemModeSender)?,
+                buf: reader.read(FieldPath::RedeemModePayload)?,
             }),
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Invalid RedeemMode",
-            )),
+            (value, offset) => Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::RedeemMode,
+                offset,
+                value,
+            }
+            .into()),
         }
     }
 }
@@ -87,6 +114,70 @@ impl Writeable for RedeemMode {
                 gas_dropoff.write(writer)?;
                 relaying_fee.write(writer)
             }
+            Self::RelayPayload {
+                gas_dropoff,
+                relaying_fee,
+                sender,
+                buf,
+            } => {
+                Self::RELAY_PAYLOAD.write(writer)?;
+                gas_dropoff.write(writer)?;
+                relaying_fee.write(writer)?;
+                sender.write(writer)?;
+                buf.write(writer)
+            }
         }
     }
 }
+
+/// Borrowed view of [RedeemMode]. The payload is not copied out of the encoded message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedeemModeRef<'a> {
+    Direct,
+    Payload {
+        sender: [u8; 32],
+        buf: &'a [u8],
+    },
+    Relay {
+        gas_dropoff: u32,
+        relaying_fee: Uint48,
+    },
+    RelayPayload {
+        gas_dropoff: u32,
+        relaying_fee: Uint48,
+        sender: [u8; 32],
+        buf: &'a [u8],
+    },
+}
+
+impl<'a> RedeemModeRef<'a> {
+    pub(crate) fn parse(reader: &mut FieldReader<&'a [u8]>) -> Result<Self, DecodeError> {
+        match reader.read_discriminant(FieldPath::RedeemMode)? {
+            (RedeemMode::DIRECT, _) => Ok(Self::Direct),
+            (RedeemMode::PAYLOAD, _) => Ok(Self::Payload {
+                sender: reader.read(FieldPath::RedeemModeSender)?,
+                buf: read_payload(reader)?,
+            }),
+            (RedeemMode::RELAY, _) => Ok(Self::Relay {
+                gas_dropoff: reader.read(FieldPath::RedeemModeGasDropoff)?,
+                relaying_fee: reader.read(FieldPath::RedeemModeRelayingFee)?,
+            }),
+            (RedeemMode::RELAY_PAYLOAD, _) => Ok(Self::RelayPayload {
+                gas_dropoff: reader.read(FieldPath::RedeemModeGasDropoff)?,
+                relaying_fee: reader.read(FieldPath::RedeemModeRelayingFee)?,
+                sender: reader.read(FieldPath::RedeemModeSender)?,
+                buf: read_payload(reader)?,
+            }),
+            (value, offset) => Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::RedeemMode,
+                offset,
+                value,
+            }),
+        }
+    }
+}
+
+fn read_payload<'a>(reader: &mut FieldReader<&'a [u8]>) -> Result<&'a [u8], DecodeError> {
+    let len = reader.read::<u16>(FieldPath::RedeemModePayload)?;
+    reader.read_bytes(FieldPath::RedeemModePayload, len.into())
+}
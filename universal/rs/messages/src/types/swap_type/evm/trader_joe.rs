@@ -1,6 +1,9 @@
 use std::io::{self, ErrorKind};
 
-use crate::wormhole_io::{Readable, Writeable};
+use crate::{
+    error::{DecodeError, FieldPath, FieldReader},
+    wormhole_io::{Readable, Writeable},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TraderJoeSwapParameters {
@@ -20,6 +23,24 @@ impl TraderJoeSwapParameters {
     }
 }
 
+impl TraderJoeSwapParameters {
+    /// Reads the encoded parameters without decoding the swap path.
+    pub(crate) fn read_encoded<'a>(
+        reader: &mut FieldReader<&'a [u8]>,
+    ) -> Result<&'a [u8], DecodeError> {
+        reader.read_span(|reader| {
+            reader.read_bytes(FieldPath::SwapParameters, TraderJoePoolId::ENCODED_SIZE)?;
+            let path_len = reader.read::<u8>(FieldPath::SwapParameters)?;
+            reader.read_bytes(
+                FieldPath::SwapParameters,
+                usize::from(path_len).saturating_mul(TraderJoeSwapPath::ENCODED_SIZE),
+            )?;
+
+            Ok(())
+        })
+    }
+}
+
 impl Readable for TraderJoeSwapParameters {
     fn read<R>(reader: &mut R) -> io::Result<Self>
     where
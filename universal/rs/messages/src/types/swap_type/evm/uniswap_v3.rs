@@ -1,5 +1,6 @@
 use std::io::{self, ErrorKind};
 
+use crate::error::{DecodeError, FieldPath, FieldReader};
 use crate::types::Uint24;
 use crate::wormhole_io::{Readable, Writeable};
 
@@ -21,6 +22,24 @@ impl UniswapSwapParameters {
     }
 }
 
+impl UniswapSwapParameters {
+    /// Reads the encoded parameters without decoding the swap path.
+    pub(crate) fn read_encoded<'a>(
+        reader: &mut FieldReader<&'a [u8]>,
+    ) -> Result<&'a [u8], DecodeError> {
+        reader.read_span(|reader| {
+            reader.read_bytes(FieldPath::SwapParameters, Uint24::BYTES)?;
+            let path_len = reader.read::<u8>(FieldPath::SwapParameters)?;
+            reader.read_bytes(
+                FieldPath::SwapParameters,
+                usize::from(path_len).saturating_mul(UniswapSwapPath::ENCODED_SIZE),
+            )?;
+
+            Ok(())
+        })
+    }
+}
+
 impl Readable for UniswapSwapParameters {
     fn read<R>(reader: &mut R) -> io::Result<Self>
     where
@@ -6,7 +6,10 @@ pub use solana::*;
 
 use std::io;
 
-use crate::wormhole_io::{Readable, Writeable};
+use crate::{
+    error::{DecodeError, FieldPath, FieldReader},
+    wormhole_io::{Readable, Writeable},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SwapType {
@@ -37,14 +40,18 @@ impl Readable for SwapType {
         Self: Sized,
         R: io::Read,
     {
-        match u8::read(reader)? {
-            Self::UNISWAP_V3 => Ok(Self::UniswapV3(Readable::read(reader)?)),
-            Self::TRADER_JOE => Ok(Self::TraderJoe(Readable::read(reader)?)),
-            Self::JUPITER_V6 => Ok(Self::JupiterV6(Readable::read(reader)?)),
-            _ => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Invalid SwapType",
-            )),
+        let mut reader = FieldReader::new(reader);
+
+        match reader.read_discriminant(FieldPath::SwapType)? {
+            (Self::UNISWAP_V3, _) => Ok(Self::UniswapV3(reader.read(FieldPath::SwapParameters)?)),
+            (Self::TRADER_JOE, _) => Ok(Self::TraderJoe(reader.read(FieldPath::SwapParameters)?)),
+            (Self::JUPITER_V6, _) => Ok(Self::JupiterV6(reader.read(FieldPath::SwapParameters)?)),
+            (value, offset) => Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::SwapType,
+                offset,
+                value,
+            }
+            .into()),
         }
     }
 }
@@ -74,3 +81,35 @@ impl Writeable for SwapType {
         }
     }
 }
+
+/// Borrowed view of [SwapType]. EVM swap parameters are left encoded because they are only
+/// meaningful to the target network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapTypeRef<'a> {
+    /// Encoded [UniswapSwapParameters].
+    UniswapV3(&'a [u8]),
+    /// Encoded [TraderJoeSwapParameters].
+    TraderJoe(&'a [u8]),
+    JupiterV6(JupiterV6SwapParameters),
+}
+
+impl<'a> SwapTypeRef<'a> {
+    pub(crate) fn parse(reader: &mut FieldReader<&'a [u8]>) -> Result<Self, DecodeError> {
+        match reader.read_discriminant(FieldPath::SwapType)? {
+            (SwapType::UNISWAP_V3, _) => Ok(Self::UniswapV3(UniswapSwapParameters::read_encoded(
+                reader,
+            )?)),
+            (SwapType::TRADER_JOE, _) => Ok(Self::TraderJoe(
+                TraderJoeSwapParameters::read_encoded(reader)?,
+            )),
+            (SwapType::JUPITER_V6, _) => {
+                Ok(Self::JupiterV6(reader.read(FieldPath::SwapParameters)?))
+            }
+            (value, offset) => Err(DecodeError::UnknownDiscriminant {
+                field: FieldPath::SwapType,
+                offset,
+                value,
+            }),
+        }
+    }
+}
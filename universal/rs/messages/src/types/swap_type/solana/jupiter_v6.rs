@@ -1,8 +1,11 @@
 use std::io;
 
-use crate::wormhole_io::{Readable, Writeable};
+use crate::{
+    error::{DecodeError, FieldPath, FieldReader},
+    wormhole_io::{Readable, Writeable},
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct JupiterV6SwapParameters {
     pub dex_program_id: Option<[u8; 32]>,
 }
@@ -22,9 +25,22 @@ impl Readable for JupiterV6SwapParameters {
         Self: Sized,
         R: io::Read,
     {
-        Ok(Self {
-            dex_program_id: Readable::read(reader)?,
-        })
+        let mut reader = FieldReader::new(reader);
+
+        let dex_program_id = match reader.read_discriminant(FieldPath::SwapParameters)? {
+            (0, _) => None,
+            (1, _) => Some(reader.read(FieldPath::SwapParameters)?),
+            (value, offset) => {
+                return Err(DecodeError::UnknownDiscriminant {
+                    field: FieldPath::SwapParameters,
+                    offset,
+                    value,
+                }
+                .into())
+            }
+        };
+
+        Ok(Self { dex_program_id })
     }
 }
 
@@ -0,0 +1,246 @@
+use std::{fmt, io, str::FromStr};
+
+use crate::wormhole_io::{Readable, Writeable};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniversalAddressError {
+    /// Address is the zero address.
+    ZeroAddress,
+    /// Upper 12 bytes of an EVM address are not zero.
+    DirtyEvmAddress,
+    /// String is neither a valid hex nor base58 encoded address.
+    InvalidEncoding,
+}
+
+impl fmt::Display for UniversalAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ZeroAddress => write!(f, "zero address"),
+            Self::DirtyEvmAddress => write!(f, "upper 12 bytes of EVM address are not zero"),
+            Self::InvalidEncoding => write!(f, "invalid address encoding"),
+        }
+    }
+}
+
+impl std::error::Error for UniversalAddressError {}
+
+/// New type for a 32-byte address, which can represent an address on any network Wormhole
+/// supports. EVM addresses are left-padded with zeros.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UniversalAddress(pub [u8; 32]);
+
+impl UniversalAddress {
+    pub const ZERO: UniversalAddress = Self([0; 32]);
+    pub const BYTES: usize = 32;
+    const EVM_PADDING: usize = 12;
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    pub fn from_evm(addr: [u8; 20]) -> Self {
+        let mut out = [0; 32];
+        out[Self::EVM_PADDING..].copy_from_slice(&addr);
+        Self(out)
+    }
+
+    /// Convert to an EVM address. This conversion fails if the upper 12 bytes are not zero.
+    pub fn to_evm(&self) -> Result<[u8; 20], UniversalAddressError> {
+        let (padding, addr) = self.0.split_at(Self::EVM_PADDING);
+        if padding.iter().any(|&byte| byte != 0) {
+            return Err(UniversalAddressError::DirtyEvmAddress);
+        }
+
+        let mut out = [0; 20];
+        out.copy_from_slice(addr);
+        Ok(out)
+    }
+
+    /// Verify that this address is valid for a network, where `is_evm` indicates whether the
+    /// network uses EVM addresses. Mirroring `checkAddr` in the EVM Swap Layer's `Params.sol`, the
+    /// zero address is never valid.
+    pub fn validate(&self, is_evm: bool) -> Result<(), UniversalAddressError> {
+        if self.is_zero() {
+            return Err(UniversalAddressError::ZeroAddress);
+        }
+
+        if is_evm {
+            self.to_evm()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_base58(&self) -> String {
+        bs58::encode(self.0).into_string()
+    }
+}
+
+impl From<[u8; 32]> for UniversalAddress {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<UniversalAddress> for [u8; 32] {
+    fn from(value: UniversalAddress) -> Self {
+        value.0
+    }
+}
+
+impl From<[u8; 20]> for UniversalAddress {
+    fn from(value: [u8; 20]) -> Self {
+        Self::from_evm(value)
+    }
+}
+
+impl TryFrom<UniversalAddress> for [u8; 20] {
+    type Error = UniversalAddressError;
+
+    fn try_from(value: UniversalAddress) -> Result<Self, Self::Error> {
+        value.to_evm()
+    }
+}
+
+impl AsRef<[u8]> for UniversalAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "solana")]
+impl From<solana_program::pubkey::Pubkey> for UniversalAddress {
+    fn from(value: solana_program::pubkey::Pubkey) -> Self {
+        Self(value.to_bytes())
+    }
+}
+
+#[cfg(feature = "solana")]
+impl From<UniversalAddress> for solana_program::pubkey::Pubkey {
+    fn from(value: UniversalAddress) -> Self {
+        Self::new_from_array(value.0)
+    }
+}
+
+/// Displays the address as 0x-prefixed hex.
+impl fmt::Display for UniversalAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+/// Parses either a 0x-prefixed hex string (32-byte universal address or 20-byte EVM address) or a
+/// base58 string (e.g. Solana pubkey).
+impl FromStr for UniversalAddress {
+    type Err = UniversalAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x") {
+            Some(encoded) => {
+                let decoded =
+                    hex::decode(encoded).map_err(|_| UniversalAddressError::InvalidEncoding)?;
+                match decoded.len() {
+                    32 => <[u8; 32]>::try_from(decoded).map(Self::from),
+                    20 => <[u8; 20]>::try_from(decoded).map(Self::from_evm),
+                    _ => return Err(UniversalAddressError::InvalidEncoding),
+                }
+                .map_err(|_| UniversalAddressError::InvalidEncoding)
+            }
+            None => {
+                let mut out = [0; 32];
+                let len = bs58::decode(s)
+                    .onto(&mut out)
+                    .map_err(|_| UniversalAddressError::InvalidEncoding)?;
+                if len != Self::BYTES {
+                    return Err(UniversalAddressError::InvalidEncoding);
+                }
+                Ok(Self(out))
+            }
+        }
+    }
+}
+
+impl Readable for UniversalAddress {
+    fn read<R>(reader: &mut R) -> io::Result<Self>
+    where
+        Self: Sized,
+        R: io::Read,
+    {
+        Ok(Self(Readable::read(reader)?))
+    }
+}
+
+impl Writeable for UniversalAddress {
+    fn write<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.0.write(writer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hex_literal::hex;
+
+    use super::*;
+
+    const EVM_ADDRESS: [u8; 20] = hex!("6ca6d1e2d5347bfab1d91e883f1915560e09129d");
+
+    #[test]
+    fn test_universal_address_evm() {
+        let addr = UniversalAddress::from_evm(EVM_ADDRESS);
+        assert_eq!(
+            addr.0,
+            hex!("0000000000000000000000006ca6d1e2d5347bfab1d91e883f1915560e09129d")
+        );
+        assert_eq!(addr.to_evm(), Ok(EVM_ADDRESS));
+        assert_eq!(addr.validate(true), Ok(()));
+        assert_eq!(addr.validate(false), Ok(()));
+
+        let dirty = UniversalAddress(hex!(
+            "0000000000000000000000016ca6d1e2d5347bfab1d91e883f1915560e09129d"
+        ));
+        assert_eq!(dirty.to_evm(), Err(UniversalAddressError::DirtyEvmAddress));
+        assert_eq!(
+            dirty.validate(true),
+            Err(UniversalAddressError::DirtyEvmAddress)
+        );
+        assert_eq!(dirty.validate(false), Ok(()));
+
+        assert_eq!(
+            UniversalAddress::ZERO.validate(true),
+            Err(UniversalAddressError::ZeroAddress)
+        );
+        assert_eq!(
+            UniversalAddress::ZERO.validate(false),
+            Err(UniversalAddressError::ZeroAddress)
+        );
+    }
+
+    #[test]
+    fn test_universal_address_from_str() {
+        let addr = UniversalAddress::from_evm(EVM_ADDRESS);
+        assert_eq!(
+            "0x6ca6d1e2d5347bfab1d91e883f1915560e09129d".parse(),
+            Ok(addr)
+        );
+        assert_eq!(addr.to_string().parse(), Ok(addr));
+        assert_eq!(addr.to_base58().parse(), Ok(addr));
+
+        let solana = solana_program::pubkey::Pubkey::new_unique();
+        assert_eq!(
+            solana.to_string().parse(),
+            Ok(UniversalAddress(solana.to_bytes()))
+        );
+
+        assert_eq!(
+            "0x6ca6d1".parse::<UniversalAddress>(),
+            Err(UniversalAddressError::InvalidEncoding)
+        );
+        assert_eq!(
+            "not base58!".parse::<UniversalAddress>(),
+            Err(UniversalAddressError::InvalidEncoding)
+        );
+    }
+}